@@ -1,13 +1,15 @@
 // Module for analyzing workout data
 use crate::WorkoutEntry;
 use crate::body_parts::body_part_for;
-use crate::exercise_mapping;
+use crate::exercise_mapping::{self, canonical_name};
 use crate::exercise_utils::normalize_exercise;
-use crate::plotting::OneRmFormula;
-use chrono::{Datelike, NaiveDate};
+use crate::plotting::{FormulaChoice, OneRmFormula};
+use crate::store::workout_key;
+use crate::workout::Workout;
+use chrono::{Datelike, NaiveDate, NaiveDateTime, Timelike};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 
 /// Threshold for the acute/chronic workload ratio.
 ///
@@ -131,7 +133,7 @@ pub struct WeeklySummary {
 /// one-rep max is calculated using the provided [`OneRmFormula`].
 pub fn aggregate_exercise_stats(
     entries: &[WorkoutEntry],
-    formula: OneRmFormula,
+    formula: FormulaChoice<'_>,
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
 ) -> HashMap<String, ExerciseStats> {
@@ -143,7 +145,7 @@ pub fn aggregate_exercise_stats(
             if let Some(d) = parse_date(&e.date) {
                 if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
                     let stats = map
-                        .entry(e.exercise.clone())
+                        .entry(canonical_name(&e.exercise))
                         .or_insert_with(ExerciseStats::default);
                     stats.total_sets += 1;
                     stats.total_reps += reps;
@@ -154,7 +156,8 @@ pub fn aggregate_exercise_stats(
                         _ => Some(weight),
                     };
 
-                    let est = match formula.estimate(weight as f64, reps) {
+                    let formula = formula.for_exercise(&e.exercise);
+                    let est = match formula.estimate_set(weight as f64, reps, e.raw.rpe) {
                         Some(v) => v as f32,
                         None => continue,
                     };
@@ -165,11 +168,10 @@ pub fn aggregate_exercise_stats(
 
                     // Scale the time axis so slope represents change per month
                     let t = d.num_days_from_ce() as f32 / 30.0;
-                    trend_data.entry(e.exercise.clone()).or_default().push((
-                        t,
-                        weight,
-                        weight * reps as f32,
-                    ));
+                    trend_data
+                        .entry(canonical_name(&e.exercise))
+                        .or_default()
+                        .push((t, weight, weight * reps as f32));
                 }
             }
         }
@@ -191,21 +193,65 @@ pub fn aggregate_exercise_stats(
     map
 }
 
-/// Count how many sets target each primary body part.
+/// How a set is credited to the muscles an exercise works.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
+pub enum MuscleCredit {
+    /// The whole set counts for the primary muscle only.
+    #[default]
+    PrimaryOnly,
+    /// The primary and each secondary muscle get their share of the set.
+    Weighted { primary: f32, secondary: f32 },
+}
+
+impl MuscleCredit {
+    /// Muscles credited for one set of `exercise` and the share each receives.
+    ///
+    /// Exercises without a primary muscle in the mapping receive no credit.
+    pub fn credits(self, exercise: &str) -> Vec<(String, f64)> {
+        let Some(primary) = body_part_for(exercise) else {
+            return Vec::new();
+        };
+        match self {
+            MuscleCredit::PrimaryOnly => vec![(primary, 1.0)],
+            MuscleCredit::Weighted {
+                primary: p,
+                secondary,
+            } => {
+                let mut credits = vec![(primary.clone(), p as f64)];
+                if secondary > 0.0 {
+                    let mapping = exercise_mapping::get(exercise).unwrap_or_default();
+                    for m in mapping.secondary {
+                        if m != primary && !credits.iter().any(|(c, _)| *c == m) {
+                            credits.push((m, secondary as f64));
+                        }
+                    }
+                }
+                credits
+            }
+        }
+    }
+}
+
+/// Count how many sets target each body part.
 ///
 /// Entries outside the optional date range are ignored. Exercises are mapped
-/// to a body part via [`body_part_for`]. The resulting map uses the body part
-/// name as the key with the number of sets as the value.
+/// to body parts via [`MuscleCredit::credits`], so with weighted credit a set
+/// counts fractionally towards its secondary muscles. The resulting map uses
+/// the body part name as the key with the number of sets as the value.
 pub fn aggregate_sets_by_body_part(
     entries: &[WorkoutEntry],
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
-) -> HashMap<String, usize> {
-    let mut map: HashMap<String, usize> = HashMap::new();
+    credit: MuscleCredit,
+) -> HashMap<String, f64> {
+    let mut map: HashMap<String, f64> = HashMap::new();
     for e in entries {
-        if let (Some(bp), Some(d)) = (body_part_for(&e.exercise), parse_date(&e.date)) {
-            if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                *map.entry(bp).or_insert(0) += 1;
+        if let Some(d) = parse_date(&e.date)
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            for (bp, share) in credit.credits(&e.exercise) {
+                *map.entry(bp).or_insert(0.0) += share;
             }
         }
     }
@@ -248,7 +294,7 @@ pub fn aggregate_rep_counts(
 /// range are considered.
 pub fn personal_records(
     entries: &[WorkoutEntry],
-    formula: OneRmFormula,
+    formula: FormulaChoice<'_>,
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
 ) -> HashMap<String, ExerciseRecord> {
@@ -257,7 +303,7 @@ pub fn personal_records(
         if let (Some(weight), Some(reps)) = (e.weight, e.reps) {
             if let Some(d) = parse_date(&e.date) {
                 if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                    let rec = map.entry(e.exercise.clone()).or_default();
+                    let rec = map.entry(canonical_name(&e.exercise)).or_default();
                     rec.max_weight = match rec.max_weight {
                         Some(w) if w >= weight => Some(w),
                         _ => Some(weight),
@@ -275,7 +321,8 @@ pub fn personal_records(
                             }
                         })
                         .or_insert(weight);
-                    let est = match formula.estimate(weight as f64, reps) {
+                    let formula = formula.for_exercise(&e.exercise);
+                    let est = match formula.estimate_set(weight as f64, reps, e.raw.rpe) {
                         Some(v) => v as f32,
                         None => continue,
                     };
@@ -290,6 +337,628 @@ pub fn personal_records(
     map
 }
 
+/// Rep counts covered by [`rep_max_matrix`].
+pub const REP_MAX_RANGE: std::ops::RangeInclusive<u32> = 1..=20;
+
+/// How far an actual rep max may trail its estimate, as a fraction, before the
+/// rep range is flagged as soft.
+pub const SOFT_REP_MAX_GAP: f32 = 0.05;
+
+/// One rep count of an exercise's rep-max matrix.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RepMaxCell {
+    pub reps: u32,
+    /// Heaviest weight lifted for exactly this many reps, in lbs.
+    pub actual: Option<f32>,
+    /// Date the actual best was lifted.
+    pub date: Option<NaiveDate>,
+    /// Weight predicted for this rep count from the exercise's best estimated
+    /// 1RM, in lbs.
+    pub estimated: Option<f32>,
+    /// The actual best was set before the stale cutoff.
+    pub stale: bool,
+    /// The actual best trails the estimate by more than [`SOFT_REP_MAX_GAP`].
+    pub soft: bool,
+}
+
+/// Actual and estimated rep maxes of `exercise` for 1 to 20 reps.
+///
+/// The estimate inverts `formula` at the best estimated 1RM across all sets in
+/// the range, so every cell is compared with the same strength level. Cells
+/// whose best is older than `stale_before` are flagged stale.
+pub fn rep_max_matrix(
+    entries: &[WorkoutEntry],
+    exercise: &str,
+    formula: FormulaChoice<'_>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    stale_before: Option<NaiveDate>,
+) -> Vec<RepMaxCell> {
+    let formula = formula.for_exercise(exercise);
+    let ex_norm = normalize_exercise(exercise);
+    let mut best: BTreeMap<u32, (f32, NaiveDate)> = BTreeMap::new();
+    let mut best_1rm: Option<f64> = None;
+    for e in entries {
+        if normalize_exercise(&e.exercise) != ex_norm {
+            continue;
+        }
+        if let (Some(weight), Some(reps)) = (e.weight, e.reps)
+            && let Some(d) = parse_date(&e.date)
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            if let Some(est) = formula.estimate_set(weight as f64, reps, e.raw.rpe) {
+                best_1rm = Some(best_1rm.map_or(est, |b| b.max(est)));
+            }
+            let cell = best.entry(reps).or_insert((weight, d));
+            if weight > cell.0 {
+                *cell = (weight, d);
+            }
+        }
+    }
+    REP_MAX_RANGE
+        .map(|reps| {
+            let estimated = best_1rm.and_then(|b| {
+                // An all-out set, so RPE-based estimates read the RPE 10 column.
+                let per_lb = formula.estimate_set(1.0, reps, Some(10.0))?;
+                (per_lb > 0.0).then(|| (b / per_lb) as f32)
+            });
+            let actual = best.get(&reps).copied();
+            RepMaxCell {
+                reps,
+                actual: actual.map(|(w, _)| w),
+                date: actual.map(|(_, d)| d),
+                estimated,
+                stale: actual.is_some_and(|(_, d)| stale_before.is_some_and(|s| d < s)),
+                soft: match (actual, estimated) {
+                    (Some((w, _)), Some(est)) => w < est * (1.0 - SOFT_REP_MAX_GAP),
+                    _ => false,
+                },
+            }
+        })
+        .collect()
+}
+
+/// Rep-based formulas compared by [`calibrate_formulas`].
+pub const REP_FORMULAS: [OneRmFormula; 7] = [
+    OneRmFormula::Epley,
+    OneRmFormula::Brzycki,
+    OneRmFormula::Lombardi,
+    OneRmFormula::Mayhew,
+    OneRmFormula::OConner,
+    OneRmFormula::Wathan,
+    OneRmFormula::Lander,
+];
+
+/// How well one formula predicted an exercise's tested singles.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaFit {
+    pub formula: OneRmFormula,
+    /// Tested singles the formula could predict.
+    pub samples: usize,
+    /// Mean absolute error as a percentage of the tested single.
+    pub mean_abs_error: f32,
+    /// Mean signed error in percent; positive when the formula overestimates.
+    pub bias: f32,
+}
+
+/// Formula errors for one exercise, best fit first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Calibration {
+    pub exercise: String,
+    /// Days with a tested single and sub-maximal sets before it.
+    pub tests: usize,
+    pub fits: Vec<FormulaFit>,
+}
+
+impl Calibration {
+    pub fn best(&self) -> Option<OneRmFormula> {
+        self.fits.first().map(|f| f.formula)
+    }
+}
+
+/// Compare each rep formula against tested singles.
+///
+/// Each day's heaviest non-warmup single is treated as a tested max when it
+/// was logged at RPE 9.5 or above or, without an RPE, matched or beat every
+/// earlier single of the exercise. Lighter openers would otherwise make every
+/// formula look like it overestimates. A test is predicted from the sets of 2
+/// to 12 reps logged in the `weeks` before it, taking the highest estimate as
+/// the prediction. Exercises without any such test are left out.
+pub fn calibrate_formulas(
+    entries: &[WorkoutEntry],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    weeks: u32,
+) -> Vec<Calibration> {
+    // Heaviest single per day with its RPE, if logged.
+    let mut singles: HashMap<String, BTreeMap<NaiveDate, (f32, Option<f32>)>> = HashMap::new();
+    let mut sets: HashMap<String, Vec<(NaiveDate, f32, u32)>> = HashMap::new();
+    for e in entries {
+        let warmup = e
+            .raw
+            .set_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("warmup"));
+        if let (Some(weight), Some(reps), Some(d)) = (e.weight, e.reps, parse_date(&e.date))
+            && weight > 0.0
+            && !warmup
+        {
+            let ex = canonical_name(&e.exercise);
+            if reps == 1 {
+                let best = singles
+                    .entry(ex)
+                    .or_default()
+                    .entry(d)
+                    .or_insert((weight, e.raw.rpe));
+                if weight > best.0 {
+                    *best = (weight, e.raw.rpe);
+                }
+            } else if (2..=12).contains(&reps) {
+                sets.entry(ex).or_default().push((d, weight, reps));
+            }
+        }
+    }
+
+    let mut out = Vec::new();
+    for (exercise, days) in singles {
+        let Some(history) = sets.get(&exercise) else {
+            continue;
+        };
+        let mut running_best = f32::NEG_INFINITY;
+        let mut tests = Vec::new();
+        for (day, (weight, rpe)) in days {
+            let maximal = match rpe {
+                Some(rpe) => rpe >= 9.5,
+                None => weight >= running_best,
+            };
+            running_best = running_best.max(weight);
+            if maximal && start.is_none_or(|s| day >= s) && end.is_none_or(|e2| day <= e2) {
+                tests.push((day, weight));
+            }
+        }
+        let mut errors: Vec<Vec<f32>> = vec![Vec::new(); REP_FORMULAS.len()];
+        let mut used = 0;
+        for (day, tested) in tests {
+            let from = day - chrono::Duration::weeks(weeks as i64);
+            let before: Vec<&(NaiveDate, f32, u32)> = history
+                .iter()
+                .filter(|(d, _, _)| *d >= from && *d < day)
+                .collect();
+            if before.is_empty() {
+                continue;
+            }
+            used += 1;
+            for (i, formula) in REP_FORMULAS.iter().enumerate() {
+                let predicted = before
+                    .iter()
+                    .filter_map(|(_, w, r)| formula.estimate(*w as f64, *r))
+                    .fold(None, |best: Option<f64>, v| {
+                        Some(best.map_or(v, |b| b.max(v)))
+                    });
+                if let Some(p) = predicted {
+                    errors[i].push(((p as f32 - tested) / tested) * 100.0);
+                }
+            }
+        }
+        if used == 0 {
+            continue;
+        }
+        let mut fits: Vec<FormulaFit> = REP_FORMULAS
+            .iter()
+            .zip(errors)
+            .filter(|(_, errs)| !errs.is_empty())
+            .map(|(formula, errs)| {
+                let n = errs.len() as f32;
+                FormulaFit {
+                    formula: *formula,
+                    samples: errs.len(),
+                    mean_abs_error: errs.iter().map(|e| e.abs()).sum::<f32>() / n,
+                    bias: errs.iter().sum::<f32>() / n,
+                }
+            })
+            .collect();
+        fits.sort_by(|a, b| a.mean_abs_error.total_cmp(&b.mean_abs_error));
+        out.push(Calibration {
+            exercise,
+            tests: used,
+            fits,
+        });
+    }
+    out.sort_by(|a, b| a.exercise.cmp(&b.exercise));
+    out
+}
+
+/// Unit of the rolling windows used by [`detect_plateaus`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PlateauWindow {
+    #[default]
+    Weeks,
+    Sessions,
+}
+
+/// Options for [`detect_plateaus`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PlateauSettings {
+    pub window: PlateauWindow,
+    /// Length of each window in weeks or sessions.
+    pub length: u32,
+    /// Drop in best estimated 1RM, in percent, counted as a regression.
+    pub regression_pct: f32,
+}
+
+impl Default for PlateauSettings {
+    fn default() -> Self {
+        Self {
+            window: PlateauWindow::Weeks,
+            length: 4,
+            regression_pct: 5.0,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlateauKind {
+    /// Neither the estimated 1RM nor the best set load went up.
+    Stalled,
+    /// The estimated 1RM fell by more than the regression threshold.
+    Regressed,
+}
+
+impl PlateauKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            PlateauKind::Stalled => "Stalled",
+            PlateauKind::Regressed => "Regressed",
+        }
+    }
+}
+
+/// A stretch of sessions without progress on one exercise.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Plateau {
+    pub exercise: String,
+    pub kind: PlateauKind,
+    /// First and last session in the stretch.
+    pub start: NaiveDate,
+    pub end: NaiveDate,
+    pub sessions: usize,
+    /// Best estimated 1RM in the window before the stretch began.
+    pub reference: f32,
+    /// Best estimated 1RM within the stretch.
+    pub best: f32,
+    /// Whether the stretch runs up to the exercise's latest session.
+    pub ongoing: bool,
+}
+
+impl Plateau {
+    /// Change of `best` relative to `reference` in percent.
+    pub fn change_pct(&self) -> f32 {
+        (self.best / self.reference - 1.0) * 100.0
+    }
+}
+
+/// Find stalls and regressions from rolling windows of each exercise's
+/// sessions.
+///
+/// For every session the window ending there is compared with the window of
+/// the same length before it, using the best estimated 1RM and the heaviest
+/// working set of each. The session is flagged as regressed when the 1RM
+/// dropped by more than `regression_pct`, and as stalled when neither value
+/// went up. Consecutive flagged sessions of the same kind form one
+/// [`Plateau`], which starts at the beginning of its first window or right
+/// after the previous plateau, whichever is later. Warmups are ignored.
+pub fn detect_plateaus(
+    entries: &[WorkoutEntry],
+    formula: FormulaChoice<'_>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    settings: PlateauSettings,
+) -> Vec<Plateau> {
+    let mut days: HashMap<String, BTreeMap<NaiveDate, (f32, f32)>> = HashMap::new();
+    for e in entries {
+        let warmup = e
+            .raw
+            .set_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("warmup"));
+        if let (Some(weight), Some(reps), Some(d)) = (e.weight, e.reps, parse_date(&e.date))
+            && weight > 0.0
+            && !warmup
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+            && let Some(est) =
+                formula
+                    .for_exercise(&e.exercise)
+                    .estimate_set(weight as f64, reps, e.raw.rpe)
+        {
+            let day = days
+                .entry(canonical_name(&e.exercise))
+                .or_default()
+                .entry(d)
+                .or_insert((0.0, 0.0));
+            day.0 = day.0.max(est as f32);
+            day.1 = day.1.max(weight);
+        }
+    }
+
+    let length = settings.length.max(1) as usize;
+    let mut out = Vec::new();
+    for (exercise, by_day) in days {
+        let sessions: Vec<(NaiveDate, f32, f32)> = by_day
+            .into_iter()
+            .map(|(d, (e1, load))| (d, e1, load))
+            .collect();
+        let best = |range: std::ops::Range<usize>| {
+            sessions[range]
+                .iter()
+                .fold((0.0f32, 0.0f32), |(e1, load), s| {
+                    (e1.max(s.1), load.max(s.2))
+                })
+        };
+        // Index of the first session of the current window, and the previous
+        // window's best 1RM, for the run being built.
+        let mut run: Option<(PlateauKind, usize, f32)> = None;
+        // First session not covered by an already closed run.
+        let mut free = 0;
+        let mut close = |run: Option<(PlateauKind, usize, f32)>, last: usize| {
+            if let Some((kind, first, reference)) = run {
+                out.push(Plateau {
+                    exercise: exercise.clone(),
+                    kind,
+                    start: sessions[first].0,
+                    end: sessions[last].0,
+                    sessions: last + 1 - first,
+                    reference,
+                    best: best(first..last + 1).0,
+                    ongoing: last + 1 == sessions.len(),
+                });
+            }
+        };
+        for i in 0..sessions.len() {
+            let (window, previous) = match settings.window {
+                PlateauWindow::Sessions => {
+                    if i + 1 < 2 * length {
+                        (i + 1, 0..0)
+                    } else {
+                        (i + 1 - length, i + 1 - 2 * length..i + 1 - length)
+                    }
+                }
+                PlateauWindow::Weeks => {
+                    let cut = sessions[i].0 - chrono::Duration::weeks(length as i64);
+                    let prev_cut = cut - chrono::Duration::weeks(length as i64);
+                    let w = sessions[..=i].partition_point(|s| s.0 <= cut);
+                    let p = sessions[..w].partition_point(|s| s.0 <= prev_cut);
+                    (w, p..w)
+                }
+            };
+            let kind = if previous.is_empty() {
+                None
+            } else {
+                let (prev_e1, prev_load) = best(previous);
+                let (cur_e1, cur_load) = best(window..i + 1);
+                if cur_e1 < prev_e1 * (1.0 - settings.regression_pct / 100.0) {
+                    Some((PlateauKind::Regressed, prev_e1))
+                } else if cur_e1 <= prev_e1 && cur_load <= prev_load {
+                    Some((PlateauKind::Stalled, prev_e1))
+                } else {
+                    None
+                }
+            };
+            match (run, kind) {
+                (Some((k, _, _)), Some((kind, _))) if k == kind => {}
+                (_, kind) => {
+                    if run.is_some() {
+                        close(run, i - 1);
+                        free = i;
+                    }
+                    run = kind.map(|(kind, reference)| (kind, window.max(free), reference));
+                }
+            }
+        }
+        close(run, sessions.len().saturating_sub(1));
+    }
+    out.sort_by(|a, b| a.exercise.cmp(&b.exercise).then(a.start.cmp(&b.start)));
+    out
+}
+
+/// Kind of personal record set by a [`PrEvent`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PrKind {
+    /// Heaviest weight lifted for any number of reps.
+    Weight,
+    /// Most reps at a weight, beating every earlier set at that weight or
+    /// heavier.
+    Reps,
+    /// Best estimated one-rep max.
+    EstOneRm,
+    /// Highest weight times reps in a single set.
+    SetVolume,
+    /// Most volume for the exercise in one session.
+    SessionVolume,
+}
+
+impl PrKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            PrKind::Weight => "Weight",
+            PrKind::Reps => "Reps",
+            PrKind::EstOneRm => "Est. 1RM",
+            PrKind::SetVolume => "Set Volume",
+            PrKind::SessionVolume => "Session Volume",
+        }
+    }
+}
+
+/// A record set on a given day.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PrEvent {
+    pub date: NaiveDate,
+    /// Canonical exercise name.
+    pub exercise: String,
+    pub kind: PrKind,
+    /// The new best: a weight or volume in lbs, a rep count or an estimated
+    /// 1RM in lbs depending on `kind`.
+    pub value: f32,
+    /// The best before this event, `None` the first time the exercise was
+    /// logged.
+    pub previous: Option<f32>,
+    /// Weight and reps of the set that set the record; `None` for session
+    /// volume.
+    pub weight: Option<f32>,
+    pub reps: Option<u32>,
+}
+
+impl PrEvent {
+    /// Whether the event beat an earlier best rather than recording the first
+    /// session of an exercise.
+    pub fn is_improvement(&self) -> bool {
+        self.previous.is_some()
+    }
+}
+
+#[derive(Default)]
+struct RecordBests {
+    weight: Option<f32>,
+    est_1rm: Option<f32>,
+    set_volume: Option<f32>,
+    session_volume: Option<f32>,
+    /// Most reps at each weight, keyed by hundredths of a pound.
+    reps_at: BTreeMap<i64, u32>,
+}
+
+/// Replace `best` with `value` if it is higher, returning the event fields.
+fn beat(best: &mut Option<f32>, value: f32) -> Option<Option<f32>> {
+    if best.is_none_or(|b| value > b) {
+        Some(best.replace(value))
+    } else {
+        None
+    }
+}
+
+/// Walk the entries in training order and emit a dated event every time an
+/// exercise set a new record.
+///
+/// Sets before `start` count towards the earlier bests, so events in the range
+/// are records at the time they were set; sets after `end` are ignored. The
+/// first set of an exercise records a baseline with `previous` left empty.
+/// When several sets of one session beat the same record only the best is
+/// kept, with `previous` from before the session. Events are returned oldest
+/// first.
+pub fn pr_events(
+    entries: &[WorkoutEntry],
+    formula: FormulaChoice<'_>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> Vec<PrEvent> {
+    let mut sets: Vec<(NaiveDate, String, &WorkoutEntry)> = entries
+        .iter()
+        .filter_map(|e| {
+            let d = parse_date(&e.date)?;
+            end.is_none_or(|e2| d <= e2).then(|| (d, workout_key(e), e))
+        })
+        .collect();
+    sets.sort_by(|a, b| {
+        (a.0, a.2.start, &a.1, a.2.raw.set_index).cmp(&(b.0, b.2.start, &b.1, b.2.raw.set_index))
+    });
+
+    let mut bests: HashMap<String, RecordBests> = HashMap::new();
+    let mut events: Vec<PrEvent> = Vec::new();
+    // Session and kind of each event, used to merge repeats within a session.
+    let mut origins: Vec<(String, Option<i64>)> = Vec::new();
+    let mut push = |events: &mut Vec<PrEvent>, session: &str, key: Option<i64>, ev: PrEvent| {
+        if let Some(i) = (0..events.len()).rev().find(|&i| {
+            events[i].date == ev.date
+                && events[i].exercise == ev.exercise
+                && events[i].kind == ev.kind
+                && origins[i] == (session.to_string(), key)
+        }) {
+            let previous = events[i].previous;
+            events[i] = PrEvent { previous, ..ev };
+        } else {
+            events.push(ev);
+            origins.push((session.to_string(), key));
+        }
+    };
+
+    let mut i = 0;
+    while i < sets.len() {
+        let (date, session) = (sets[i].0, sets[i].1.clone());
+        let mut session_volume: BTreeMap<String, f32> = BTreeMap::new();
+        while i < sets.len() && sets[i].0 == date && sets[i].1 == session {
+            let e = sets[i].2;
+            i += 1;
+            let (Some(weight), Some(reps)) = (e.weight, e.reps) else {
+                continue;
+            };
+            let exercise = canonical_name(&e.exercise);
+            *session_volume.entry(exercise.clone()).or_insert(0.0) += weight * reps as f32;
+            let b = bests.entry(exercise.clone()).or_default();
+            let event = |kind, value, previous| PrEvent {
+                date,
+                exercise: exercise.clone(),
+                kind,
+                value,
+                previous,
+                weight: Some(weight),
+                reps: Some(reps),
+            };
+            let mut found = Vec::new();
+            if let Some(prev) = beat(&mut b.weight, weight) {
+                found.push((None, event(PrKind::Weight, weight, prev)));
+            }
+            let key = (weight as f64 * 100.0).round() as i64;
+            let most_reps = b.reps_at.range(key..).map(|(_, r)| *r).max();
+            if let Some(prev) = most_reps
+                && reps > prev
+            {
+                found.push((
+                    Some(key),
+                    event(PrKind::Reps, reps as f32, Some(prev as f32)),
+                ));
+            }
+            let at = b.reps_at.entry(key).or_insert(0);
+            *at = (*at).max(reps);
+            if let Some(est) =
+                formula
+                    .for_exercise(&exercise)
+                    .estimate_set(weight as f64, reps, e.raw.rpe)
+                && let Some(prev) = beat(&mut b.est_1rm, est as f32)
+            {
+                found.push((None, event(PrKind::EstOneRm, est as f32, prev)));
+            }
+            let volume = weight * reps as f32;
+            if let Some(prev) = beat(&mut b.set_volume, volume) {
+                found.push((None, event(PrKind::SetVolume, volume, prev)));
+            }
+            for (key, ev) in found {
+                push(&mut events, &session, key, ev);
+            }
+        }
+        for (exercise, volume) in session_volume {
+            let b = bests.entry(exercise.clone()).or_default();
+            if let Some(prev) = beat(&mut b.session_volume, volume) {
+                let ev = PrEvent {
+                    date,
+                    exercise,
+                    kind: PrKind::SessionVolume,
+                    value: volume,
+                    previous: prev,
+                    weight: None,
+                    reps: None,
+                };
+                push(&mut events, &session, None, ev);
+            }
+        }
+    }
+    let mut kept: Vec<PrEvent> = events
+        .into_iter()
+        .filter(|ev| start.is_none_or(|s| ev.date >= s))
+        .collect();
+    kept.sort_by_key(|ev| ev.date);
+    kept
+}
+
 /// Compute the average RPE for each date.
 ///
 /// Only entries with an `rpe` value are considered. Sets outside the optional
@@ -368,6 +1037,394 @@ pub fn aggregate_weekly_summary(
     weeks
 }
 
+/// Weekly working-set landmarks for one muscle group.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct VolumeLandmarks {
+    /// Minimum effective volume.
+    pub mev: f32,
+    /// Maximum adaptive volume.
+    pub mav: f32,
+    /// Maximum recoverable volume.
+    pub mrv: f32,
+}
+
+impl Default for VolumeLandmarks {
+    fn default() -> Self {
+        Self {
+            mev: 10.0,
+            mav: 16.0,
+            mrv: 22.0,
+        }
+    }
+}
+
+/// Where a week's set count falls relative to a muscle's landmarks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DoseStatus {
+    /// Fewer sets than the MEV.
+    Under,
+    /// Between MEV and MAV.
+    Productive,
+    /// Between MAV and MRV.
+    High,
+    /// More sets than the MRV.
+    Over,
+}
+
+impl DoseStatus {
+    pub fn label(self) -> &'static str {
+        match self {
+            DoseStatus::Under => "Below MEV",
+            DoseStatus::Productive => "Productive",
+            DoseStatus::High => "Above MAV",
+            DoseStatus::Over => "Above MRV",
+        }
+    }
+}
+
+impl VolumeLandmarks {
+    pub fn status(&self, sets: f64) -> DoseStatus {
+        if sets < self.mev as f64 {
+            DoseStatus::Under
+        } else if sets <= self.mav as f64 {
+            DoseStatus::Productive
+        } else if sets <= self.mrv as f64 {
+            DoseStatus::High
+        } else {
+            DoseStatus::Over
+        }
+    }
+}
+
+/// Hard sets credited to each muscle in one ISO week.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WeeklyMuscleSets {
+    /// ISO week year.
+    pub year: i32,
+    /// ISO week number within the year.
+    pub week: u32,
+    pub sets: BTreeMap<String, f64>,
+}
+
+/// Count hard sets per muscle for every ISO week.
+///
+/// Warmup sets are left out. When `min_rpe` is set, sets logged below that
+/// RPE are left out as well; sets without an RPE are kept. Sets are credited
+/// to muscles via [`MuscleCredit::credits`]. Weeks are returned oldest first.
+pub fn weekly_hard_sets(
+    entries: &[WorkoutEntry],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    min_rpe: Option<f32>,
+    credit: MuscleCredit,
+) -> Vec<WeeklyMuscleSets> {
+    let mut map: BTreeMap<(i32, u32), WeeklyMuscleSets> = BTreeMap::new();
+    for e in entries {
+        let warmup = e
+            .raw
+            .set_type
+            .as_deref()
+            .is_some_and(|t| t.eq_ignore_ascii_case("warmup"));
+        let easy = min_rpe.is_some_and(|min| e.raw.rpe.is_some_and(|r| r < min));
+        if warmup || easy {
+            continue;
+        }
+        if let Some(d) = parse_date(&e.date)
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            let iso = d.iso_week();
+            let week = map
+                .entry((iso.year(), iso.week()))
+                .or_insert_with(|| WeeklyMuscleSets {
+                    year: iso.year(),
+                    week: iso.week(),
+                    sets: BTreeMap::new(),
+                });
+            for (muscle, share) in credit.credits(&e.exercise) {
+                *week.sets.entry(muscle).or_insert(0.0) += share;
+            }
+        }
+    }
+    map.into_values().collect()
+}
+
+/// Part of the day a session started in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TimeOfDay {
+    /// 05:00 to 11:59.
+    Morning,
+    /// 12:00 to 16:59.
+    Afternoon,
+    /// 17:00 to 21:59.
+    Evening,
+    /// 22:00 to 04:59.
+    Night,
+}
+
+impl TimeOfDay {
+    pub fn from_hour(hour: u32) -> Self {
+        match hour {
+            5..=11 => TimeOfDay::Morning,
+            12..=16 => TimeOfDay::Afternoon,
+            17..=21 => TimeOfDay::Evening,
+            _ => TimeOfDay::Night,
+        }
+    }
+}
+
+/// Averages over a group of sessions, such as all morning sessions.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SessionGroupStats {
+    pub sessions: usize,
+    /// Mean session length in minutes.
+    pub avg_minutes: Option<f64>,
+    /// Mean volume per minute in lbs.
+    pub avg_density: Option<f64>,
+    pub avg_sets_per_hour: Option<f64>,
+    /// Mean estimated 1RM of the group's sets relative to each exercise's
+    /// mean over all sessions considered, so 1.05 means 5% stronger than
+    /// usual.
+    pub relative_strength: Option<f64>,
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Group sessions with a known start time by `key` and average their
+/// duration, density, pace and relative strength.
+///
+/// Relative strength compares each working set's estimated 1RM with the
+/// exercise's mean across every session in the range, which lets lifts of
+/// different size be averaged together. Warmup sets are left out.
+fn session_performance<K: Ord>(
+    workouts: &[Workout],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    formula: FormulaChoice<'_>,
+    key: impl Fn(NaiveDateTime) -> K,
+) -> BTreeMap<K, SessionGroupStats> {
+    let sessions: Vec<(&Workout, NaiveDateTime)> = workouts
+        .iter()
+        .filter_map(|w| {
+            let d = parse_date(&w.date)?;
+            (start.is_none_or(|s| d >= s) && end.is_none_or(|e| d <= e)).then_some((w, w.start?))
+        })
+        .collect();
+    let working_1rms = |w: &Workout| -> Vec<(String, f64)> {
+        w.exercises
+            .iter()
+            .flat_map(|x| &x.sets)
+            .filter(|e| {
+                !e.raw
+                    .set_type
+                    .as_deref()
+                    .is_some_and(|t| t.eq_ignore_ascii_case("warmup"))
+            })
+            .filter_map(|e| {
+                let one_rm = formula.for_exercise(&e.exercise).estimate_set(
+                    e.weight? as f64,
+                    e.reps?,
+                    e.raw.rpe,
+                )?;
+                (one_rm > 0.0).then(|| (canonical_name(&e.exercise), one_rm))
+            })
+            .collect()
+    };
+    let mut totals: HashMap<String, (f64, usize)> = HashMap::new();
+    for (w, _) in &sessions {
+        for (ex, one_rm) in working_1rms(w) {
+            let t = totals.entry(ex).or_insert((0.0, 0));
+            t.0 += one_rm;
+            t.1 += 1;
+        }
+    }
+
+    #[derive(Default)]
+    struct Samples {
+        sessions: usize,
+        minutes: Vec<f64>,
+        density: Vec<f64>,
+        sets_per_hour: Vec<f64>,
+        strength: Vec<f64>,
+    }
+    let mut groups: BTreeMap<K, Samples> = BTreeMap::new();
+    for (w, started) in sessions {
+        let g = groups.entry(key(started)).or_default();
+        g.sessions += 1;
+        g.minutes.extend(w.minutes());
+        g.density.extend(w.density());
+        g.sets_per_hour.extend(w.sets_per_hour());
+        for (ex, one_rm) in working_1rms(w) {
+            let (sum, count) = totals[&ex];
+            g.strength.push(one_rm / (sum / count as f64));
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(k, g)| {
+            let stats = SessionGroupStats {
+                sessions: g.sessions,
+                avg_minutes: mean(&g.minutes),
+                avg_density: mean(&g.density),
+                avg_sets_per_hour: mean(&g.sets_per_hour),
+                relative_strength: mean(&g.strength),
+            };
+            (k, stats)
+        })
+        .collect()
+}
+
+/// [`SessionGroupStats`] for morning, afternoon, evening and night sessions.
+pub fn performance_by_time_of_day(
+    workouts: &[Workout],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    formula: FormulaChoice<'_>,
+) -> BTreeMap<TimeOfDay, SessionGroupStats> {
+    session_performance(workouts, start, end, formula, |t| {
+        TimeOfDay::from_hour(t.hour())
+    })
+}
+
+/// [`SessionGroupStats`] per weekday, keyed by days from Monday.
+pub fn performance_by_weekday(
+    workouts: &[Workout],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    formula: FormulaChoice<'_>,
+) -> BTreeMap<u32, SessionGroupStats> {
+    session_performance(workouts, start, end, formula, |t| {
+        t.weekday().num_days_from_monday()
+    })
+}
+
+/// Value each day of the training calendar is colored by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum CalendarMetric {
+    /// Total weight times reps in lbs.
+    #[default]
+    Volume,
+    Sets,
+    Sessions,
+}
+
+/// Sum `metric` for every training day in the optional range.
+pub fn daily_totals(
+    entries: &[WorkoutEntry],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    metric: CalendarMetric,
+) -> BTreeMap<NaiveDate, f64> {
+    let mut totals: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    let mut sessions: HashMap<NaiveDate, HashSet<String>> = HashMap::new();
+    for e in entries {
+        if let Some(d) = parse_date(&e.date)
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            let value = match metric {
+                CalendarMetric::Volume => e.volume().unwrap_or(0.0) as f64,
+                CalendarMetric::Sets => 1.0,
+                CalendarMetric::Sessions => {
+                    let new = sessions.entry(d).or_default().insert(workout_key(e));
+                    if new { 1.0 } else { 0.0 }
+                }
+            };
+            *totals.entry(d).or_insert(0.0) += value;
+        }
+    }
+    totals
+}
+
+/// Weekly consistency measured against a target number of sessions.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConsistencyStats {
+    /// Consecutive weeks meeting the target up to now. The current week only
+    /// counts once the target is reached, but does not break the streak
+    /// before then.
+    pub current_streak: usize,
+    pub longest_streak: usize,
+    /// Weeks from the first session to the current week, including weeks
+    /// without any training. The current week is left out until it meets
+    /// the target.
+    pub weeks: usize,
+    pub weeks_on_target: usize,
+    pub sessions: usize,
+}
+
+impl ConsistencyStats {
+    /// Share of weeks that met the target.
+    pub fn adherence(&self) -> f32 {
+        if self.weeks == 0 {
+            0.0
+        } else {
+            self.weeks_on_target as f32 / self.weeks as f32
+        }
+    }
+
+    pub fn sessions_per_week(&self) -> f32 {
+        if self.weeks == 0 {
+            0.0
+        } else {
+            self.sessions as f32 / self.weeks as f32
+        }
+    }
+}
+
+/// Count sessions per ISO week and derive streaks and adherence against
+/// `target` sessions per week.
+///
+/// Weeks run from the first session in the range to the week containing
+/// `today`, or `end` if that is earlier. The week containing `today` may
+/// still be under way, so it neither breaks the streak nor counts towards
+/// `weeks` until it has met the target.
+pub fn consistency(
+    entries: &[WorkoutEntry],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    target: u32,
+    today: NaiveDate,
+) -> ConsistencyStats {
+    let target = target.max(1) as f64;
+    let days = daily_totals(entries, start, end, CalendarMetric::Sessions);
+    let Some(first) = days.keys().next().copied() else {
+        return ConsistencyStats::default();
+    };
+    let monday =
+        |d: NaiveDate| d - chrono::Duration::days(d.weekday().num_days_from_monday() as i64);
+    let current = monday(today);
+    let last = monday(end.map_or(today, |e| e.min(today)));
+    let mut per_week: BTreeMap<NaiveDate, f64> = BTreeMap::new();
+    for (d, n) in &days {
+        *per_week.entry(monday(*d)).or_insert(0.0) += n;
+    }
+
+    let mut stats = ConsistencyStats {
+        sessions: days.values().sum::<f64>() as usize,
+        ..ConsistencyStats::default()
+    };
+    let mut run = 0;
+    let mut week = monday(first);
+    while week <= last {
+        let met = per_week.get(&week).is_some_and(|n| *n >= target);
+        if met || week != current {
+            stats.weeks += 1;
+        }
+        if met {
+            stats.weeks_on_target += 1;
+            run += 1;
+            stats.longest_streak = stats.longest_streak.max(run);
+        } else if week != current {
+            run = 0;
+        }
+        week += chrono::Duration::weeks(1);
+    }
+    stats.current_streak = run;
+    stats
+}
+
 fn parse_date(date: &str) -> Option<NaiveDate> {
     NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
 }
@@ -408,6 +1465,8 @@ pub fn format_load_message(entries: usize, filename: &str) -> String {
 /// Compute overall statistics for the loaded workout entries.
 ///
 /// Only entries within the optional `start` and `end` date range are included.
+/// Every imported set counts towards the workout and set totals, including
+/// timed, distance and reps-only sets; the rep average covers sets with reps.
 /// If no valid workout dates are found an empty [`BasicStats`] is returned.
 pub fn compute_stats(
     entries: &[WorkoutEntry],
@@ -425,22 +1484,22 @@ pub fn compute_stats(
     // Track the date of each unique workout for gap calculations
     let mut workout_dates: HashMap<String, NaiveDate> = HashMap::new();
     let mut total_reps = 0u32;
-    let mut exercise_counts: HashMap<&str, usize> = HashMap::new();
+    let mut rep_sets = 0usize;
+    let mut exercise_counts: HashMap<String, usize> = HashMap::new();
 
     for e in entries {
-        if let (Some(_weight), Some(reps)) = (e.weight, e.reps) {
-            if let Some(d) = parse_date(&e.date) {
-                if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                    let id = format!(
-                        "{}{}",
-                        e.raw.title.as_deref().unwrap_or(""),
-                        e.raw.start_time
-                    );
-                    *sets_per_workout.entry(id.clone()).or_insert(0) += 1;
-                    workout_dates.entry(id).or_insert(d);
+        if let Some(d) = parse_date(&e.date) {
+            if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
+                let id = workout_key(e);
+                *sets_per_workout.entry(id.clone()).or_insert(0) += 1;
+                workout_dates.entry(id).or_insert(d);
+                if let Some(reps) = e.reps {
                     total_reps += reps;
-                    *exercise_counts.entry(e.exercise.as_str()).or_insert(0) += 1;
+                    rep_sets += 1;
                 }
+                *exercise_counts
+                    .entry(canonical_name(&e.exercise))
+                    .or_insert(0) += 1;
             }
         }
     }
@@ -454,7 +1513,11 @@ pub fn compute_stats(
     }
 
     let avg_sets_per_workout = total_sets as f32 / total_workouts as f32;
-    let avg_reps_per_set = total_reps as f32 / total_sets as f32;
+    let avg_reps_per_set = if rep_sets > 0 {
+        total_reps as f32 / rep_sets as f32
+    } else {
+        0.0
+    };
 
     // Days between workouts
     let mut dates: Vec<NaiveDate> = workout_dates.values().cloned().collect();
@@ -474,7 +1537,7 @@ pub fn compute_stats(
     let most_common_exercise = exercise_counts
         .into_iter()
         .max_by_key(|(_, c)| *c)
-        .map(|(ex, _)| ex.to_string());
+        .map(|(ex, _)| ex);
 
     BasicStats {
         total_workouts,
@@ -511,6 +1574,7 @@ pub fn unique_superset_ids(entries: &[WorkoutEntry]) -> Vec<String> {
 mod tests {
     use super::*;
     use crate::RawWorkoutRow;
+    use crate::test_util::set_entry;
 
     fn sample_entries() -> Vec<WorkoutEntry> {
         vec![
@@ -519,6 +1583,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     title: Some("Workout 1".into()),
                     start_time: "01 Jan 2024, 10:00".into(),
@@ -531,6 +1596,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(80.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     title: Some("Workout 1".into()),
                     start_time: "01 Jan 2024, 10:00".into(),
@@ -543,6 +1609,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(105.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     title: Some("Workout 2".into()),
                     start_time: "03 Jan 2024, 10:00".into(),
@@ -555,6 +1622,7 @@ mod tests {
                 exercise: "Deadlift".into(),
                 weight: Some(120.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     title: Some("Workout 3".into()),
                     start_time: "05 Jan 2024, 10:00".into(),
@@ -572,6 +1640,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -579,6 +1648,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(80.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ]
@@ -596,6 +1666,35 @@ mod tests {
         assert_eq!(stats.most_common_exercise.as_deref(), Some("Squat"));
     }
 
+    #[test]
+    fn test_compute_stats_counts_sets_without_weight() {
+        let mut entries = sample_entries();
+        let mut plank = entries[0].clone();
+        plank.exercise = "Plank".into();
+        plank.weight = None;
+        plank.reps = None;
+        plank.raw.duration_seconds = Some(60.0);
+        let mut pull_up = entries[0].clone();
+        pull_up.exercise = "Pull Up".into();
+        pull_up.weight = None;
+        pull_up.reps = Some(8);
+        let mut walk = entries[0].clone();
+        walk.exercise = "Walking".into();
+        walk.weight = None;
+        walk.reps = None;
+        walk.date = "2024-01-09".into();
+        walk.raw.workout_id = Some("walk".into());
+        walk.raw.distance_miles = Some(2.0);
+        entries.extend([plank, pull_up, walk]);
+
+        let stats = compute_stats(&entries, None, None);
+        assert_eq!(stats.total_workouts, 4);
+        assert!((stats.avg_sets_per_workout - 7.0 / 4.0).abs() < 1e-6);
+        // Four 5-rep sets and one of 8; timed and distance sets have no reps.
+        assert!((stats.avg_reps_per_set - 28.0 / 5.0).abs() < 1e-6);
+        assert!((stats.avg_days_between - 8.0 / 3.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_invalid_dates_safe_stats() {
         let entries = invalid_date_entries();
@@ -618,7 +1717,7 @@ mod tests {
     #[test]
     fn test_aggregate_exercise_stats() {
         let entries = sample_entries();
-        let map = aggregate_exercise_stats(&entries, OneRmFormula::Epley, None, None);
+        let map = aggregate_exercise_stats(&entries, OneRmFormula::Epley.into(), None, None);
 
         let squat = map.get("Squat").unwrap();
         assert_eq!(squat.total_sets, 2);
@@ -647,6 +1746,22 @@ mod tests {
         assert_eq!(deadlift.volume_trend.unwrap(), 0.0);
     }
 
+    #[test]
+    fn test_aggregate_exercise_stats_merges_aliases() {
+        let mut entries = sample_entries();
+        let squat = entries.iter_mut().find(|e| e.exercise == "Squat").unwrap();
+        squat.exercise = "Sqaut (Old Name)".into();
+        exercise_mapping::set_alias("Sqaut (Old Name)", "Squat");
+
+        let map = aggregate_exercise_stats(&entries, OneRmFormula::Epley.into(), None, None);
+        let records = personal_records(&entries, OneRmFormula::Epley.into(), None, None);
+        exercise_mapping::remove_alias("Sqaut (Old Name)");
+
+        assert!(!map.contains_key("Sqaut (Old Name)"));
+        assert_eq!(map.get("Squat").unwrap().total_sets, 2);
+        assert!((records.get("Squat").unwrap().max_weight.unwrap() - 105.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_aggregate_exercise_stats_formulas() {
         let entries = vec![
@@ -655,6 +1770,7 @@ mod tests {
                 exercise: "Test".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -662,6 +1778,7 @@ mod tests {
                 exercise: "Test".into(),
                 weight: Some(60.0),
                 reps: Some(20),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -673,7 +1790,7 @@ mod tests {
             OneRmFormula::Wathan,
             OneRmFormula::Lander,
         ] {
-            let map = aggregate_exercise_stats(&entries, formula, None, None);
+            let map = aggregate_exercise_stats(&entries, formula.into(), None, None);
             let stats = map.get("Test").unwrap();
             let est1 = formula.estimate(100.0, 5).unwrap();
             let est2 = formula.estimate(60.0, 20).unwrap();
@@ -690,9 +1807,10 @@ mod tests {
             exercise: "T".into(),
             weight: Some(40.0),
             reps: Some(37),
+            start: None,
             raw: RawWorkoutRow::default(),
         }];
-        let map = aggregate_exercise_stats(&entries, OneRmFormula::Brzycki, None, None);
+        let map = aggregate_exercise_stats(&entries, OneRmFormula::Brzycki.into(), None, None);
         assert!(map.get("T").unwrap().best_est_1rm.is_none());
 
         // Lander: reps beyond ~37.9 invalid, mixed with valid 37
@@ -702,6 +1820,7 @@ mod tests {
                 exercise: "T".into(),
                 weight: Some(40.0),
                 reps: Some(37),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -709,10 +1828,11 @@ mod tests {
                 exercise: "T".into(),
                 weight: Some(40.0),
                 reps: Some(38),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
-        let map = aggregate_exercise_stats(&entries, OneRmFormula::Lander, None, None);
+        let map = aggregate_exercise_stats(&entries, OneRmFormula::Lander.into(), None, None);
         let stats = map.get("T").unwrap();
         let expected = OneRmFormula::Lander.estimate(40.0, 37).unwrap() as f32;
         assert!((stats.best_est_1rm.unwrap() - expected).abs() < 1e-6);
@@ -723,9 +1843,10 @@ mod tests {
             exercise: "T".into(),
             weight: Some(40.0),
             reps: Some(38),
+            start: None,
             raw: RawWorkoutRow::default(),
         }];
-        let map = aggregate_exercise_stats(&entries, OneRmFormula::Lander, None, None);
+        let map = aggregate_exercise_stats(&entries, OneRmFormula::Lander.into(), None, None);
         assert!(map.get("T").unwrap().best_est_1rm.is_none());
     }
 
@@ -741,10 +1862,41 @@ mod tests {
     fn test_aggregate_sets_by_body_part() {
         exercise_mapping::load();
         let entries = sample_entries();
-        let map = aggregate_sets_by_body_part(&entries, None, None);
-        assert_eq!(map.get("Quads"), Some(&2));
-        assert_eq!(map.get("Chest"), Some(&1));
-        assert_eq!(map.get("Back"), Some(&1));
+        let map = aggregate_sets_by_body_part(&entries, None, None, MuscleCredit::PrimaryOnly);
+        assert_eq!(map.get("Quads"), Some(&2.0));
+        assert_eq!(map.get("Chest"), Some(&1.0));
+        assert_eq!(map.get("Back"), Some(&1.0));
+    }
+
+    #[test]
+    fn test_aggregate_sets_by_body_part_weighted() {
+        exercise_mapping::set(
+            "Credit Test Press".into(),
+            exercise_mapping::MuscleMapping {
+                primary: "Chest".into(),
+                secondary: vec!["Triceps".into(), "Shoulders".into()],
+                category: String::new(),
+            },
+        );
+        let mut entries = sample_entries();
+        entries.truncate(2);
+        for e in &mut entries {
+            e.exercise = "Credit Test Press".into();
+        }
+        let weighted = MuscleCredit::Weighted {
+            primary: 1.0,
+            secondary: 0.5,
+        };
+        let map = aggregate_sets_by_body_part(&entries, None, None, weighted);
+        let primary_only =
+            aggregate_sets_by_body_part(&entries, None, None, MuscleCredit::PrimaryOnly);
+        exercise_mapping::remove("Credit Test Press");
+
+        assert_eq!(map.get("Chest"), Some(&2.0));
+        assert_eq!(map.get("Triceps"), Some(&1.0));
+        assert_eq!(map.get("Shoulders"), Some(&1.0));
+        assert_eq!(primary_only.len(), 1);
+        assert_eq!(primary_only.get("Chest"), Some(&2.0));
     }
 
     #[test]
@@ -752,9 +1904,9 @@ mod tests {
         exercise_mapping::load();
         let entries = sample_entries();
         let start = NaiveDate::parse_from_str("2024-01-03", "%Y-%m-%d").ok();
-        let map = aggregate_sets_by_body_part(&entries, start, None);
-        assert_eq!(map.get("Quads"), Some(&1));
-        assert_eq!(map.get("Back"), Some(&1));
+        let map = aggregate_sets_by_body_part(&entries, start, None, MuscleCredit::PrimaryOnly);
+        assert_eq!(map.get("Quads"), Some(&1.0));
+        assert_eq!(map.get("Back"), Some(&1.0));
         assert!(map.get("Chest").is_none());
     }
 
@@ -798,7 +1950,7 @@ mod tests {
     #[test]
     fn test_personal_records() {
         let entries = sample_entries();
-        let map = personal_records(&entries, OneRmFormula::Epley, None, None);
+        let map = personal_records(&entries, OneRmFormula::Epley.into(), None, None);
 
         let squat = map.get("Squat").unwrap();
         assert!((squat.max_weight.unwrap() - 105.0).abs() < 1e-6);
@@ -826,6 +1978,7 @@ mod tests {
                 exercise: "Test".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -833,6 +1986,7 @@ mod tests {
                 exercise: "Test".into(),
                 weight: Some(60.0),
                 reps: Some(20),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -844,7 +1998,7 @@ mod tests {
             OneRmFormula::Wathan,
             OneRmFormula::Lander,
         ] {
-            let map = personal_records(&entries, formula, None, None);
+            let map = personal_records(&entries, formula.into(), None, None);
             let rec = map.get("Test").unwrap();
             let est1 = formula.estimate(100.0, 5).unwrap();
             let est2 = formula.estimate(60.0, 20).unwrap();
@@ -861,9 +2015,10 @@ mod tests {
             exercise: "T".into(),
             weight: Some(40.0),
             reps: Some(37),
+            start: None,
             raw: RawWorkoutRow::default(),
         }];
-        let map = personal_records(&entries, OneRmFormula::Brzycki, None, None);
+        let map = personal_records(&entries, OneRmFormula::Brzycki.into(), None, None);
         assert!(map.get("T").unwrap().best_est_1rm.is_none());
 
         // Lander: invalid 38 rep should be ignored in favor of valid 37
@@ -873,6 +2028,7 @@ mod tests {
                 exercise: "T".into(),
                 weight: Some(40.0),
                 reps: Some(37),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -880,10 +2036,11 @@ mod tests {
                 exercise: "T".into(),
                 weight: Some(40.0),
                 reps: Some(38),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
-        let map = personal_records(&entries, OneRmFormula::Lander, None, None);
+        let map = personal_records(&entries, OneRmFormula::Lander.into(), None, None);
         let rec = map.get("T").unwrap();
         let expected = OneRmFormula::Lander.estimate(40.0, 37).unwrap() as f32;
         assert!((rec.best_est_1rm.unwrap() - expected).abs() < 1e-6);
@@ -894,9 +2051,10 @@ mod tests {
             exercise: "T".into(),
             weight: Some(40.0),
             reps: Some(38),
+            start: None,
             raw: RawWorkoutRow::default(),
         }];
-        let map = personal_records(&entries, OneRmFormula::Lander, None, None);
+        let map = personal_records(&entries, OneRmFormula::Lander.into(), None, None);
         assert!(map.get("T").unwrap().best_est_1rm.is_none());
     }
 
@@ -930,6 +2088,357 @@ mod tests {
         assert!(!w.over_threshold);
     }
 
+    #[test]
+    fn test_weekly_hard_sets() {
+        exercise_mapping::load();
+        let mut entries = sample_entries();
+        entries[0].raw.set_type = Some("warmup".into());
+        entries[1].raw.rpe = Some(6.0);
+
+        let weeks = weekly_hard_sets(&entries, None, None, None, MuscleCredit::PrimaryOnly);
+        assert_eq!(weeks.len(), 1);
+        assert_eq!((weeks[0].year, weeks[0].week), (2024, 1));
+        assert_eq!(weeks[0].sets.get("Quads"), Some(&1.0));
+        assert_eq!(weeks[0].sets.get("Chest"), Some(&1.0));
+
+        let hard = weekly_hard_sets(&entries, None, None, Some(7.0), MuscleCredit::PrimaryOnly);
+        assert!(!hard[0].sets.contains_key("Chest"));
+        assert_eq!(hard[0].sets.get("Back"), Some(&1.0));
+    }
+
+    #[test]
+    fn test_volume_landmark_status() {
+        let l = VolumeLandmarks::default();
+        assert_eq!(l.status(4.0), DoseStatus::Under);
+        assert_eq!(l.status(10.0), DoseStatus::Productive);
+        assert_eq!(l.status(18.5), DoseStatus::High);
+        assert_eq!(l.status(23.0), DoseStatus::Over);
+    }
+
+    fn timed_set(start: &str, end: &str, weight: f32) -> WorkoutEntry {
+        let mut e = set_entry("Squat", start, weight, 5);
+        e.raw.title = Some("Legs".into());
+        e.raw.end_time = Some(end.into());
+        e
+    }
+
+    #[test]
+    fn test_performance_by_time_of_day_and_weekday() {
+        let entries = vec![
+            timed_set("01 Jan 2024, 07:00", "01 Jan 2024, 08:00", 100.0),
+            timed_set("03 Jan 2024, 18:00", "03 Jan 2024, 18:30", 120.0),
+            timed_set("08 Jan 2024, 07:00", "08 Jan 2024, 08:00", 100.0),
+        ];
+        let workouts = crate::workout::build_workouts(&entries);
+
+        let by_time = performance_by_time_of_day(&workouts, None, None, OneRmFormula::Epley.into());
+        assert_eq!(
+            by_time.keys().copied().collect::<Vec<_>>(),
+            vec![TimeOfDay::Morning, TimeOfDay::Evening]
+        );
+        let morning = &by_time[&TimeOfDay::Morning];
+        assert_eq!(morning.sessions, 2);
+        assert_eq!(morning.avg_minutes, Some(60.0));
+        assert!((morning.avg_density.unwrap() - 500.0 / 60.0).abs() < 1e-9);
+        assert_eq!(morning.avg_sets_per_hour, Some(1.0));
+        let evening = &by_time[&TimeOfDay::Evening];
+        assert!((evening.avg_density.unwrap() - 20.0).abs() < 1e-9);
+        // Morning sets average 100 lb against a 106.7 lb overall mean.
+        assert!((morning.relative_strength.unwrap() - 0.9375).abs() < 1e-9);
+        assert!((evening.relative_strength.unwrap() - 1.125).abs() < 1e-9);
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 2);
+        let by_day = performance_by_weekday(&workouts, start, None, OneRmFormula::Epley.into());
+        assert_eq!(by_day.keys().copied().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(by_day[&0].sessions, 1);
+        assert_eq!(by_day[&2].relative_strength, Some(120.0 / 110.0));
+    }
+
+    #[test]
+    fn test_daily_totals_and_consistency() {
+        let session = |date: &str, time: &str| {
+            let d = NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+            let start = format!("{}, {time}", d.format("%d %b %Y"));
+            timed_set(&start, &start, 100.0)
+        };
+        let entries = vec![
+            session("2024-01-01", "07:00"),
+            session("2024-01-01", "07:00"),
+            session("2024-01-03", "07:00"),
+            session("2024-01-10", "07:00"),
+            session("2024-01-22", "07:00"),
+            session("2024-01-24", "18:00"),
+            session("2024-01-29", "07:00"),
+        ];
+        let jan1 = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let sets = daily_totals(&entries, None, None, CalendarMetric::Sets);
+        assert_eq!(sets[&jan1], 2.0);
+        let sessions = daily_totals(&entries, None, None, CalendarMetric::Sessions);
+        assert_eq!(sessions[&jan1], 1.0);
+        assert_eq!(sessions.len(), 6);
+        let volume = daily_totals(&entries, None, None, CalendarMetric::Volume);
+        assert_eq!(volume[&jan1], 1000.0);
+
+        // The current week is short of the target but neither ends the
+        // streak nor counts against adherence.
+        let today = NaiveDate::from_ymd_opt(2024, 1, 30).unwrap();
+        let c = consistency(&entries, None, None, 2, today);
+        assert_eq!(c.weeks, 4);
+        assert_eq!(c.sessions, 6);
+        assert_eq!(c.weeks_on_target, 2);
+        assert_eq!(c.current_streak, 1);
+        assert_eq!(c.longest_streak, 1);
+        assert!((c.adherence() - 0.5).abs() < 1e-6);
+
+        // Once it meets the target the current week counts.
+        let c = consistency(&entries, None, None, 1, today);
+        assert_eq!(c.weeks, 5);
+        assert_eq!(c.weeks_on_target, 4);
+        assert_eq!(c.current_streak, 2);
+        assert_eq!(c.longest_streak, 2);
+
+        // A finished week at the end of a past range that missed the target
+        // counts and ends the streak.
+        let end = NaiveDate::from_ymd_opt(2024, 1, 21);
+        let c = consistency(&entries, None, end, 1, today);
+        assert_eq!(c.weeks, 3);
+        assert_eq!(c.weeks_on_target, 2);
+        assert_eq!(c.current_streak, 0);
+        assert_eq!(c.longest_streak, 2);
+    }
+
+    #[test]
+    fn test_pr_events() {
+        let set = |date: &str, weight: f32, reps: u32, idx: u32| {
+            let mut e = set_entry("Bench", date, weight, reps);
+            e.raw.title = Some("Push".into());
+            e.raw.set_index = Some(idx);
+            e
+        };
+        let entries = vec![
+            set("2024-01-01", 100.0, 5, 0),
+            set("2024-01-01", 100.0, 5, 1),
+            // Two heavier sets in one session merge into a single weight PR.
+            set("2024-01-08", 105.0, 3, 0),
+            set("2024-01-08", 110.0, 2, 1),
+            // Six reps at 100 beats the five done at 100 or more before.
+            set("2024-01-15", 100.0, 6, 0),
+        ];
+        let events = pr_events(&entries, OneRmFormula::Epley.into(), None, None);
+        let of = |kind| -> Vec<&PrEvent> { events.iter().filter(|e| e.kind == kind).collect() };
+
+        let weight = of(PrKind::Weight);
+        assert_eq!(weight.len(), 2);
+        assert!(!weight[0].is_improvement());
+        assert_eq!(weight[1].value, 110.0);
+        assert_eq!(weight[1].previous, Some(100.0));
+        assert_eq!(weight[1].reps, Some(2));
+
+        let reps = of(PrKind::Reps);
+        assert_eq!(reps.len(), 1);
+        assert_eq!(reps[0].value, 6.0);
+        assert_eq!(reps[0].previous, Some(5.0));
+        assert_eq!(reps[0].weight, Some(100.0));
+
+        let session = of(PrKind::SessionVolume);
+        assert_eq!(
+            session.iter().map(|e| e.value).collect::<Vec<_>>(),
+            vec![1000.0]
+        );
+        assert_eq!(of(PrKind::EstOneRm).len(), 3);
+
+        // Earlier sets still count as the bests to beat.
+        let start = NaiveDate::from_ymd_opt(2024, 1, 8);
+        let later = pr_events(&entries, OneRmFormula::Epley.into(), start, None);
+        assert!(later.iter().all(|e| e.is_improvement()));
+        assert!(later.iter().all(|e| e.kind != PrKind::SessionVolume));
+    }
+
+    #[test]
+    fn test_rep_max_matrix() {
+        let set = |date: &str, weight: f32, reps: u32| set_entry("Squat", date, weight, reps);
+        let entries = vec![
+            set("2023-01-01", 280.0, 1),
+            set("2024-01-01", 240.0, 5),
+            set("2024-02-01", 250.0, 5),
+            set("2024-02-01", 150.0, 10),
+        ];
+        let cutoff = NaiveDate::from_ymd_opt(2023, 6, 1);
+        let matrix = rep_max_matrix(
+            &entries,
+            "squat",
+            OneRmFormula::Epley.into(),
+            None,
+            None,
+            cutoff,
+        );
+        assert_eq!(matrix.len(), 20);
+        // Best 1RM is 250 x 5 -> 291.7, so a single is estimated at ~282.3.
+        let single = &matrix[0];
+        assert_eq!(single.actual, Some(280.0));
+        assert!((single.estimated.unwrap() - 282.26).abs() < 0.01);
+        assert!(single.stale);
+        assert!(!single.soft);
+        let five = &matrix[4];
+        assert_eq!(five.actual, Some(250.0));
+        assert_eq!(five.date, NaiveDate::from_ymd_opt(2024, 2, 1));
+        assert!(!five.stale);
+        assert!(!five.soft);
+        // 150 x 10 is far below the 218.75 estimate.
+        assert!(matrix[9].soft);
+        assert_eq!(matrix[2].actual, None);
+        assert!(!matrix[2].soft);
+    }
+
+    #[test]
+    fn test_rpe_formula_uses_rpe_with_fallback() {
+        let mut entries = sample_entries();
+        // 100 x 5 @ 8 is 81.1% of a 123.3 1RM; without an RPE Epley gives
+        // 116.7 for the same set.
+        entries[0].raw.rpe = Some(8.0);
+        entries[2].raw.rpe = None;
+        let recs = personal_records(&entries, OneRmFormula::Rpe.into(), None, None);
+        let squat = recs["Squat"].best_est_1rm.unwrap();
+        assert!((squat - 100.0 / 0.811).abs() < 0.01);
+        let stats = aggregate_exercise_stats(&entries, OneRmFormula::Rpe.into(), None, None);
+        assert!((stats["Squat"].best_est_1rm.unwrap() - squat).abs() < 1e-6);
+
+        let epley = personal_records(&entries, OneRmFormula::Epley.into(), None, None);
+        let expected = OneRmFormula::Epley.estimate(105.0, 5).unwrap() as f32;
+        assert!((epley["Squat"].best_est_1rm.unwrap() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_calibrate_formulas_against_singles() {
+        let mut entries = sample_entries();
+        let single = |date: &str, exercise: &str, weight: f32| {
+            let mut e = entries[0].clone();
+            e.date = date.into();
+            e.exercise = exercise.into();
+            e.weight = Some(weight);
+            e.reps = Some(1);
+            e.raw.rpe = None;
+            e
+        };
+        // Epley puts 105 x 5 at exactly 122.5.
+        let tested = single("2024-01-08", "Squat", 122.5);
+        // Sub-maximal: logged at RPE 8, or lighter than an earlier single.
+        let mut easy = single("2024-01-09", "Squat", 122.5);
+        easy.raw.rpe = Some(8.0);
+        let opener = single("2024-01-10", "Squat", 100.0);
+        // Too long after the last set of 2-12 reps.
+        let late = single("2024-03-01", "Squat", 130.0);
+        // No earlier sets to predict from.
+        let press = single("2024-01-08", "Overhead Press", 60.0);
+        // A light single counts once it is logged as a near-max effort.
+        let mut hard = single("2024-01-11", "Squat", 100.0);
+        hard.raw.rpe = Some(10.0);
+        entries.extend([tested, easy, opener, late, press]);
+
+        let cals = calibrate_formulas(&entries, None, None, 2);
+        assert_eq!(cals.len(), 1);
+        let squat = &cals[0];
+        assert_eq!(squat.exercise, "Squat");
+        assert_eq!(squat.tests, 1);
+        assert_eq!(squat.fits.len(), REP_FORMULAS.len());
+        assert_eq!(squat.best(), Some(OneRmFormula::Epley));
+        assert!(squat.fits[0].mean_abs_error < 1e-3);
+        assert!(
+            squat
+                .fits
+                .windows(2)
+                .all(|w| w[0].mean_abs_error <= w[1].mean_abs_error)
+        );
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 9);
+        assert!(calibrate_formulas(&entries, start, None, 2).is_empty());
+
+        entries.push(hard);
+        let cals = calibrate_formulas(&entries, start, None, 2);
+        assert_eq!(cals[0].tests, 1);
+        assert!(cals[0].fits.iter().all(|f| f.bias > 0.0));
+    }
+
+    #[test]
+    fn test_preferred_formula_overrides_global() {
+        let mut entries = sample_entries();
+        entries.retain(|e| e.exercise == "Squat");
+        for e in &mut entries {
+            e.exercise = "Calibrated Squat".into();
+        }
+        let preferred = crate::plotting::preferred_formulas(&BTreeMap::from([(
+            "Calibrated Squat".to_string(),
+            OneRmFormula::Brzycki,
+        )]));
+        let formulas = FormulaChoice::with_preferred(OneRmFormula::Epley, &preferred);
+        let recs = personal_records(&entries, formulas, None, None);
+        let expected = OneRmFormula::Brzycki.estimate(105.0, 5).unwrap() as f32;
+        assert!((recs["Calibrated Squat"].best_est_1rm.unwrap() - expected).abs() < 1e-3);
+    }
+
+    #[test]
+    fn test_detect_plateaus_stall_then_regression() {
+        let first = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let day = |i: i64| first + chrono::Duration::weeks(i);
+        let mut entries: Vec<WorkoutEntry> = [100.0, 105.0, 110.0, 110.0, 110.0, 110.0, 95.0]
+            .into_iter()
+            .chain([95.0, 120.0])
+            .enumerate()
+            .map(|(i, w)| {
+                let mut e = sample_entries()[0].clone();
+                e.date = day(i as i64).format("%Y-%m-%d").to_string();
+                e.weight = Some(w);
+                e
+            })
+            .collect();
+        // Warmups never count as progress.
+        let mut warmup = entries[5].clone();
+        warmup.weight = Some(200.0);
+        warmup.raw.set_type = Some("warmup".into());
+        entries.push(warmup);
+
+        let settings = PlateauSettings {
+            window: PlateauWindow::Sessions,
+            length: 2,
+            regression_pct: 5.0,
+        };
+        let found = detect_plateaus(&entries, OneRmFormula::Epley.into(), None, None, settings);
+        assert_eq!(found.len(), 2);
+        let stall = &found[0];
+        assert_eq!(stall.kind, PlateauKind::Stalled);
+        assert_eq!((stall.start, stall.end), (day(3), day(6)));
+        assert_eq!(stall.sessions, 4);
+        assert!(!stall.ongoing);
+        assert!(stall.change_pct().abs() < 1e-3);
+        let drop = &found[1];
+        assert_eq!(drop.kind, PlateauKind::Regressed);
+        // The regression picks up after the stall instead of sharing its last
+        // session.
+        assert_eq!((drop.start, drop.end), (day(7), day(7)));
+        assert_eq!(drop.sessions, 1);
+        assert!((drop.change_pct() - (95.0 / 110.0 - 1.0) * 100.0).abs() < 1e-3);
+        assert!(!drop.ongoing);
+
+        // Two-week windows over weekly sessions find the same stretches.
+        let weeks = PlateauSettings {
+            window: PlateauWindow::Weeks,
+            ..settings
+        };
+        assert_eq!(
+            detect_plateaus(&entries, OneRmFormula::Epley.into(), None, None, weeks),
+            found
+        );
+
+        let recent = detect_plateaus(
+            &entries,
+            OneRmFormula::Epley.into(),
+            None,
+            Some(day(7)),
+            settings,
+        );
+        assert!(recent.last().unwrap().ongoing);
+    }
+
     #[test]
     fn test_aggregate_weekly_summary_range() {
         let entries = sample_entries();
@@ -1,12 +1,12 @@
-use phf::phf_map;
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeSet;
 
-use crate::exercise_mapping;
+use crate::{exercise_catalog, exercise_mapping};
 
 /// Type of exercise based on muscle engagement.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 pub enum ExerciseType {
+    #[default]
     Compound,
     Isolation,
     Isometric,
@@ -57,44 +57,198 @@ pub const ALL_EQUIPMENT: [Equipment; 6] = [
 ];
 
 /// Metadata about an exercise excluding muscle mappings.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, Default)]
 pub struct ExerciseInfo {
     pub kind: ExerciseType,
+    #[serde(default)]
+    pub difficulty: Option<Difficulty>,
+    #[serde(default)]
+    pub equipment: Option<Equipment>,
+}
+
+/// Type and equipment guessed from an exercise name. Either may be missing
+/// when the name gives no hint.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct InferredInfo {
+    pub kind: Option<ExerciseType>,
+    pub equipment: Option<Equipment>,
+}
+
+/// Exercise information together with which fields were guessed from the
+/// name rather than taken from the catalog.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResolvedInfo {
+    pub kind: Option<ExerciseType>,
     pub difficulty: Option<Difficulty>,
     pub equipment: Option<Equipment>,
+    pub kind_inferred: bool,
+    pub equipment_inferred: bool,
 }
 
-pub static EXERCISES: phf::Map<&'static str, ExerciseInfo> = phf_map! {
-    "Bench" => ExerciseInfo {
-        kind: ExerciseType::Compound,
-        difficulty: Some(Difficulty::Intermediate),
-        equipment: Some(Equipment::Barbell),
-    },
-    "Squat" => ExerciseInfo {
-        kind: ExerciseType::Compound,
-        difficulty: None,
-        equipment: None,
-    },
-    "Deadlift" => ExerciseInfo {
-        kind: ExerciseType::Compound,
-        difficulty: None,
-        equipment: None,
-    },
-    "Push-Up" => ExerciseInfo {
-        kind: ExerciseType::Compound,
-        difficulty: Some(Difficulty::Beginner),
-        equipment: Some(Equipment::Bodyweight),
-    },
-    "Lying Leg Curl (Machine)" => ExerciseInfo {
-        kind: ExerciseType::Isolation,
-        difficulty: Some(Difficulty::Beginner),
-        equipment: Some(Equipment::Machine),
-    },
-};
+/// Map a Hevy name suffix such as `Machine` in `Leg Press (Machine)` to the
+/// equipment it denotes.
+fn equipment_from_suffix(suffix: &str) -> Option<Equipment> {
+    match suffix.trim().to_lowercase().as_str() {
+        "barbell" | "ez bar" | "trap bar" => Some(Equipment::Barbell),
+        "dumbbell" => Some(Equipment::Dumbbell),
+        "machine" | "smith machine" | "plate loaded" => Some(Equipment::Machine),
+        "cable" => Some(Equipment::Cable),
+        "bodyweight" | "weighted" | "assisted" => Some(Equipment::Bodyweight),
+        "band" | "kettlebell" | "plate" | "suspension" | "landmine" => Some(Equipment::Other),
+        _ => None,
+    }
+}
+
+const EQUIPMENT_KEYWORDS: &[(&str, Equipment)] = &[
+    ("smith", Equipment::Machine),
+    ("machine", Equipment::Machine),
+    ("barbell", Equipment::Barbell),
+    ("dumbbell", Equipment::Dumbbell),
+    ("cable", Equipment::Cable),
+    ("push up", Equipment::Bodyweight),
+    ("push-up", Equipment::Bodyweight),
+    ("pull up", Equipment::Bodyweight),
+    ("pull-up", Equipment::Bodyweight),
+    ("chin up", Equipment::Bodyweight),
+    ("chin-up", Equipment::Bodyweight),
+    ("dip", Equipment::Bodyweight),
+    ("plank", Equipment::Bodyweight),
+    ("sit up", Equipment::Bodyweight),
+    ("crunch", Equipment::Bodyweight),
+];
+
+const TYPE_KEYWORDS: &[(&str, ExerciseType)] = &[
+    ("plank", ExerciseType::Isometric),
+    ("hold", ExerciseType::Isometric),
+    ("dead hang", ExerciseType::Isometric),
+    ("wall sit", ExerciseType::Isometric),
+    ("running", ExerciseType::Cardio),
+    ("treadmill", ExerciseType::Cardio),
+    ("cycling", ExerciseType::Cardio),
+    ("elliptical", ExerciseType::Cardio),
+    ("walking", ExerciseType::Cardio),
+    ("rowing machine", ExerciseType::Cardio),
+    ("stair", ExerciseType::Cardio),
+    ("jump rope", ExerciseType::Cardio),
+    ("burpee", ExerciseType::Plyometric),
+    ("jump", ExerciseType::Plyometric),
+    ("curl", ExerciseType::Isolation),
+    ("extension", ExerciseType::Isolation),
+    ("raise", ExerciseType::Isolation),
+    ("fly", ExerciseType::Isolation),
+    ("flye", ExerciseType::Isolation),
+    ("kickback", ExerciseType::Isolation),
+    ("pushdown", ExerciseType::Isolation),
+    ("crossover", ExerciseType::Isolation),
+    ("pec deck", ExerciseType::Isolation),
+    ("shrug", ExerciseType::Isolation),
+    ("calf", ExerciseType::Isolation),
+];
+
+/// Whether `keyword` appears in `name` as whole words, so `hang` does not
+/// match `hanging`. A plural `s` or `es` on a name word is accepted.
+fn has_keyword(name: &str, keyword: &str) -> bool {
+    let words = |s: &str| -> Vec<String> {
+        s.split(|c: char| !c.is_alphanumeric())
+            .filter(|w| !w.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let name = words(name);
+    let keyword = words(keyword);
+    !keyword.is_empty()
+        && name.windows(keyword.len()).any(|window| {
+            window.iter().zip(&keyword).all(|(w, k)| {
+                w == k
+                    || w.strip_prefix(k.as_str())
+                        .is_some_and(|rest| rest == "s" || rest == "es")
+            })
+        })
+}
+
+/// Guess equipment and exercise type from Hevy naming conventions.
+///
+/// The parenthesised suffix decides the equipment when it names one,
+/// otherwise keywords in the name are used. The type comes from keywords,
+/// then from whether the muscle mapping lists secondary muscles. Returns
+/// `None` when nothing could be guessed.
+pub fn infer_info(exercise: &str) -> Option<InferredInfo> {
+    let lower = exercise.to_lowercase();
+    let (base, suffix) = match lower.rsplit_once('(') {
+        Some((base, rest)) => (base.trim(), rest.strip_suffix(')')),
+        None => (lower.as_str(), None),
+    };
+    let equipment = suffix.and_then(equipment_from_suffix).or_else(|| {
+        EQUIPMENT_KEYWORDS
+            .iter()
+            .find(|(k, _)| has_keyword(base, k))
+            .map(|(_, e)| *e)
+    });
+    let kind = TYPE_KEYWORDS
+        .iter()
+        .find(|(k, _)| has_keyword(base, k))
+        .map(|(_, t)| *t)
+        .or_else(|| {
+            exercise_mapping::get(exercise)
+                .filter(|m| !m.primary.is_empty())
+                .map(|m| {
+                    if m.secondary.is_empty() {
+                        ExerciseType::Isolation
+                    } else {
+                        ExerciseType::Compound
+                    }
+                })
+        });
+    if kind.is_none() && equipment.is_none() {
+        return None;
+    }
+    Some(InferredInfo { kind, equipment })
+}
+
+/// Look up an exercise in the catalog and fill the gaps from
+/// [`infer_info`]. Catalog values always win over guesses.
+pub fn resolve_info(exercise: &str) -> Option<ResolvedInfo> {
+    let explicit = exercise_catalog::get(exercise);
+    let inferred = infer_info(exercise);
+    match (explicit, inferred) {
+        (Some(info), guess) => {
+            let equipment_inferred =
+                info.equipment.is_none() && guess.is_some_and(|g| g.equipment.is_some());
+            Some(ResolvedInfo {
+                kind: Some(info.kind),
+                difficulty: info.difficulty,
+                equipment: info.equipment.or(guess.and_then(|g| g.equipment)),
+                kind_inferred: false,
+                equipment_inferred,
+            })
+        }
+        (None, Some(guess)) => Some(ResolvedInfo {
+            kind: guess.kind,
+            difficulty: None,
+            equipment: guess.equipment,
+            kind_inferred: guess.kind.is_some(),
+            equipment_inferred: guess.equipment.is_some(),
+        }),
+        (None, None) => None,
+    }
+}
 
 /// Lookup full information for a given exercise name.
-pub fn info_for(exercise: &str) -> Option<&'static ExerciseInfo> {
-    EXERCISES.get(exercise)
+///
+/// Catalog entries win; missing values are guessed from the name. Returns
+/// `None` unless the exercise type is known.
+pub fn info_for(exercise: &str) -> Option<ExerciseInfo> {
+    let r = resolve_info(exercise)?;
+    Some(ExerciseInfo {
+        kind: r.kind?,
+        difficulty: r.difficulty,
+        equipment: r.equipment,
+    })
+}
+
+/// Convenience wrapper returning the exercise type.
+pub fn kind_for(exercise: &str) -> Option<ExerciseType> {
+    resolve_info(exercise).and_then(|r| r.kind)
 }
 
 /// Convenience wrapper returning only the primary muscle group from the
@@ -109,6 +263,33 @@ pub fn body_part_for(exercise: &str) -> Option<String> {
     })
 }
 
+/// Categories an exercise belongs to, such as a movement pattern or an
+/// exercise family.
+///
+/// The mapping's `category` field may hold several comma-separated names,
+/// e.g. `Horizontal Push, Bench Variants`.
+pub fn categories_for(exercise: &str) -> Vec<String> {
+    exercise_mapping::get(exercise)
+        .map(|m| {
+            m.category
+                .split(',')
+                .map(str::trim)
+                .filter(|c| !c.is_empty())
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Return a sorted list of all categories used in the mapping.
+pub fn all_categories() -> Vec<String> {
+    let mut set = BTreeSet::new();
+    for ex in exercise_mapping::all().keys() {
+        set.extend(categories_for(ex));
+    }
+    set.into_iter().collect()
+}
+
 /// Return a sorted list of all unique primary muscle groups.
 pub fn primary_muscle_groups() -> Vec<String> {
     let mut set = BTreeSet::new();
@@ -122,18 +303,109 @@ pub fn primary_muscle_groups() -> Vec<String> {
 
 /// Convenience wrapper returning the difficulty classification.
 pub fn difficulty_for(exercise: &str) -> Option<Difficulty> {
-    info_for(exercise).and_then(|i| i.difficulty)
+    resolve_info(exercise).and_then(|r| r.difficulty)
 }
 
 /// Convenience wrapper returning the typical equipment.
 pub fn equipment_for(exercise: &str) -> Option<Equipment> {
-    info_for(exercise).and_then(|i| i.equipment)
+    resolve_info(exercise).and_then(|r| r.equipment)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn splits_mapping_categories() {
+        exercise_mapping::set(
+            "Category Test Press".into(),
+            exercise_mapping::MuscleMapping {
+                primary: "Chest".into(),
+                secondary: Vec::new(),
+                category: "Horizontal Push, Bench Variants,".into(),
+            },
+        );
+        let cats = categories_for("Category Test Press");
+        assert!(all_categories().contains(&"Bench Variants".to_string()));
+        exercise_mapping::remove("Category Test Press");
+
+        assert_eq!(cats, vec!["Horizontal Push", "Bench Variants"]);
+        assert!(categories_for("Category Test Press").is_empty());
+    }
+
+    #[test]
+    fn infers_equipment_from_suffix_and_keywords() {
+        let eq = |name: &str| infer_info(name).and_then(|i| i.equipment);
+        assert_eq!(eq("Bench Press (Dumbbell)"), Some(Equipment::Dumbbell));
+        assert_eq!(eq("Squat (Smith Machine)"), Some(Equipment::Machine));
+        assert_eq!(eq("Pull Up (Assisted)"), Some(Equipment::Bodyweight));
+        assert_eq!(eq("Dips (Chest Lean)"), Some(Equipment::Bodyweight));
+        assert_eq!(eq("Cable Crossover"), Some(Equipment::Cable));
+        assert_eq!(infer_info("Zottman Thing"), None);
+    }
+
+    #[test]
+    fn infers_type_from_keywords() {
+        let kind = |name: &str| infer_info(name).and_then(|i| i.kind);
+        assert_eq!(
+            kind("Lying Leg Curl (Machine)"),
+            Some(ExerciseType::Isolation)
+        );
+        assert_eq!(kind("Plank"), Some(ExerciseType::Isometric));
+        assert_eq!(kind("Running"), Some(ExerciseType::Cardio));
+        assert_eq!(kind("Box Jump"), Some(ExerciseType::Plyometric));
+        assert_eq!(kind("Dead Hang"), Some(ExerciseType::Isometric));
+        assert_eq!(
+            kind("Hanging Leg Raise (Type Test)"),
+            Some(ExerciseType::Isolation)
+        );
+        assert_eq!(kind("Hang Clean (Barbell)"), None);
+        assert_eq!(kind("Bicep Curls"), Some(ExerciseType::Isolation));
+    }
+
+    #[test]
+    fn equipment_only_guess_leaves_type_empty() {
+        let r = resolve_info("Type Gap Pulldown (Cable)").unwrap();
+        assert_eq!(r.kind, None);
+        assert!(!r.kind_inferred);
+        assert_eq!(r.equipment, Some(Equipment::Cable));
+        assert!(r.equipment_inferred);
+        assert_eq!(info_for("Type Gap Pulldown (Cable)"), None);
+    }
+
+    #[test]
+    fn catalog_wins_over_inference() {
+        exercise_catalog::set(
+            "Inference Test (Dumbbell)".into(),
+            ExerciseInfo {
+                kind: ExerciseType::Isolation,
+                difficulty: None,
+                equipment: Some(Equipment::Cable),
+            },
+        );
+        let r = resolve_info("Inference Test (Dumbbell)").unwrap();
+        assert_eq!(r.equipment, Some(Equipment::Cable));
+        assert!(!r.kind_inferred && !r.equipment_inferred);
+
+        exercise_catalog::set(
+            "Inference Test (Dumbbell)".into(),
+            ExerciseInfo {
+                kind: ExerciseType::Isolation,
+                difficulty: None,
+                equipment: None,
+            },
+        );
+        let r = resolve_info("Inference Test (Dumbbell)").unwrap();
+        assert_eq!(r.kind, Some(ExerciseType::Isolation));
+        assert_eq!(r.equipment, Some(Equipment::Dumbbell));
+        assert!(!r.kind_inferred && r.equipment_inferred);
+        exercise_catalog::remove("Inference Test (Dumbbell)");
+
+        let r = resolve_info("Inference Test (Dumbbell)").unwrap();
+        assert_eq!(r.kind, None);
+        assert!(!r.kind_inferred && r.equipment_inferred);
+    }
+
     #[test]
     fn body_part_for_uses_mapping() {
         exercise_mapping::set(
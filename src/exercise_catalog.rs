@@ -0,0 +1,131 @@
+//! User-editable catalog of exercise type, difficulty and equipment, stored
+//! as `exercise_catalog.json` next to the muscle mapping.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+
+use dirs_next as dirs;
+
+use crate::body_parts::ExerciseInfo;
+
+static CATALOG: Lazy<Mutex<HashMap<String, ExerciseInfo>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+const FILE: &str = "exercise_catalog.json";
+
+fn path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|p| p.join(FILE))
+}
+
+/// Load the catalog, writing the bundled default on first run.
+pub fn load() {
+    if let Some(p) = path() {
+        if !p.exists() {
+            if let Some(parent) = p.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            let _ = std::fs::write(&p, include_str!("../data/default_exercise_catalog.json"));
+        }
+        if let Ok(data) = std::fs::read_to_string(&p) {
+            match serde_json::from_str::<HashMap<String, ExerciseInfo>>(&data) {
+                Ok(map) => *CATALOG.lock().unwrap() = map,
+                Err(e) => log::error!("Failed to parse exercise catalog: {e}"),
+            }
+        }
+    }
+}
+
+pub fn save() {
+    if let Some(p) = path() {
+        if let Some(parent) = p.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(data) = serde_json::to_string_pretty(&*CATALOG.lock().unwrap()) {
+            let _ = std::fs::write(p, data);
+        }
+    }
+}
+
+pub fn get(ex: &str) -> Option<ExerciseInfo> {
+    CATALOG.lock().unwrap().get(ex).copied()
+}
+
+pub fn set(ex: String, info: ExerciseInfo) {
+    CATALOG.lock().unwrap().insert(ex, info);
+}
+
+pub fn remove(ex: &str) {
+    CATALOG.lock().unwrap().remove(ex);
+}
+
+pub fn all() -> HashMap<String, ExerciseInfo> {
+    CATALOG.lock().unwrap().clone()
+}
+
+pub fn export_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
+    if let Some(parent) = path.as_ref().parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let data = serde_json::to_string_pretty(&all()).map_err(io::Error::other)?;
+    std::fs::write(path, data)
+}
+
+/// Merge catalog files into the current catalog. Files are applied in the
+/// order given, so later files override earlier entries.
+pub fn merge_files<P: AsRef<Path>>(paths: &[P]) -> io::Result<()> {
+    let mut map = all();
+    for p in paths {
+        let data = std::fs::read_to_string(p)?;
+        let part: HashMap<String, ExerciseInfo> =
+            serde_json::from_str(&data).map_err(io::Error::other)?;
+        map.extend(part);
+    }
+    *CATALOG.lock().unwrap() = map;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::body_parts::{Equipment, ExerciseType};
+    use std::io::Write;
+    use tempfile::NamedTempFile;
+
+    #[test]
+    fn default_catalog_parses() {
+        let map: HashMap<String, ExerciseInfo> =
+            serde_json::from_str(include_str!("../data/default_exercise_catalog.json")).unwrap();
+        assert_eq!(
+            map["Barbell Bench Press"].equipment,
+            Some(Equipment::Barbell)
+        );
+        assert_eq!(map["Plank"].kind, ExerciseType::Isometric);
+
+        let mapping: HashMap<String, serde_json::Value> =
+            serde_json::from_str(include_str!("../data/default_exercise_mapping.json")).unwrap();
+        assert!(map.keys().all(|k| mapping.contains_key(k)));
+    }
+
+    #[test]
+    fn merge_file_overrides_entries() {
+        let mut file = NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "{}",
+            serde_json::json!({"Catalog Merge Test": {"kind": "Isometric", "equipment": "Bodyweight"}})
+        )
+        .unwrap();
+
+        merge_files(&[file.path()]).expect("merge should succeed");
+
+        let info = get("Catalog Merge Test").expect("entry should exist");
+        assert_eq!(info.kind, ExerciseType::Isometric);
+        assert_eq!(info.equipment, Some(Equipment::Bodyweight));
+        assert_eq!(info.difficulty, None);
+
+        remove("Catalog Merge Test");
+    }
+}
@@ -19,12 +19,25 @@ pub struct MuscleMapping {
 static MAPPINGS: Lazy<Mutex<HashMap<String, MuscleMapping>>> =
     Lazy::new(|| Mutex::new(HashMap::new()));
 
+/// Raw exercise names, trimmed and lowercased, mapped to the canonical name
+/// their history is merged into.
+static ALIASES: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
 const FILE: &str = "exercise_mapping.json";
+const ALIAS_FILE: &str = "exercise_aliases.json";
 
 fn path() -> Option<std::path::PathBuf> {
     dirs::config_dir().map(|p| p.join(FILE))
 }
 
+fn alias_path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|p| p.join(ALIAS_FILE))
+}
+
+fn alias_key(name: &str) -> String {
+    name.trim().to_lowercase()
+}
+
 pub fn load() {
     if let Some(p) = path() {
         if !p.exists() {
@@ -34,11 +47,36 @@ pub fn load() {
             let _ = std::fs::write(&p, include_str!("../data/default_exercise_mapping.json"));
         }
         if let Ok(data) = std::fs::read_to_string(&p) {
-            if let Ok(map) = serde_json::from_str::<HashMap<String, MuscleMapping>>(&data) {
+            if let Ok(mut map) = serde_json::from_str::<HashMap<String, MuscleMapping>>(&data) {
+                fill_default_categories(&mut map);
                 *MAPPINGS.lock().unwrap() = map;
             }
         }
     }
+    if let Some(p) = alias_path()
+        && let Ok(data) = std::fs::read_to_string(&p)
+        && let Ok(map) = serde_json::from_str::<HashMap<String, String>>(&data)
+    {
+        *ALIASES.lock().unwrap() = map;
+    }
+}
+
+/// Give bundled exercises with an empty category the bundled one, so mapping
+/// files written before categories were seeded still group by movement
+/// pattern. Categories the user typed are left alone.
+fn fill_default_categories(map: &mut HashMap<String, MuscleMapping>) {
+    let Ok(defaults) = serde_json::from_str::<HashMap<String, MuscleMapping>>(include_str!(
+        "../data/default_exercise_mapping.json"
+    )) else {
+        return;
+    };
+    for (name, m) in map.iter_mut() {
+        if m.category.is_empty()
+            && let Some(d) = defaults.get(name)
+        {
+            m.category = d.category.clone();
+        }
+    }
 }
 
 pub fn save() {
@@ -50,10 +88,20 @@ pub fn save() {
             let _ = std::fs::write(p, data);
         }
     }
+    if let Some(p) = alias_path()
+        && let Ok(data) = serde_json::to_string_pretty(&*ALIASES.lock().unwrap())
+    {
+        let _ = std::fs::write(p, data);
+    }
 }
 
+/// Mapping for an exercise, falling back to the mapping of its canonical
+/// name when the exercise is an alias.
 pub fn get(ex: &str) -> Option<MuscleMapping> {
-    MAPPINGS.lock().unwrap().get(ex).cloned()
+    let map = MAPPINGS.lock().unwrap();
+    map.get(ex)
+        .or_else(|| map.get(&canonical_name(ex)))
+        .cloned()
 }
 
 pub fn set(ex: String, map: MuscleMapping) {
@@ -68,6 +116,46 @@ pub fn all() -> HashMap<String, MuscleMapping> {
     MAPPINGS.lock().unwrap().clone()
 }
 
+/// Name an exercise's history is recorded under.
+///
+/// Returns the canonical name if `name` is an alias, otherwise `name` itself.
+pub fn canonical_name(name: &str) -> String {
+    ALIASES
+        .lock()
+        .unwrap()
+        .get(&alias_key(name))
+        .cloned()
+        .unwrap_or_else(|| name.to_string())
+}
+
+/// Record `alias` as another name for `canonical`.
+///
+/// `canonical` stops being an alias itself, and aliases that pointed at
+/// `alias` are moved to `canonical` so lookups never need more than one step.
+pub fn set_alias(alias: &str, canonical: &str) {
+    let key = alias_key(alias);
+    let mut aliases = ALIASES.lock().unwrap();
+    aliases.remove(&alias_key(canonical));
+    if key == alias_key(canonical) {
+        return;
+    }
+    for target in aliases.values_mut() {
+        if alias_key(target) == key {
+            *target = canonical.to_string();
+        }
+    }
+    aliases.insert(key, canonical.to_string());
+}
+
+pub fn remove_alias(alias: &str) {
+    ALIASES.lock().unwrap().remove(&alias_key(alias));
+}
+
+/// All aliases keyed by lowercased raw name.
+pub fn aliases() -> HashMap<String, String> {
+    ALIASES.lock().unwrap().clone()
+}
+
 pub fn export_all<P: AsRef<Path>>(path: P) -> io::Result<()> {
     let map = all();
     if let Some(parent) = path.as_ref().parent() {
@@ -117,6 +205,31 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn bundled_mappings_have_categories() {
+        let defaults: HashMap<String, MuscleMapping> =
+            serde_json::from_str(include_str!("../data/default_exercise_mapping.json")).unwrap();
+        assert!(defaults.values().all(|m| !m.category.is_empty()));
+        assert_eq!(defaults["Barbell Bench Press"].category, "Horizontal Push");
+        assert_eq!(defaults["Romanian Deadlift"].category, "Hinge");
+
+        let mut map = HashMap::from([
+            ("Front Squat".to_string(), MuscleMapping::default()),
+            (
+                "Romanian Deadlift".to_string(),
+                MuscleMapping {
+                    category: "Posterior Chain".into(),
+                    ..MuscleMapping::default()
+                },
+            ),
+            ("Not Bundled".to_string(), MuscleMapping::default()),
+        ]);
+        fill_default_categories(&mut map);
+        assert_eq!(map["Front Squat"].category, "Squat");
+        assert_eq!(map["Romanian Deadlift"].category, "Posterior Chain");
+        assert!(map["Not Bundled"].category.is_empty());
+    }
+
     #[test]
     fn merge_file_with_missing_fields_defaults() {
         let mut file = NamedTempFile::new().unwrap();
@@ -137,4 +250,40 @@ mod tests {
 
         remove("Custom Exercise");
     }
+
+    #[test]
+    fn aliases_resolve_to_canonical_name() {
+        set(
+            "Incline Bench Press (Dumbbell)".into(),
+            MuscleMapping {
+                primary: "Chest".into(),
+                ..MuscleMapping::default()
+            },
+        );
+        set_alias("Incline DB Press", "Incline Bench Press (Dumbbell)");
+        set_alias(" incline db prss", "Incline Bench Press (Dumbbell)");
+
+        assert_eq!(
+            canonical_name("incline db press"),
+            "Incline Bench Press (Dumbbell)"
+        );
+        assert_eq!(
+            canonical_name("Incline DB Prss"),
+            "Incline Bench Press (Dumbbell)"
+        );
+        assert_eq!(canonical_name("Flat DB Press"), "Flat DB Press");
+        assert_eq!(get("Incline DB Press").unwrap().primary, "Chest");
+
+        set_alias("Incline Bench Press (Dumbbell)", "Incline DB Press");
+        assert_eq!(canonical_name("Incline DB Press"), "Incline DB Press");
+        assert_eq!(canonical_name("Incline DB Prss"), "Incline DB Press");
+        assert_eq!(
+            canonical_name("Incline Bench Press (Dumbbell)"),
+            "Incline DB Press"
+        );
+
+        remove_alias("Incline DB Prss");
+        remove_alias("Incline Bench Press (Dumbbell)");
+        remove("Incline Bench Press (Dumbbell)");
+    }
 }
@@ -1,3 +1,129 @@
+use std::collections::BTreeSet;
+
+use strsim::normalized_damerau_levenshtein;
+
+use crate::exercise_mapping;
+
+/// Key used to compare exercise names, with aliases resolved to their
+/// canonical name.
 pub fn normalize_exercise(name: &str) -> String {
-    name.trim().to_lowercase()
+    exercise_mapping::canonical_name(name).trim().to_lowercase()
+}
+
+/// Similarity above which two names are suggested as the same exercise.
+pub const DUPLICATE_THRESHOLD: f64 = 0.75;
+
+const ABBREVIATIONS: &[(&str, &str)] = &[
+    ("db", "dumbbell"),
+    ("dbs", "dumbbell"),
+    ("bb", "barbell"),
+    ("kb", "kettlebell"),
+    ("ohp", "overhead press"),
+    ("rdl", "romanian deadlift"),
+];
+
+const EQUIPMENT_WORDS: &[&str] = &[
+    "barbell",
+    "dumbbell",
+    "cable",
+    "machine",
+    "smith",
+    "kettlebell",
+    "band",
+    "bodyweight",
+    "assisted",
+    "weighted",
+];
+
+/// Lowercased words of a name with punctuation removed, abbreviations
+/// expanded and the order sorted, so `Incline DB Press` and
+/// `Incline Press (Dumbbell)` compare equal.
+fn comparison_key(name: &str) -> Vec<String> {
+    let cleaned: String = name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { ' ' })
+        .collect();
+    let mut words: Vec<String> = cleaned
+        .split_whitespace()
+        .flat_map(|w| {
+            let expanded = ABBREVIATIONS
+                .iter()
+                .find(|(abbr, _)| *abbr == w)
+                .map_or(w, |(_, full)| full);
+            expanded.split(' ').map(str::to_string).collect::<Vec<_>>()
+        })
+        .collect();
+    words.sort();
+    words
+}
+
+fn equipment_words(words: &[String]) -> BTreeSet<&str> {
+    words
+        .iter()
+        .map(String::as_str)
+        .filter(|w| EQUIPMENT_WORDS.contains(w))
+        .collect()
+}
+
+/// Pairs of names that probably refer to the same exercise, most similar
+/// first.
+///
+/// Names already sharing a canonical name are skipped, as are pairs naming
+/// different equipment such as `Curl (Barbell)` and `Curl (Dumbbell)`.
+pub fn likely_duplicates(names: &[String]) -> Vec<(String, String, f64)> {
+    let keys: Vec<Vec<String>> = names.iter().map(|n| comparison_key(n)).collect();
+    let mut pairs = Vec::new();
+    for i in 0..names.len() {
+        for j in i + 1..names.len() {
+            if normalize_exercise(&names[i]) == normalize_exercise(&names[j]) {
+                continue;
+            }
+            let (a, b) = (equipment_words(&keys[i]), equipment_words(&keys[j]));
+            if !a.is_empty() && !b.is_empty() && a != b {
+                continue;
+            }
+            let score = normalized_damerau_levenshtein(&keys[i].join(" "), &keys[j].join(" "));
+            if score >= DUPLICATE_THRESHOLD {
+                pairs.push((names[i].clone(), names[j].clone(), score));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn suggests_renamed_and_misspelled_exercises() {
+        let names: Vec<String> = [
+            "Incline DB Press",
+            "Incline Bench Press (Dumbbell)",
+            "Bicep Curl (Barbell)",
+            "Bicep Curl (Dumbbell)",
+            "Lat Pulldown (Cable)",
+            "Lat Pulldwon (Cable)",
+            "Squat (Barbell)",
+        ]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+
+        let pairs = likely_duplicates(&names);
+        let found: Vec<(&str, &str)> = pairs
+            .iter()
+            .map(|(a, b, _)| (a.as_str(), b.as_str()))
+            .collect();
+        assert!(found.contains(&("Incline DB Press", "Incline Bench Press (Dumbbell)")));
+        assert!(found.contains(&("Lat Pulldown (Cable)", "Lat Pulldwon (Cable)")));
+        assert!(!found.contains(&("Bicep Curl (Barbell)", "Bicep Curl (Dumbbell)")));
+        assert!(
+            found
+                .iter()
+                .all(|(a, b)| *a != "Squat (Barbell)" && *b != "Squat (Barbell)")
+        );
+    }
 }
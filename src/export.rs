@@ -1,6 +1,7 @@
 use crate::{
     WorkoutEntry,
-    analysis::{BasicStats, ExerciseRecord, ExerciseStats},
+    analysis::{BasicStats, ExerciseRecord, ExerciseStats, RepMaxCell},
+    import::ImportReport,
 };
 use serde::Serialize;
 use std::io::Write;
@@ -72,6 +73,17 @@ pub fn save_entries_json<P: AsRef<Path>>(path: P, entries: &[WorkoutEntry]) -> s
     write_json(entries, path)
 }
 
+pub fn save_import_report_csv<P: AsRef<Path>>(path: P, report: &ImportReport) -> csv::Result<()> {
+    write_csv(std::fs::File::create(path)?, &report.skipped)
+}
+
+pub fn save_import_report_json<P: AsRef<Path>>(
+    path: P,
+    report: &ImportReport,
+) -> std::io::Result<()> {
+    write_json(report, path)
+}
+
 pub fn save_prs_csv<P: AsRef<Path>>(
     path: P,
     records: &[(String, ExerciseRecord)],
@@ -102,6 +114,50 @@ pub fn save_prs_json<P: AsRef<Path>>(
     write_json(records, path)
 }
 
+/// One rep-max cell as a CSV row. The csv crate cannot serialize
+/// `#[serde(flatten)]`, so every column is spelled out.
+#[derive(Serialize)]
+struct RepMaxRow<'a> {
+    exercise: &'a str,
+    reps: u32,
+    actual: Option<f32>,
+    date: Option<chrono::NaiveDate>,
+    estimated: Option<f32>,
+    stale: bool,
+    soft: bool,
+}
+
+fn rep_max_rows(matrix: &[(String, Vec<RepMaxCell>)]) -> Vec<RepMaxRow<'_>> {
+    matrix
+        .iter()
+        .flat_map(|(ex, cells)| {
+            cells.iter().map(move |c| RepMaxRow {
+                exercise: ex,
+                reps: c.reps,
+                actual: c.actual,
+                date: c.date,
+                estimated: c.estimated,
+                stale: c.stale,
+                soft: c.soft,
+            })
+        })
+        .collect()
+}
+
+pub fn save_rep_max_csv<P: AsRef<Path>>(
+    path: P,
+    matrix: &[(String, Vec<RepMaxCell>)],
+) -> csv::Result<()> {
+    write_csv(std::fs::File::create(path)?, &rep_max_rows(matrix))
+}
+
+pub fn save_rep_max_json<P: AsRef<Path>>(
+    path: P,
+    matrix: &[(String, Vec<RepMaxCell>)],
+) -> std::io::Result<()> {
+    write_json(matrix, path)
+}
+
 #[derive(Serialize)]
 pub struct StatsExport<'a> {
     pub summary: &'a BasicStats,
@@ -138,3 +194,69 @@ pub fn save_stats_csv<P: AsRef<Path>>(
     }
     wtr.flush().map_err(Into::into)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn rep_max_csv_round_trip() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 1);
+        let matrix = vec![(
+            "Squat".to_string(),
+            vec![
+                RepMaxCell {
+                    reps: 1,
+                    actual: Some(280.0),
+                    date,
+                    estimated: Some(282.5),
+                    stale: true,
+                    soft: false,
+                },
+                RepMaxCell {
+                    reps: 2,
+                    actual: None,
+                    date: None,
+                    estimated: Some(270.0),
+                    stale: false,
+                    soft: false,
+                },
+            ],
+        )];
+        let mut buf = Vec::new();
+        write_csv(&mut buf, &rep_max_rows(&matrix)).unwrap();
+
+        let mut rdr = csv::Reader::from_reader(buf.as_slice());
+        assert_eq!(
+            rdr.headers().unwrap(),
+            vec![
+                "exercise",
+                "reps",
+                "actual",
+                "date",
+                "estimated",
+                "stale",
+                "soft"
+            ]
+        );
+        let rows: Vec<csv::StringRecord> = rdr.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0],
+            vec![
+                "Squat",
+                "1",
+                "280.0",
+                "2024-02-01",
+                "282.5",
+                "true",
+                "false"
+            ]
+        );
+        assert_eq!(
+            rows[1],
+            vec!["Squat", "2", "", "", "270.0", "false", "false"]
+        );
+    }
+}
@@ -0,0 +1,417 @@
+//! Timestamp parsing and diagnostics for importing Hevy CSV exports.
+
+use chrono::{DateTime, Local, NaiveDateTime};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Timestamp layouts found in Hevy exports from different phones and locales.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum DateFormat {
+    /// Pick the known format that parses the most rows of a file.
+    #[default]
+    Auto,
+    /// `26 Jul 2025, 07:06`, optionally with seconds or localized month names.
+    DayMonthName,
+    /// `Jul 26, 2025, 07:06` or `Jul 26, 2025 7:06 AM`.
+    MonthNameDay,
+    /// `2025-07-26T07:06:00Z`, `2025-07-26 07:06` and similar.
+    Iso,
+    /// `26/07/2025 07:06` or `26.07.2025 07:06`.
+    DayMonthNumeric,
+    /// `07/26/2025 07:06` or `07/26/2025 7:06 AM`.
+    MonthDayNumeric,
+}
+
+impl DateFormat {
+    /// Concrete formats in detection order; earlier entries win ties.
+    pub const KNOWN: [DateFormat; 5] = [
+        DateFormat::DayMonthName,
+        DateFormat::Iso,
+        DateFormat::MonthNameDay,
+        DateFormat::DayMonthNumeric,
+        DateFormat::MonthDayNumeric,
+    ];
+
+    pub const ALL: [DateFormat; 6] = [
+        DateFormat::Auto,
+        DateFormat::DayMonthName,
+        DateFormat::MonthNameDay,
+        DateFormat::Iso,
+        DateFormat::DayMonthNumeric,
+        DateFormat::MonthDayNumeric,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            DateFormat::Auto => "Auto-detect",
+            DateFormat::DayMonthName => "26 Jul 2025, 07:06",
+            DateFormat::MonthNameDay => "Jul 26, 2025, 07:06",
+            DateFormat::Iso => "2025-07-26 07:06",
+            DateFormat::DayMonthNumeric => "26/07/2025 07:06",
+            DateFormat::MonthDayNumeric => "07/26/2025 07:06",
+        }
+    }
+
+    fn patterns(self) -> &'static [&'static str] {
+        match self {
+            DateFormat::Auto => &[],
+            DateFormat::DayMonthName => &[
+                "%d %b %Y, %H:%M",
+                "%d %b %Y, %H:%M:%S",
+                "%d %b %Y %H:%M",
+                "%d %b %Y %H:%M:%S",
+            ],
+            DateFormat::MonthNameDay => &[
+                "%b %d, %Y, %H:%M",
+                "%b %d, %Y, %H:%M:%S",
+                "%b %d, %Y %H:%M",
+                "%b %d, %Y, %I:%M %p",
+                "%b %d, %Y %I:%M %p",
+            ],
+            DateFormat::Iso => &[
+                "%Y-%m-%dT%H:%M:%S%.f",
+                "%Y-%m-%dT%H:%M",
+                "%Y-%m-%d %H:%M:%S%.f",
+                "%Y-%m-%d %H:%M",
+            ],
+            DateFormat::DayMonthNumeric => &[
+                "%d/%m/%Y %H:%M",
+                "%d/%m/%Y, %H:%M",
+                "%d/%m/%Y %H:%M:%S",
+                "%d/%m/%Y, %H:%M:%S",
+                "%d.%m.%Y %H:%M",
+                "%d.%m.%Y, %H:%M",
+                "%d.%m.%Y %H:%M:%S",
+                "%d.%m.%Y, %H:%M:%S",
+            ],
+            DateFormat::MonthDayNumeric => &[
+                "%m/%d/%Y %H:%M",
+                "%m/%d/%Y, %H:%M",
+                "%m/%d/%Y %H:%M:%S",
+                "%m/%d/%Y, %H:%M:%S",
+                "%m/%d/%Y %I:%M %p",
+                "%m/%d/%Y, %I:%M %p",
+            ],
+        }
+    }
+
+    /// Parse a timestamp, trying every known format when set to `Auto`.
+    pub fn parse(self, s: &str) -> Option<NaiveDateTime> {
+        let s = s.trim();
+        match self {
+            DateFormat::Auto => Self::KNOWN.iter().find_map(|f| f.parse(s)),
+            DateFormat::Iso => DateTime::parse_from_rfc3339(s)
+                .map(|dt| dt.naive_local())
+                .ok()
+                .or_else(|| parse_with(s, self.patterns())),
+            DateFormat::DayMonthName | DateFormat::MonthNameDay => {
+                parse_with(&normalize_months(s), self.patterns())
+            }
+            _ => parse_with(s, self.patterns()),
+        }
+    }
+}
+
+/// Parse a Hevy API timestamp into the lifter's local wall-clock time.
+///
+/// The API reports instants with an offset, normally UTC, while CSV exports
+/// already hold local times. Converting here keeps synced sets on the same
+/// hour and day as imported ones. Timestamps without an offset are taken
+/// as local already.
+pub fn parse_api_time(s: &str) -> Option<NaiveDateTime> {
+    match DateTime::parse_from_rfc3339(s.trim()) {
+        Ok(dt) => Some(dt.with_timezone(&Local).naive_local()),
+        Err(_) => DateFormat::Iso.parse(s),
+    }
+}
+
+fn parse_with(s: &str, patterns: &[&str]) -> Option<NaiveDateTime> {
+    patterns
+        .iter()
+        .find_map(|p| NaiveDateTime::parse_from_str(s, p).ok())
+}
+
+/// Localized month names and abbreviations mapped to the English abbreviation
+/// understood by chrono's `%b`.
+const MONTH_NAMES: &[(&str, &str)] = &[
+    // German
+    ("januar", "Jan"),
+    ("februar", "Feb"),
+    ("mär", "Mar"),
+    ("märz", "Mar"),
+    ("mrz", "Mar"),
+    ("mai", "May"),
+    ("juni", "Jun"),
+    ("juli", "Jul"),
+    ("okt", "Oct"),
+    ("oktober", "Oct"),
+    ("dez", "Dec"),
+    ("dezember", "Dec"),
+    // French
+    ("janv", "Jan"),
+    ("janvier", "Jan"),
+    ("févr", "Feb"),
+    ("fevr", "Feb"),
+    ("février", "Feb"),
+    ("mars", "Mar"),
+    ("avr", "Apr"),
+    ("avril", "Apr"),
+    ("juin", "Jun"),
+    ("juil", "Jul"),
+    ("juillet", "Jul"),
+    ("août", "Aug"),
+    ("aout", "Aug"),
+    ("sept", "Sep"),
+    ("septembre", "Sep"),
+    ("octobre", "Oct"),
+    ("novembre", "Nov"),
+    ("déc", "Dec"),
+    ("décembre", "Dec"),
+    // Spanish
+    ("ene", "Jan"),
+    ("enero", "Jan"),
+    ("febrero", "Feb"),
+    ("marzo", "Mar"),
+    ("abr", "Apr"),
+    ("abril", "Apr"),
+    ("mayo", "May"),
+    ("junio", "Jun"),
+    ("julio", "Jul"),
+    ("ago", "Aug"),
+    ("agosto", "Aug"),
+    ("septiembre", "Sep"),
+    ("octubre", "Oct"),
+    ("noviembre", "Nov"),
+    ("dic", "Dec"),
+    ("diciembre", "Dec"),
+    // Italian
+    ("gen", "Jan"),
+    ("gennaio", "Jan"),
+    ("febbraio", "Feb"),
+    ("aprile", "Apr"),
+    ("mag", "May"),
+    ("maggio", "May"),
+    ("giu", "Jun"),
+    ("giugno", "Jun"),
+    ("lug", "Jul"),
+    ("luglio", "Jul"),
+    ("set", "Sep"),
+    ("settembre", "Sep"),
+    ("ott", "Oct"),
+    ("ottobre", "Oct"),
+    ("dicembre", "Dec"),
+    // Portuguese
+    ("janeiro", "Jan"),
+    ("fev", "Feb"),
+    ("fevereiro", "Feb"),
+    ("março", "Mar"),
+    ("maio", "May"),
+    ("junho", "Jun"),
+    ("julho", "Jul"),
+    ("setembro", "Sep"),
+    ("out", "Oct"),
+    ("outubro", "Oct"),
+    ("novembro", "Nov"),
+    ("dezembro", "Dec"),
+    // Dutch
+    ("januari", "Jan"),
+    ("februari", "Feb"),
+    ("mrt", "Mar"),
+    ("maart", "Mar"),
+    ("mei", "May"),
+    ("augustus", "Aug"),
+];
+
+/// Replace localized month names with English abbreviations and drop the
+/// Spanish/Portuguese `de` separators so chrono can parse the result.
+fn normalize_months(s: &str) -> String {
+    s.split_whitespace()
+        .filter(|t| !t.eq_ignore_ascii_case("de"))
+        .map(|token| {
+            let trailing_comma = token.ends_with(',');
+            let word = token.trim_end_matches(',').trim_end_matches('.');
+            let lower = word.to_lowercase();
+            match MONTH_NAMES.iter().find(|(name, _)| *name == lower) {
+                Some((_, en)) if trailing_comma => format!("{en},"),
+                Some((_, en)) => en.to_string(),
+                None if trailing_comma => format!("{word},"),
+                None => word.to_string(),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Choose the known format that parses the most of the given timestamps.
+///
+/// Every sample is scored, since a single late row such as `12/31/2024` may be
+/// the only one that settles the day and month order. Ties go to the format listed first in [`DateFormat::KNOWN`], so ambiguous
+/// numeric dates such as `01/02/2024` are read day first.
+pub fn detect_date_format<'a>(samples: impl IntoIterator<Item = &'a str>) -> DateFormat {
+    let samples: Vec<&str> = samples.into_iter().collect();
+    let mut best = (DateFormat::DayMonthName, 0);
+    for format in DateFormat::KNOWN {
+        let parsed = samples.iter().filter(|s| format.parse(s).is_some()).count();
+        if parsed > best.1 {
+            best = (format, parsed);
+        }
+    }
+    best.0
+}
+
+/// Why a CSV row did not become a workout entry.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub enum SkipReason {
+    /// The row could not be deserialized into the expected columns.
+    Malformed,
+    /// `start_time` did not match the selected or detected date format.
+    BadDate,
+    /// The set had no reps, duration or distance to record.
+    NoMeasurement,
+}
+
+impl SkipReason {
+    pub fn label(self) -> &'static str {
+        match self {
+            SkipReason::Malformed => "Malformed row",
+            SkipReason::BadDate => "Unparseable start_time",
+            SkipReason::NoMeasurement => "No reps, duration or distance",
+        }
+    }
+}
+
+/// A single row that was dropped during import.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SkippedRow {
+    /// One-based line number in the source file.
+    pub line: u64,
+    pub reason: SkipReason,
+    /// The offending value, or the exercise title when a value is missing.
+    pub value: String,
+}
+
+/// Summary of a CSV import.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ImportReport {
+    /// Timestamp format used for the file, after auto-detection.
+    pub date_format: Option<DateFormat>,
+    pub total_rows: usize,
+    pub imported: usize,
+    pub skipped: Vec<SkippedRow>,
+}
+
+impl ImportReport {
+    pub fn skip(&mut self, line: u64, reason: SkipReason, value: impl Into<String>) {
+        self.skipped.push(SkippedRow {
+            line,
+            reason,
+            value: value.into(),
+        });
+    }
+
+    /// Number of skipped rows per reason.
+    pub fn counts(&self) -> BTreeMap<SkipReason, usize> {
+        let mut counts = BTreeMap::new();
+        for row in &self.skipped {
+            *counts.entry(row.reason).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Up to `n` example rows skipped for `reason`.
+    pub fn samples(&self, reason: SkipReason, n: usize) -> Vec<&SkippedRow> {
+        self.skipped
+            .iter()
+            .filter(|r| r.reason == reason)
+            .take(n)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_and_samples_by_reason() {
+        let mut report = ImportReport::default();
+        report.skip(2, SkipReason::BadDate, "2024/01/01");
+        report.skip(3, SkipReason::NoMeasurement, "Bench Press");
+        report.skip(5, SkipReason::BadDate, "yesterday");
+
+        let counts = report.counts();
+        assert_eq!(counts[&SkipReason::BadDate], 2);
+        assert_eq!(counts[&SkipReason::NoMeasurement], 1);
+        assert!(!counts.contains_key(&SkipReason::Malformed));
+
+        let samples = report.samples(SkipReason::BadDate, 1);
+        assert_eq!(samples.len(), 1);
+        assert_eq!(samples[0].line, 2);
+        assert_eq!(samples[0].value, "2024/01/01");
+    }
+
+    fn dt(y: i32, m: u32, d: u32, h: u32, min: u32) -> NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(y, m, d)
+            .unwrap()
+            .and_hms_opt(h, min, 0)
+            .unwrap()
+    }
+
+    #[test]
+    fn parses_known_variants() {
+        let expected = dt(2025, 7, 26, 7, 6);
+        for s in [
+            "26 Jul 2025, 07:06",
+            "26 Jul 2025, 07:06:00",
+            "26 juil. 2025, 07:06",
+            "26 de julio de 2025, 07:06",
+            "26. Juli 2025, 07:06",
+            "Jul 26, 2025, 07:06",
+            "Jul 26, 2025 7:06 AM",
+            "2025-07-26T07:06:00Z",
+            "2025-07-26T07:06:00+02:00",
+            "2025-07-26 07:06",
+            "26/07/2025 07:06",
+            "26.07.2025, 07:06",
+        ] {
+            assert_eq!(DateFormat::Auto.parse(s), Some(expected), "{s}");
+        }
+        assert_eq!(
+            DateFormat::MonthDayNumeric.parse("07/26/2025 7:06 AM"),
+            Some(expected)
+        );
+        assert_eq!(DateFormat::DayMonthName.parse("2025-07-26 07:06"), None);
+    }
+
+    #[test]
+    fn api_times_convert_to_local() {
+        let utc = dt(2024, 1, 1, 0, 0).and_utc();
+        let local = utc.with_timezone(&Local).naive_local();
+        assert_eq!(parse_api_time("2024-01-01T00:00:00+00:00"), Some(local));
+        assert_eq!(parse_api_time("2024-01-01T00:00:00Z"), Some(local));
+        assert_eq!(
+            parse_api_time("2024-01-01 19:00"),
+            Some(dt(2024, 1, 1, 19, 0))
+        );
+    }
+
+    #[test]
+    fn detects_format_from_samples() {
+        let us = ["01/02/2024 10:00", "12/31/2024 09:30"];
+        assert_eq!(detect_date_format(us), DateFormat::MonthDayNumeric);
+
+        let eu = ["01/02/2024 10:00", "31/12/2024 09:30"];
+        assert_eq!(detect_date_format(eu), DateFormat::DayMonthNumeric);
+
+        let ambiguous = ["01/02/2024 10:00"];
+        assert_eq!(detect_date_format(ambiguous), DateFormat::DayMonthNumeric);
+
+        let hevy = ["26 Jul 2025, 07:06"];
+        assert_eq!(detect_date_format(hevy), DateFormat::DayMonthName);
+
+        let late_us: Vec<&str> = std::iter::repeat_n("01/02/2024 10:00", 500)
+            .chain(["12/31/2024 09:30"])
+            .collect();
+        assert_eq!(detect_date_format(late_us), DateFormat::MonthDayNumeric);
+    }
+}
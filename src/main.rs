@@ -10,21 +10,27 @@ use serde::{Deserialize, Serialize};
 use std::collections::{BTreeMap, BTreeSet, HashSet};
 use std::fs::File;
 use std::io::{BufReader, Cursor, Read};
-use std::sync::mpsc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, mpsc};
 use std::time::{Duration, Instant};
 
-use chrono::{Local, NaiveDate, Utc};
+use chrono::{Datelike, Local, NaiveDate, Utc};
 use log::info;
 use strsim::damerau_levenshtein;
 
 mod analysis;
-use analysis::{BasicStats, ExerciseStats, NotesQuery, compute_stats, format_load_message};
+use analysis::{
+    BasicStats, CalendarMetric, ExerciseStats, MuscleCredit, NotesQuery, PlateauKind,
+    PlateauSettings, PlateauWindow, PrKind, VolumeLandmarks, compute_stats, format_load_message,
+};
 mod plotting;
 use plotting::{
-    HistogramMetric, OneRmFormula, SmoothingMethod, VolumeAggregation, XAxis, YAxis,
+    HistogramMetric, OneRmFormula, SessionMetric, SmoothingMethod, VolumeAggregation, XAxis, YAxis,
     aggregated_volume_points, average_rpe_line, body_part_distribution, body_part_pie,
-    body_part_volume_line, body_part_volume_trend, draw_crosshair, draw_pie_chart,
-    estimated_1rm_line, exercise_volume_line, forecast_line_points, format_hover_text, histogram,
+    body_part_volume_line, body_part_volume_trend, bodyweight_load_line, category_volume_line,
+    distance_per_week_bar, dose_color, draw_calendar_heatmap, draw_crosshair, draw_pie_chart,
+    estimated_1rm_line, exercise_volume_line, forecast_line_points, format_hover_text,
+    hard_sets_chart, histogram, hold_time_bar, pace_line, session_histogram, session_metric_line,
     sets_per_day_bar, training_volume_line, trend_line_points, unique_exercises,
     weekly_summary_plot, weight_over_time_line, weight_reps_scatter,
 };
@@ -32,7 +38,8 @@ mod capture;
 use capture::{crop_image, save_png};
 mod export;
 use export::{
-    save_entries_csv, save_entries_json, save_prs_csv, save_prs_json, save_stats_csv,
+    save_entries_csv, save_entries_json, save_import_report_csv, save_import_report_json,
+    save_prs_csv, save_prs_json, save_rep_max_csv, save_rep_max_json, save_stats_csv,
     save_stats_json,
 };
 mod report;
@@ -41,12 +48,25 @@ mod body_parts;
 use body_parts::ExerciseType;
 mod exercise_utils;
 use exercise_utils::normalize_exercise;
+mod exercise_catalog;
 mod exercise_mapping;
+mod import;
+mod rpe_table;
+mod store;
 mod sync;
+mod templates;
+mod workout;
+
+#[cfg(test)]
+mod test_util;
+use import::{DateFormat, ImportReport, SkipReason, detect_date_format};
 
 #[derive(Debug, Deserialize, Clone, Serialize)]
 struct WorkoutEntry {
     date: String,
+    /// Full start timestamp of the workout the set belongs to.
+    #[serde(default)]
+    start: Option<chrono::NaiveDateTime>,
     exercise: String,
     weight: Option<f32>,
     reps: Option<u32>,
@@ -54,12 +74,87 @@ struct WorkoutEntry {
 }
 
 impl WorkoutEntry {
+    /// Build an entry from a raw row whose start time has already been parsed.
+    ///
+    /// The weight is normalised to lbs. Weighted, reps-only, timed and
+    /// distance sets are all kept; only rows with no reps, duration or
+    /// distance are rejected. CSV imports and API syncs both go through here
+    /// so they keep the same rows.
+    fn from_raw(raw: RawWorkoutRow, start: chrono::NaiveDateTime) -> Option<Self> {
+        if raw.reps.is_none() && raw.duration_seconds.is_none() && raw.distance_miles.is_none() {
+            return None;
+        }
+        let weight = raw
+            .weight_lbs
+            .or_else(|| raw.weight_kg.map(|kg| kg * 2.20462));
+        Some(WorkoutEntry {
+            date: start.date().format("%Y-%m-%d").to_string(),
+            start: Some(start),
+            exercise: raw.exercise_title.clone(),
+            weight,
+            reps: raw.reps,
+            raw,
+        })
+    }
+
+    /// Weight times reps, or `None` for sets missing either.
+    fn volume(&self) -> Option<f32> {
+        Some(self.weight? * self.reps? as f32)
+    }
+
+    /// Distance covered in miles, if any.
+    fn distance(&self) -> Option<f32> {
+        self.raw.distance_miles.filter(|d| *d > 0.0)
+    }
+
+    /// Duration of the set in seconds, if any.
+    fn duration(&self) -> Option<f32> {
+        self.raw.duration_seconds.filter(|d| *d > 0.0)
+    }
+
+    /// Whether the exercise uses a machine or band to take weight off, such
+    /// as Hevy's `Pull Up (Assisted)`.
+    fn is_assisted(&self) -> bool {
+        self.exercise.to_lowercase().contains("assisted")
+    }
+
+    /// Whether the lifter's own body weight is part of the load.
+    ///
+    /// Hevy's `(Weighted)` variants and reps-only sets without a duration or
+    /// distance are treated as bodyweight work even when the exercise is not
+    /// in the catalog.
+    fn is_bodyweight(&self) -> bool {
+        self.is_assisted()
+            || self.exercise.to_lowercase().contains("(weighted)")
+            || self.equipment() == Some(body_parts::Equipment::Bodyweight)
+            || (self.weight.is_none()
+                && self.reps.is_some()
+                && self.duration().is_none()
+                && self.distance().is_none())
+    }
+
+    /// Load moved in lbs with `body_weight` folded in for bodyweight work.
+    ///
+    /// Added weight counts on top of body weight while assistance is
+    /// subtracted from it. Other exercises return their plain weight.
+    fn bodyweight_load(&self, body_weight: f32) -> Option<f32> {
+        if !self.is_bodyweight() {
+            return self.weight;
+        }
+        let extra = self.weight.unwrap_or(0.0);
+        if self.is_assisted() {
+            Some((body_weight - extra).max(0.0))
+        } else {
+            Some(body_weight + extra)
+        }
+    }
+
     fn body_part(&self) -> Option<String> {
         body_parts::body_part_for(&self.exercise)
     }
 
     fn exercise_type(&self) -> Option<ExerciseType> {
-        body_parts::info_for(&self.exercise).map(|i| i.kind)
+        body_parts::kind_for(&self.exercise)
     }
 
     fn difficulty(&self) -> Option<body_parts::Difficulty> {
@@ -71,6 +166,26 @@ impl WorkoutEntry {
     }
 }
 
+/// Format a duration in seconds as `m:ss`, or `h:mm:ss` past an hour.
+fn format_duration(secs: f32) -> String {
+    let total = secs.round() as u64;
+    let (h, m, s) = (total / 3600, total / 60 % 60, total % 60);
+    if h > 0 {
+        format!("{h}:{m:02}:{s:02}")
+    } else {
+        format!("{m}:{s:02}")
+    }
+}
+
+/// Label a catalog value, marking it when it was guessed from the name.
+fn inferred_label(value: impl std::fmt::Debug, inferred: bool) -> String {
+    if inferred {
+        format!("{:?} (inferred)", value)
+    } else {
+        format!("{:?}", value)
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, Serialize, Default)]
 struct RawWorkoutRow {
     title: Option<String>,
@@ -88,6 +203,8 @@ struct RawWorkoutRow {
     distance_miles: Option<f32>,
     duration_seconds: Option<f32>,
     rpe: Option<f32>,
+    #[serde(default)]
+    workout_id: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -107,49 +224,93 @@ impl WeightUnit {
 
 enum LoadMessage {
     Progress(f32),
-    Done(Vec<WorkoutEntry>),
+    Done(Vec<WorkoutEntry>, ImportReport),
+    Merged(Vec<WorkoutEntry>, store::MergeReport),
     Error(String),
 }
 
+enum SyncMessage {
+    Progress(f32),
+    Workouts(Vec<WorkoutEntry>),
+    Events(Vec<sync::WorkoutEvent>),
+    Templates(Vec<sync::ExerciseTemplate>),
+    Error(sync::SyncError),
+}
+
 fn parse_workout_csv<R: std::io::Read>(
     reader: R,
+    format: DateFormat,
     progress: Option<mpsc::Sender<LoadMessage>>,
 ) -> Result<Vec<WorkoutEntry>, csv::Error> {
+    parse_workout_csv_with_report(reader, format, progress).map(|(entries, _)| entries)
+}
+
+/// Parse a Hevy CSV export and record why any rows were skipped.
+///
+/// With [`DateFormat::Auto`] the timestamp format is detected from the file's
+/// `start_time` values before any row is converted.
+fn parse_workout_csv_with_report<R: std::io::Read>(
+    reader: R,
+    format: DateFormat,
+    progress: Option<mpsc::Sender<LoadMessage>>,
+) -> Result<(Vec<WorkoutEntry>, ImportReport), csv::Error> {
     let mut buf = String::new();
     let mut rdr = BufReader::new(reader);
     rdr.read_to_string(&mut buf).map_err(csv::Error::from)?;
     let total_lines = buf.lines().count().max(1);
     let mut rdr = csv::Reader::from_reader(Cursor::new(buf));
+    let headers = rdr.headers()?.clone();
+    let rows: Vec<(u64, Result<RawWorkoutRow, csv::Error>)> = rdr
+        .records()
+        .enumerate()
+        .map(|(i, result)| {
+            let position = match &result {
+                Ok(r) => r.position(),
+                Err(e) => e.position(),
+            };
+            let line = position.map_or(i as u64 + 2, |p| p.line());
+            let parsed = result.and_then(|r| r.deserialize::<RawWorkoutRow>(Some(&headers)));
+            (line, parsed)
+        })
+        .collect();
+    let format = match format {
+        DateFormat::Auto => detect_date_format(
+            rows.iter()
+                .filter_map(|(_, r)| r.as_ref().ok())
+                .map(|r| r.start_time.as_str()),
+        ),
+        f => f,
+    };
     let mut entries = Vec::new();
-    for (i, result) in rdr.deserialize::<RawWorkoutRow>().enumerate() {
-        if let Ok(raw) = result {
-            if let Ok(dt) =
-                chrono::NaiveDateTime::parse_from_str(&raw.start_time, "%d %b %Y, %H:%M")
-            {
-                let date = dt.date().format("%Y-%m-%d").to_string();
-                let weight = raw
-                    .weight_lbs
-                    .or_else(|| raw.weight_kg.map(|kg| kg * 2.20462));
-                if let (Some(weight), Some(reps)) = (weight, raw.reps) {
-                    entries.push(WorkoutEntry {
-                        date,
-                        exercise: raw.exercise_title.clone(),
-                        weight: Some(weight),
-                        reps: Some(reps),
-                        raw,
-                    });
+    let mut report = ImportReport {
+        date_format: Some(format),
+        ..ImportReport::default()
+    };
+    for (i, (line, parsed)) in rows.into_iter().enumerate() {
+        report.total_rows += 1;
+        match parsed {
+            Err(e) => report.skip(line, SkipReason::Malformed, e.to_string()),
+            Ok(raw) => match format.parse(&raw.start_time) {
+                None => report.skip(line, SkipReason::BadDate, raw.start_time),
+                Some(start) => {
+                    let title = raw.exercise_title.clone();
+                    match WorkoutEntry::from_raw(raw, start) {
+                        Some(entry) => entries.push(entry),
+                        None => report.skip(line, SkipReason::NoMeasurement, title),
+                    }
                 }
-            }
+            },
         }
         if let Some(tx) = &progress {
             let _ = tx.send(LoadMessage::Progress((i + 1) as f32 / total_lines as f32));
         }
     }
+    report.imported = entries.len();
     if let Some(tx) = progress {
         let _ = tx.send(LoadMessage::Progress(1.0));
-        let _ = tx.send(LoadMessage::Done(entries.clone()));
+        let _ = tx.send(LoadMessage::Done(entries.clone(), report.clone()));
     }
-    Ok(entries)
+    Ok((entries, report))
 }
 
 fn parse_latest_pr_number(json: &str) -> Option<u64> {
@@ -201,6 +362,96 @@ fn default_panel_width() -> f32 {
     200.0
 }
 
+fn default_sync_templates() -> bool {
+    true
+}
+
+fn default_primary_credit() -> f32 {
+    1.0
+}
+
+fn default_secondary_credit() -> f32 {
+    0.5
+}
+
+fn default_session_target() -> u32 {
+    3
+}
+
+fn default_calibration_weeks() -> u32 {
+    4
+}
+
+fn default_stale_months() -> u32 {
+    6
+}
+
+fn default_duration_bin_size() -> f32 {
+    10.0
+}
+
+fn default_density_bin_size() -> f32 {
+    50.0
+}
+
+/// Fallback picker and editable percentage grid for the RPE table. Every
+/// change is saved straight away.
+fn draw_rpe_table_editor(ui: &mut egui::Ui) {
+    let mut table = rpe_table::get();
+    let before = table.clone();
+    egui::CollapsingHeader::new("RPE Table")
+        .default_open(false)
+        .show(ui, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Without RPE use:");
+                egui::ComboBox::from_id_source("rpe_fallback")
+                    .selected_text(format!("{:?}", table.fallback))
+                    .show_ui(ui, |ui| {
+                        for f in [
+                            OneRmFormula::Epley,
+                            OneRmFormula::Brzycki,
+                            OneRmFormula::Lombardi,
+                            OneRmFormula::Mayhew,
+                            OneRmFormula::OConner,
+                            OneRmFormula::Wathan,
+                            OneRmFormula::Lander,
+                        ] {
+                            ui.selectable_value(&mut table.fallback, f, format!("{f:?}"));
+                        }
+                    });
+                if ui.button("Reset to RTS").clicked() {
+                    table = rpe_table::RpeTable::default();
+                }
+            });
+            ui.label(RichText::new("Percent of 1RM for each rep count and RPE.").small());
+            egui::Grid::new("rpe_table_grid").show(ui, |ui| {
+                ui.label("Reps");
+                for rpe in &table.rpes {
+                    ui.label(format!("@{rpe}"));
+                }
+                ui.end_row();
+                for (i, row) in table.rows.iter_mut().enumerate() {
+                    ui.label((i + 1).to_string());
+                    for pct in row.iter_mut() {
+                        let mut text = format!("{pct:.1}");
+                        if ui
+                            .add(egui::TextEdit::singleline(&mut text).desired_width(36.0))
+                            .changed()
+                            && let Ok(v) = text.parse::<f32>()
+                        {
+                            *pct = v.clamp(0.0, 100.0);
+                        }
+                    }
+                    ui.end_row();
+                }
+            });
+        });
+    if table != before {
+        rpe_table::set(table);
+        rpe_table::save();
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 enum ExerciseSelection {
     All,
@@ -301,6 +552,12 @@ struct Settings {
     show_rpe_histogram: bool,
     #[serde(default)]
     rpe_bin_size: f32,
+    /// Bin width of the session duration histogram in minutes.
+    #[serde(default = "default_duration_bin_size")]
+    duration_bin_size: f32,
+    /// Bin width of the session density histogram in volume per minute.
+    #[serde(default = "default_density_bin_size")]
+    density_bin_size: f32,
     #[serde(default)]
     show_weight_reps_scatter: bool,
     #[serde(default)]
@@ -314,12 +571,25 @@ struct Settings {
     show_rpe: bool,
     #[serde(default)]
     show_rpe_trend: bool,
+    #[serde(default)]
+    show_pace: bool,
+    #[serde(default)]
+    show_distance_per_week: bool,
+    #[serde(default)]
+    show_hold_time: bool,
+    #[serde(default)]
+    show_bodyweight_load: bool,
+    /// Lifter's body weight in lbs, used for bodyweight-adjusted load.
+    #[serde(default)]
+    body_weight: Option<f32>,
     show_body_part_volume: bool,
     #[serde(default)]
     show_body_part_distribution: bool,
     #[serde(default)]
     show_body_part_trend: bool,
     #[serde(default)]
+    show_category_volume: bool,
+    #[serde(default)]
     show_exercise_volume: bool,
     #[serde(default)]
     show_weekly_summary: bool,
@@ -345,6 +615,45 @@ struct Settings {
     show_overall_analysis_window: bool,
     #[serde(default)]
     show_mapping: bool,
+    #[serde(default)]
+    show_hard_sets_window: bool,
+    #[serde(default)]
+    show_sessions_window: bool,
+    #[serde(default)]
+    show_session_analytics_window: bool,
+    #[serde(default)]
+    show_calendar_window: bool,
+    #[serde(default)]
+    calendar_metric: CalendarMetric,
+    #[serde(default)]
+    show_rep_max_window: bool,
+    #[serde(default)]
+    show_calibration_window: bool,
+    #[serde(default)]
+    show_stalled_window: bool,
+    /// Shade stalls and regressions on the weight and 1RM plots.
+    #[serde(default)]
+    show_plateaus: bool,
+    #[serde(default)]
+    plateau: PlateauSettings,
+    /// Weeks of sets before a tested single used to predict it.
+    #[serde(default = "default_calibration_weeks")]
+    calibration_weeks: u32,
+    /// Formula used for an exercise instead of `one_rm_formula`.
+    #[serde(default)]
+    preferred_formulas: BTreeMap<String, OneRmFormula>,
+    /// Rep maxes older than this many months are flagged stale.
+    #[serde(default = "default_stale_months")]
+    rep_max_stale_months: u32,
+    /// Sessions per week counted as on target for streaks and adherence.
+    #[serde(default = "default_session_target")]
+    weekly_session_target: u32,
+    /// Leave sets logged below this RPE out of the weekly hard-set counts.
+    #[serde(default)]
+    hard_set_min_rpe: Option<f32>,
+    /// MEV, MAV and MRV per muscle group; missing muscles use the defaults.
+    #[serde(default)]
+    volume_landmarks: BTreeMap<String, VolumeLandmarks>,
     highlight_max: bool,
     #[serde(default)]
     show_pr_markers: bool,
@@ -373,6 +682,13 @@ struct Settings {
     volume_aggregation: VolumeAggregation,
     #[serde(default)]
     body_part_volume_aggregation: VolumeAggregation,
+    /// Credit secondary muscles with a share of each set in body-part totals.
+    #[serde(default)]
+    weighted_muscle_credit: bool,
+    #[serde(default = "default_primary_credit")]
+    primary_credit: f32,
+    #[serde(default = "default_secondary_credit")]
+    secondary_credit: f32,
     weight_unit: WeightUnit,
     one_rm_formula: OneRmFormula,
     start_date: Option<NaiveDate>,
@@ -382,6 +698,8 @@ struct Settings {
     set_type_filter: Option<String>,
     superset_filter: Option<String>,
     body_part_filter: Option<String>,
+    #[serde(default)]
+    category_filter: Option<String>,
     exercise_filter: Option<String>,
     exercise_type_filter: Option<ExerciseType>,
     difficulty_filter: Option<body_parts::Difficulty>,
@@ -403,6 +721,15 @@ struct Settings {
     #[serde(default)]
     last_sync: Option<String>,
     #[serde(default)]
+    date_format: DateFormat,
+    #[serde(default)]
+    sync_mode: sync::SyncMode,
+    #[serde(default)]
+    sync_retry: sync::RetryPolicy,
+    /// Fetch Hevy exercise templates after a sync when exercises are unmapped.
+    #[serde(default = "default_sync_templates")]
+    sync_templates: bool,
+    #[serde(default)]
     check_prs: bool,
     github_repo: Option<String>,
     last_pr: Option<u64>,
@@ -454,6 +781,18 @@ impl Settings {
         Self::default()
     }
 
+    /// How sets are credited to body parts in the body-part plots.
+    fn muscle_credit(&self) -> MuscleCredit {
+        if self.weighted_muscle_credit {
+            MuscleCredit::Weighted {
+                primary: self.primary_credit,
+                secondary: self.secondary_credit,
+            }
+        } else {
+            MuscleCredit::PrimaryOnly
+        }
+    }
+
     /// Persist the current settings, including the `show_rpe` flag, to disk.
     fn save(&self) {
         if let Some(path) = Self::path() {
@@ -482,6 +821,8 @@ impl Default for Settings {
             weight_bin_size: 10.0,
             show_volume_histogram: false,
             volume_bin_size: 100.0,
+            duration_bin_size: default_duration_bin_size(),
+            density_bin_size: default_density_bin_size(),
             show_rpe_histogram: false,
             rpe_bin_size: 1.0,
             show_weight_reps_scatter: false,
@@ -489,9 +830,15 @@ impl Default for Settings {
             show_volume: false,
             show_rpe: false,
             show_rpe_trend: false,
+            show_pace: false,
+            show_distance_per_week: false,
+            show_hold_time: false,
+            show_bodyweight_load: false,
+            body_weight: None,
             show_body_part_volume: false,
             show_body_part_distribution: false,
             show_body_part_trend: false,
+            show_category_volume: false,
             show_exercise_volume: false,
             show_weekly_summary: false,
             show_weekly_summary_table_window: false,
@@ -505,6 +852,22 @@ impl Default for Settings {
             show_stats_window: false,
             show_overall_analysis_window: false,
             show_mapping: false,
+            show_hard_sets_window: false,
+            show_sessions_window: false,
+            show_session_analytics_window: false,
+            show_calendar_window: false,
+            show_rep_max_window: false,
+            show_calibration_window: false,
+            show_stalled_window: false,
+            show_plateaus: true,
+            plateau: PlateauSettings::default(),
+            calibration_weeks: default_calibration_weeks(),
+            preferred_formulas: BTreeMap::new(),
+            rep_max_stale_months: default_stale_months(),
+            calendar_metric: CalendarMetric::default(),
+            weekly_session_target: default_session_target(),
+            hard_set_min_rpe: None,
+            volume_landmarks: BTreeMap::new(),
             highlight_max: true,
             show_pr_markers: true,
             show_weight_trend: false,
@@ -521,6 +884,9 @@ impl Default for Settings {
             grid_rows: default_grid_rows(),
             volume_aggregation: VolumeAggregation::Weekly,
             body_part_volume_aggregation: VolumeAggregation::Weekly,
+            weighted_muscle_credit: false,
+            primary_credit: default_primary_credit(),
+            secondary_credit: default_secondary_credit(),
             weight_unit: WeightUnit::Lbs,
             one_rm_formula: OneRmFormula::Epley,
             start_date: None,
@@ -530,6 +896,7 @@ impl Default for Settings {
             set_type_filter: None,
             superset_filter: None,
             body_part_filter: None,
+            category_filter: None,
             exercise_filter: None,
             exercise_type_filter: None,
             difficulty_filter: None,
@@ -546,6 +913,10 @@ impl Default for Settings {
             last_file: None,
             hevy_api_key: None,
             last_sync: None,
+            date_format: DateFormat::Auto,
+            sync_mode: sync::SyncMode::default(),
+            sync_retry: sync::RetryPolicy::default(),
+            sync_templates: true,
             check_prs: false,
             github_repo: None,
             last_pr: None,
@@ -597,6 +968,10 @@ struct MyApp {
     last_loaded: Option<String>,
     toast_start: Option<Instant>,
     settings: Settings,
+    /// `settings.preferred_formulas` keyed by normalized exercise name.
+    preferred_formulas: plotting::PreferredFormulas,
+    /// Exercise templates endpoint, replaced by a mock server in tests.
+    templates_url: String,
     show_settings: bool,
     show_entries: bool,
     show_plot_window: bool,
@@ -617,9 +992,31 @@ struct MyApp {
     capture_rect: Option<egui::Rect>,
     settings_dirty: bool,
     show_mapping: bool,
+    show_hard_sets_window: bool,
+    show_sessions_window: bool,
+    show_session_analytics_window: bool,
+    show_calendar_window: bool,
+    show_rep_max_window: bool,
+    show_calibration_window: bool,
+    show_stalled_window: bool,
+    /// Also list plateaus that have since ended in the Stalled Lifts window.
+    stalled_show_past: bool,
+    /// Sessions built from `workouts`, newest first.
+    sessions: Vec<workout::Workout>,
+    /// Id of the session open in the sessions browser.
+    selected_session: Option<String>,
+    session_filter: String,
+    /// Record kind listed in the PR timeline; `None` lists every kind.
+    pr_kind_filter: Option<PrKind>,
+    pr_show_baselines: bool,
+    /// ISO week shown in the hard-sets window; `None` follows the current week.
+    hard_sets_week: Option<(i32, u32)>,
     mapping_exercises: Vec<String>,
     mapping_dirty: bool,
     mapping_entry: exercise_mapping::MuscleMapping,
+    catalog_entry: body_parts::ExerciseInfo,
+    alias_canonical: String,
+    alias_suggestions: Vec<(String, String, f64)>,
     mapping_toast_start: Option<Instant>,
     mapping_message: Option<String>,
     pr_toast_start: Option<Instant>,
@@ -629,6 +1026,17 @@ struct MyApp {
     load_rx: Option<mpsc::Receiver<LoadMessage>>,
     pending_filename: Option<String>,
     pending_path: Option<String>,
+    import_report: Option<ImportReport>,
+    show_import_report: bool,
+    merge_report: Option<store::MergeReport>,
+    show_merge_report: bool,
+    syncing: bool,
+    sync_progress: f32,
+    sync_rx: Option<mpsc::Receiver<SyncMessage>>,
+    sync_cancel: Arc<AtomicBool>,
+    sync_task: &'static str,
+    template_proposals: Vec<templates::MappingProposal>,
+    show_template_review: bool,
     show_point_details: bool,
     show_exercise_popup: bool,
     point_entries: Vec<WorkoutEntry>,
@@ -638,6 +1046,9 @@ impl Default for MyApp {
     fn default() -> Self {
         let settings = Settings::load();
         exercise_mapping::load();
+        exercise_catalog::load();
+        rpe_table::load();
+        let preferred_formulas = plotting::preferred_formulas(&settings.preferred_formulas);
         let show_exercise_stats = settings.show_exercise_stats;
         let show_pr_window = settings.show_pr_window;
         let show_exercise_panel = settings.show_exercise_panel;
@@ -647,6 +1058,13 @@ impl Default for MyApp {
         let show_overall_analysis_window = settings.show_overall_analysis_window;
         let show_weekly_summary_table_window = settings.show_weekly_summary_table_window;
         let show_mapping = settings.show_mapping;
+        let show_hard_sets_window = settings.show_hard_sets_window;
+        let show_sessions_window = settings.show_sessions_window;
+        let show_session_analytics_window = settings.show_session_analytics_window;
+        let show_calendar_window = settings.show_calendar_window;
+        let show_rep_max_window = settings.show_rep_max_window;
+        let show_calibration_window = settings.show_calibration_window;
+        let show_stalled_window = settings.show_stalled_window;
         let mut app = Self {
             workouts: Vec::new(),
             stats: BasicStats::default(),
@@ -658,6 +1076,8 @@ impl Default for MyApp {
             last_loaded: None,
             toast_start: None,
             settings,
+            preferred_formulas,
+            templates_url: sync::HEVY_TEMPLATES_URL.to_string(),
             show_settings: false,
             show_entries: false,
             show_plot_window: false,
@@ -678,9 +1098,26 @@ impl Default for MyApp {
             capture_rect: None,
             settings_dirty: false,
             show_mapping,
+            show_hard_sets_window,
+            show_sessions_window,
+            show_session_analytics_window,
+            show_calendar_window,
+            show_rep_max_window,
+            show_calibration_window,
+            show_stalled_window,
+            stalled_show_past: false,
+            sessions: Vec::new(),
+            selected_session: None,
+            session_filter: String::new(),
+            pr_kind_filter: None,
+            pr_show_baselines: false,
+            hard_sets_week: None,
             mapping_exercises: Vec::new(),
             mapping_dirty: false,
             mapping_entry: exercise_mapping::MuscleMapping::default(),
+            catalog_entry: body_parts::ExerciseInfo::default(),
+            alias_canonical: String::new(),
+            alias_suggestions: Vec::new(),
             mapping_toast_start: None,
             mapping_message: None,
             pr_toast_start: None,
@@ -690,6 +1127,17 @@ impl Default for MyApp {
             load_rx: None,
             pending_filename: None,
             pending_path: None,
+            import_report: None,
+            show_import_report: false,
+            merge_report: None,
+            show_merge_report: false,
+            syncing: false,
+            sync_progress: 0.0,
+            sync_rx: None,
+            sync_cancel: Arc::new(AtomicBool::new(false)),
+            sync_task: "",
+            template_proposals: Vec::new(),
+            show_template_review: false,
             show_point_details: false,
             show_exercise_popup: false,
             point_entries: Vec::new(),
@@ -702,12 +1150,24 @@ impl Default for MyApp {
         app.summary_sort = app.settings.summary_sort;
         app.summary_sort_ascending = app.settings.summary_sort_ascending;
 
-        if app.settings.auto_load_last {
+        let stored = store::load();
+        if !stored.is_empty() {
+            app.workouts = stored;
+            app.stats = compute_stats(
+                &app.workouts,
+                app.settings.start_date,
+                app.settings.end_date,
+            );
+            app.last_loaded = Some("local store".to_string());
+            app.toast_start = Some(Instant::now());
+        } else if app.settings.auto_load_last {
             if let Some(path) = app.settings.last_file.clone() {
                 let p = std::path::Path::new(&path);
                 if p.exists() {
                     if let Ok(file) = File::open(p) {
-                        if let Ok(entries) = parse_workout_csv(file, None) {
+                        if let Ok(entries) = parse_workout_csv(file, app.settings.date_format, None)
+                        {
+                            store::append(&entries);
                             app.workouts = entries;
                             app.stats = compute_stats(
                                 &app.workouts,
@@ -741,6 +1201,14 @@ impl Default for MyApp {
 }
 
 impl MyApp {
+    /// The configured 1RM formula with the per-exercise preferences applied.
+    fn formulas(&self) -> plotting::FormulaChoice<'_> {
+        plotting::FormulaChoice::with_preferred(
+            self.settings.one_rm_formula,
+            &self.preferred_formulas,
+        )
+    }
+
     fn sort_button(
         ui: &mut egui::Ui,
         label: &str,
@@ -805,76 +1273,241 @@ impl MyApp {
         self.load_rx = Some(rx);
         self.pending_filename = Some(filename);
         self.pending_path = path;
+        let format = self.settings.date_format;
         std::thread::spawn(move || {
             let sender = tx;
-            if let Err(e) = parse_workout_csv(reader, Some(sender.clone())) {
+            if let Err(e) = parse_workout_csv_with_report(reader, format, Some(sender.clone())) {
                 let _ = sender.send(LoadMessage::Error(e.to_string()));
             }
         });
     }
 
+    /// Parse several CSV exports on a worker thread and union them with the
+    /// loaded workouts at set level. Files are merged oldest first so the
+    /// most recently modified export wins conflicts.
+    fn start_merging(&mut self, mut paths: Vec<std::path::PathBuf>) {
+        paths.sort_by_key(|p| {
+            std::fs::metadata(p)
+                .and_then(|m| m.modified())
+                .unwrap_or(std::time::SystemTime::UNIX_EPOCH)
+        });
+        let (tx, rx) = mpsc::channel();
+        self.loading = true;
+        self.loading_progress = 0.0;
+        self.load_rx = Some(rx);
+        let existing = self.workouts.clone();
+        let format = self.settings.date_format;
+        std::thread::spawn(move || {
+            let mut sources = Vec::new();
+            if !existing.is_empty() {
+                sources.push(("loaded data".to_string(), existing));
+            }
+            let total = paths.len().max(1);
+            for (i, path) in paths.iter().enumerate() {
+                let name = path
+                    .file_name()
+                    .map(|f| f.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.display().to_string());
+                let parsed = File::open(path)
+                    .map_err(csv::Error::from)
+                    .and_then(|f| parse_workout_csv(f, format, None));
+                match parsed {
+                    Ok(entries) => sources.push((name, entries)),
+                    Err(e) => {
+                        let _ = tx.send(LoadMessage::Error(format!("{name}: {e}")));
+                        return;
+                    }
+                }
+                let _ = tx.send(LoadMessage::Progress((i + 1) as f32 / total as f32));
+            }
+            let (merged, report) = store::merge_sets(sources);
+            let _ = tx.send(LoadMessage::Merged(merged, report));
+        });
+    }
+
+    /// Run a Hevy API request on a worker thread, reporting page progress and
+    /// the result through `sync_rx`.
+    fn spawn_sync<F>(&mut self, task: &'static str, job: F)
+    where
+        F: FnOnce(&str, Box<dyn FnMut(u32, u32) -> bool + Send>) -> SyncMessage + Send + 'static,
+    {
+        if self.syncing {
+            return;
+        }
+        let Some(key) = sync::resolve_api_key(self.settings.hevy_api_key.as_deref()) else {
+            log::warn!("Hevy API key not set");
+            self.pr_message = Some("Set a Hevy API key in settings to sync.".to_string());
+            self.pr_toast_start = Some(Instant::now());
+            self.show_settings = true;
+            return;
+        };
+        log::info!("Using Hevy API key: {key}");
+
+        let (tx, rx) = mpsc::channel();
+        let cancel = Arc::new(AtomicBool::new(false));
+        self.syncing = true;
+        self.sync_progress = 0.0;
+        self.sync_rx = Some(rx);
+        self.sync_cancel = cancel.clone();
+        self.sync_task = task;
+
+        std::thread::spawn(move || {
+            let progress_tx = tx.clone();
+            let progress = move |page: u32, pages: u32| {
+                log::info!("Fetched page {page}/{pages}");
+                let _ = progress_tx.send(SyncMessage::Progress(page as f32 / pages as f32));
+                !cancel.load(Ordering::Relaxed)
+            };
+            let _ = tx.send(job(&key, Box::new(progress)));
+        });
+    }
+
     fn sync_from_hevy(&mut self) {
-        let forced_key = std::env::var("HEVY_API_KEY").ok();
-        if let Some(key) = sync::resolve_api_key(self.settings.hevy_api_key.as_deref()) {
-            log::info!("Using Hevy API key: {key}");
-            if let Some(path) = FileDialog::new().add_filter("CSV", &["csv"]).save_file() {
-                match sync::fetch_latest_workouts(&key, self.settings.last_sync.as_deref()) {
-                    Ok(mut new_entries) => {
-                        let existing: HashSet<String> = self
-                            .workouts
-                            .iter()
-                            .map(|e| e.raw.start_time.clone())
-                            .collect();
-                        new_entries.retain(|e| !existing.contains(&e.raw.start_time));
+        let mode = self.settings.sync_mode;
+        let last_sync = self.settings.last_sync.clone();
+        let retry = self.settings.sync_retry;
+        self.spawn_sync("Downloading workouts...", move |key, progress| {
+            let msg = match (mode, last_sync) {
+                (sync::SyncMode::Events, Some(since)) => {
+                    sync::fetch_workout_events(key, &since, &retry, progress)
+                        .map(SyncMessage::Events)
+                }
+                (_, last_sync) => {
+                    sync::fetch_latest_workouts(key, last_sync.as_deref(), &retry, progress)
+                        .map(SyncMessage::Workouts)
+                }
+            };
+            msg.unwrap_or_else(SyncMessage::Error)
+        });
+    }
 
-                        if let Err(e) = save_entries_csv(&path, &new_entries) {
-                            log::error!("Failed to save sync data: {e}");
-                        }
+    /// Download Hevy exercise templates to propose mappings for unmapped
+    /// exercises.
+    fn fetch_exercise_templates(&mut self) {
+        let retry = self.settings.sync_retry;
+        let url = self.templates_url.clone();
+        self.spawn_sync("Downloading exercise templates...", move |key, progress| {
+            sync::fetch_exercise_templates_with_url(&url, key, &retry, progress)
+                .map(SyncMessage::Templates)
+                .unwrap_or_else(SyncMessage::Error)
+        });
+    }
 
-                        self.workouts.append(&mut new_entries);
-                        self.stats = compute_stats(
-                            &self.workouts,
-                            self.settings.start_date,
-                            self.settings.end_date,
-                        );
-                        self.update_filter_values();
-                        self.last_loaded =
-                            path.file_name().map(|f| f.to_string_lossy().to_string());
+    /// Loaded exercises that have no primary muscle group mapped.
+    fn unmapped_exercises(&self) -> Vec<String> {
+        unique_exercises(&self.workouts, None, None)
+            .into_iter()
+            .filter(|e| body_parts::body_part_for(e).is_none())
+            .collect()
+    }
 
-                        self.settings.last_sync = Some(Utc::now().to_rfc3339());
-                        self.settings.save();
+    fn finish_sync(&mut self, msg: SyncMessage) {
+        match msg {
+            SyncMessage::Progress(_) => return,
+            SyncMessage::Workouts(mut new_entries) => {
+                let existing: HashSet<String> =
+                    self.workouts.iter().map(store::start_key).collect();
+                new_entries.retain(|e| !existing.contains(&store::start_key(e)));
+                store::append(&new_entries);
+                self.workouts.append(&mut new_entries);
+            }
+            SyncMessage::Events(events) => {
+                let (updated, deleted) = sync::apply_workout_events(&mut self.workouts, events);
+                log::info!("Applied {updated} updated and {deleted} deleted workouts");
+                store::replace_all(&self.workouts);
+            }
+            SyncMessage::Templates(templates) => {
+                self.syncing = false;
+                let unmapped = self.unmapped_exercises();
+                self.template_proposals = templates::propose(&unmapped, &templates);
+                if self.template_proposals.is_empty() {
+                    self.mapping_message = Some(format!(
+                        "No Hevy templates matched the {} unmapped exercises",
+                        unmapped.len()
+                    ));
+                    self.mapping_toast_start = Some(Instant::now());
+                } else {
+                    self.show_template_review = true;
+                }
+                return;
+            }
+            SyncMessage::Error(e) => {
+                self.syncing = false;
+                self.handle_sync_error(e);
+                return;
+            }
+        }
+        self.syncing = false;
 
-                        self.toast_start = Some(Instant::now());
-                    }
-                    Err(sync::SyncError::Unauthorized(body)) => {
-                        log::error!("Sync failed: unauthorized: {body}");
-                        self.pr_message = Some(
-                            "Hevy API key unauthorized. Please update it in settings.".to_string(),
-                        );
-                        self.pr_toast_start = Some(Instant::now());
-                        if forced_key.is_none() {
-                            self.settings.hevy_api_key = None;
-                            self.settings.save();
-                        }
-                        self.show_settings = true;
-                    }
-                    Err(sync::SyncError::Forbidden(body)) => {
-                        log::error!("Sync failed: forbidden: {body}");
-                        self.pr_message = Some(
-                            "Hevy API key forbidden. Please check the key or its permissions."
-                                .to_string(),
-                        );
-                        self.pr_toast_start = Some(Instant::now());
-                        self.show_settings = true;
-                    }
-                    Err(e) => {
-                        log::error!("Sync failed: {e}");
-                    }
+        self.stats = compute_stats(
+            &self.workouts,
+            self.settings.start_date,
+            self.settings.end_date,
+        );
+        self.update_filter_values();
+        self.last_loaded = Some("Hevy".to_string());
+
+        self.settings.last_sync = Some(Utc::now().to_rfc3339());
+        self.settings.save();
+
+        self.toast_start = Some(Instant::now());
+
+        if self.settings.sync_templates && !self.unmapped_exercises().is_empty() {
+            self.fetch_exercise_templates();
+        }
+    }
+
+    /// Drain messages from the sync worker. Returns whether a sync is still
+    /// running.
+    fn poll_sync(&mut self) -> bool {
+        let Some(rx) = self.sync_rx.take() else {
+            return false;
+        };
+        while let Ok(msg) = rx.try_recv() {
+            match msg {
+                SyncMessage::Progress(p) => {
+                    self.sync_progress = p;
                 }
+                msg => self.finish_sync(msg),
             }
-        } else {
-            log::warn!("Hevy API key not set");
         }
+        // `finish_sync` may have started a follow-up request with its own
+        // receiver, which must not be replaced by the drained one.
+        if self.syncing && self.sync_rx.is_none() {
+            self.sync_rx = Some(rx);
+        }
+        self.syncing
+    }
+
+    fn handle_sync_error(&mut self, err: sync::SyncError) {
+        match err {
+            sync::SyncError::Unauthorized(body) => {
+                log::error!("Sync failed: unauthorized: {body}");
+                self.pr_message =
+                    Some("Hevy API key unauthorized. Please update it in settings.".to_string());
+                if std::env::var("HEVY_API_KEY").is_err() {
+                    self.settings.hevy_api_key = None;
+                    self.settings.save();
+                }
+                self.show_settings = true;
+            }
+            sync::SyncError::Forbidden(body) => {
+                log::error!("Sync failed: forbidden: {body}");
+                self.pr_message = Some(
+                    "Hevy API key forbidden. Please check the key or its permissions.".to_string(),
+                );
+                self.show_settings = true;
+            }
+            sync::SyncError::Cancelled => {
+                log::info!("Sync cancelled");
+                self.pr_message = Some("Sync cancelled.".to_string());
+            }
+            e => {
+                log::error!("Sync failed: {e}");
+                self.pr_message = Some(format!("Sync failed: {e}"));
+            }
+        }
+        self.pr_toast_start = Some(Instant::now());
     }
 
     fn sort_summary_stats(
@@ -923,9 +1556,12 @@ impl MyApp {
         }
     }
 
+    /// Recompute the filter choices and sessions derived from the loaded
+    /// workouts.
     fn update_filter_values(&mut self) {
         self.set_types = analysis::unique_set_types(&self.workouts);
         self.superset_ids = analysis::unique_superset_ids(&self.workouts);
+        self.sessions = workout::build_workouts(&self.workouts);
     }
 
     fn entry_matches_filters(&self, e: &WorkoutEntry) -> bool {
@@ -970,22 +1606,22 @@ impl MyApp {
             }
         }
         if let Some(min_w) = self.settings.min_weight {
-            if e.weight.unwrap() < min_w {
+            if e.weight.map(|w| w < min_w).unwrap_or(true) {
                 return false;
             }
         }
         if let Some(max_w) = self.settings.max_weight {
-            if e.weight.unwrap() > max_w {
+            if e.weight.map(|w| w > max_w).unwrap_or(true) {
                 return false;
             }
         }
         if let Some(min_r) = self.settings.min_reps {
-            if e.reps.unwrap() < min_r {
+            if e.reps.map(|r| r < min_r).unwrap_or(true) {
                 return false;
             }
         }
         if let Some(max_r) = self.settings.max_reps {
-            if e.reps.unwrap() > max_r {
+            if e.reps.map(|r| r > max_r).unwrap_or(true) {
                 return false;
             }
         }
@@ -1005,6 +1641,13 @@ impl MyApp {
                 _ => return false,
             }
         }
+        if let Some(ref cat) = self.settings.category_filter
+            && !body_parts::categories_for(&e.exercise)
+                .iter()
+                .any(|c| c.eq_ignore_ascii_case(cat))
+        {
+            return false;
+        }
         if let Some(kind) = self.settings.exercise_type_filter {
             match e.exercise_type() {
                 Some(k) if k == kind => {}
@@ -1046,6 +1689,43 @@ impl MyApp {
             .collect()
     }
 
+    /// The loaded sessions reduced to the sets that match the current filters.
+    /// Sessions left without sets are dropped.
+    fn filtered_sessions(&self) -> Vec<workout::Workout> {
+        self.sessions
+            .iter()
+            .filter_map(|w| {
+                let exercises: Vec<workout::WorkoutExercise> = w
+                    .exercises
+                    .iter()
+                    .filter_map(|x| {
+                        let sets: Vec<WorkoutEntry> = x
+                            .sets
+                            .iter()
+                            .filter(|e| self.entry_matches_filters(e))
+                            .cloned()
+                            .collect();
+                        (!sets.is_empty()).then(|| workout::WorkoutExercise {
+                            name: x.name.clone(),
+                            notes: x.notes.clone(),
+                            superset_id: x.superset_id.clone(),
+                            sets,
+                        })
+                    })
+                    .collect();
+                (!exercises.is_empty()).then(|| workout::Workout {
+                    id: w.id.clone(),
+                    title: w.title.clone(),
+                    description: w.description.clone(),
+                    date: w.date.clone(),
+                    start: w.start,
+                    end: w.end,
+                    exercises,
+                })
+            })
+            .collect()
+    }
+
     /// Return entries that match the current filters and the selected exercises.
     fn filtered_selected_entries(&self) -> Vec<WorkoutEntry> {
         match &self.selected_exercises {
@@ -1067,19 +1747,22 @@ impl MyApp {
         self.stats = compute_stats(&entries, self.settings.start_date, self.settings.end_date);
     }
 
+    /// Regroup derived data after exercise aliases changed and schedule the
+    /// mapping to be saved.
+    fn aliases_changed(&mut self) {
+        self.update_filter_values();
+        self.update_selected_stats();
+        self.mapping_dirty = true;
+    }
+
     fn exercise_set_counts(&self, exercise: &str) -> (usize, usize, usize) {
         use std::collections::HashSet;
         let mut workouts = HashSet::new();
         let mut working = 0usize;
         let mut warmups = 0usize;
         for e in self.filtered_entry_refs() {
-            if e.exercise.eq_ignore_ascii_case(exercise) {
-                let id = format!(
-                    "{}{}",
-                    e.raw.title.as_deref().unwrap_or(""),
-                    e.raw.start_time
-                );
-                workouts.insert(id);
+            if normalize_exercise(&e.exercise) == normalize_exercise(exercise) {
+                workouts.insert(store::workout_key(e));
                 if e.raw
                     .set_type
                     .as_deref()
@@ -1192,10 +1875,21 @@ impl MyApp {
             ui.vertical(|ui| {
                 let stats_map = analysis::aggregate_exercise_stats(
                     filtered,
-                    self.settings.one_rm_formula,
+                    self.formulas(),
                     self.settings.start_date,
                     self.settings.end_date,
                 );
+                let plateaus = if self.settings.show_plateaus {
+                    analysis::detect_plateaus(
+                        filtered,
+                        self.formulas(),
+                        self.settings.start_date,
+                        self.settings.end_date,
+                        self.settings.plateau,
+                    )
+                } else {
+                    Vec::new()
+                };
                 if self.settings.show_weight {
                     let x_label = match self.settings.x_axis {
                         XAxis::Date => "Date",
@@ -1265,6 +1959,9 @@ impl MyApp {
                                             all_points.push(*p);
                                             point_exercises.push(label.clone());
                                         }
+                                        for band in plotting::plateau_bands(&lw, ex, &plateaus) {
+                                            plot_ui.polygon(band);
+                                        }
                                     }
                                     plot_ui.line(lw.line);
                                     if self.settings.show_weight_trend {
@@ -1443,7 +2140,7 @@ impl MyApp {
                             for lr in estimated_1rm_line(
                                 filtered,
                                 sel,
-                                self.settings.one_rm_formula,
+                                self.formulas(),
                                 self.settings.start_date,
                                 self.settings.end_date,
                                 self.settings.x_axis,
@@ -1466,6 +2163,9 @@ impl MyApp {
                                             all_points.push(*p);
                                             point_exercises.push(label.clone());
                                         }
+                                        for band in plotting::plateau_bands(&lr, ex, &plateaus) {
+                                            plot_ui.polygon(band);
+                                        }
                                     }
                                     plot_ui.line(lr.line);
                                     if self.settings.highlight_max {
@@ -1812,6 +2512,7 @@ impl MyApp {
                                 self.settings.weight_unit,
                                 self.settings.body_part_volume_aggregation,
                                 ma,
+                                self.settings.muscle_credit(),
                             ) {
                                 plot_ui.line(l);
                             }
@@ -1822,6 +2523,7 @@ impl MyApp {
                                     self.settings.end_date,
                                     self.settings.weight_unit,
                                     self.settings.body_part_volume_aggregation,
+                                    self.settings.muscle_credit(),
                                 ) {
                                     plot_ui.line(l);
                                 }
@@ -1831,28 +2533,82 @@ impl MyApp {
                     first_resp.get_or_insert(resp);
                 }
 
-                if self.settings.show_body_part_distribution {
-                    let (bars, body_parts) = body_part_distribution(
-                        filtered,
-                        self.settings.start_date,
-                        self.settings.end_date,
-                    );
-                    let pie =
-                        body_part_pie(filtered, self.settings.start_date, self.settings.end_date);
-                    let bp_for_axis = body_parts.clone();
-                    ui.heading("Body Part Distribution");
-                    let resp = Plot::new("body_part_distribution_plot")
+                if self.settings.show_category_volume {
+                    let x_label = match self.settings.x_axis {
+                        XAxis::Date => "Date",
+                        XAxis::WorkoutIndex => "Workout",
+                    };
+                    let unit_label = match self.settings.weight_unit {
+                        WeightUnit::Kg => "kg",
+                        WeightUnit::Lbs => "lbs",
+                    };
+                    ui.heading("Category Volume Over Time");
+                    let resp = Plot::new("category_volume_plot")
                         .width(size.x)
                         .height(size.y)
-                        .x_axis_formatter(move |mark, _, _| {
-                            bp_for_axis
-                                .get(mark.value as usize)
-                                .cloned()
-                                .unwrap_or_default()
+                        .x_axis_formatter(move |mark, _chars, _| {
+                            if x_axis == XAxis::Date {
+                                NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32)
+                                    .map(|d| d.format("%Y-%m-%d").to_string())
+                                    .unwrap_or_else(|| format!("{:.0}", mark.value))
+                            } else {
+                                format!("{:.0}", mark.value)
+                            }
                         })
-                        .x_axis_label("Body Part")
-                        .y_axis_label("Sets")
-                        .legend(Legend::default())
+                        .x_axis_label(x_label)
+                        .y_axis_label(format!("Volume ({unit_label})"))
+                        .legend(Legend::default())
+                        .show(ui, |plot_ui| {
+                            let ma = if self.settings.show_smoothed {
+                                Some(self.settings.ma_window)
+                            } else {
+                                None
+                            };
+                            for l in category_volume_line(
+                                filtered,
+                                self.settings.start_date,
+                                self.settings.end_date,
+                                self.settings.x_axis,
+                                self.settings.weight_unit,
+                                self.settings.body_part_volume_aggregation,
+                                ma,
+                            ) {
+                                plot_ui.line(l);
+                            }
+                        });
+                    if body_parts::all_categories().is_empty() {
+                        ui.label("Set a category in the Muscle Mapping window to group exercises.");
+                    }
+                    first_resp.get_or_insert(resp);
+                }
+
+                if self.settings.show_body_part_distribution {
+                    let (bars, body_parts) = body_part_distribution(
+                        filtered,
+                        self.settings.start_date,
+                        self.settings.end_date,
+                        self.settings.muscle_credit(),
+                    );
+                    let pie = body_part_pie(
+                        filtered,
+                        self.settings.start_date,
+                        self.settings.end_date,
+                        self.settings.muscle_credit(),
+                    );
+                    let bp_for_axis = body_parts.clone();
+                    ui.heading("Body Part Distribution");
+                    let resp = Plot::new("body_part_distribution_plot")
+                        .width(size.x)
+                        .height(size.y)
+                        .x_axis_formatter(move |mark, _, _| {
+                            bp_for_axis
+                                .get(mark.value as usize)
+                                .cloned()
+                                .unwrap_or_default()
+                        })
+                        .x_axis_label("Body Part")
+                        .y_axis_label("Sets")
+                        .legend(Legend::default())
                         .show(ui, |plot_ui| {
                             plot_ui.bar_chart(bars);
                         });
@@ -1888,6 +2644,139 @@ impl MyApp {
                     first_resp.get_or_insert(resp);
                 }
 
+                let date_formatter =
+                    move |mark: egui_plot::GridMark,
+                          _chars: usize,
+                          _range: &std::ops::RangeInclusive<f64>| {
+                        if x_axis == XAxis::Date {
+                            NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32)
+                                .map(|d| d.format("%Y-%m-%d").to_string())
+                                .unwrap_or_else(|| format!("{:.0}", mark.value))
+                        } else {
+                            format!("{:.0}", mark.value)
+                        }
+                    };
+                let day_formatter =
+                    |mark: egui_plot::GridMark,
+                     _chars: usize,
+                     _range: &std::ops::RangeInclusive<f64>| {
+                        NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32)
+                            .map(|d| d.format("%Y-%m-%d").to_string())
+                            .unwrap_or_default()
+                    };
+                let ex_for_bars = if sel.len() == 1 {
+                    Some(sel[0].as_str())
+                } else {
+                    None
+                };
+
+                if self.settings.show_pace {
+                    let x_label = match self.settings.x_axis {
+                        XAxis::Date => "Date",
+                        XAxis::WorkoutIndex => "Workout",
+                    };
+                    ui.heading("Pace");
+                    let resp = Plot::new("pace_plot")
+                        .width(size.x)
+                        .height(size.y)
+                        .x_axis_formatter(date_formatter)
+                        .x_axis_label(x_label)
+                        .y_axis_label("Pace (min/mi)")
+                        .legend(Legend::default())
+                        .show(ui, |plot_ui| {
+                            for l in pace_line(
+                                filtered,
+                                sel,
+                                self.settings.start_date,
+                                self.settings.end_date,
+                                self.settings.x_axis,
+                            ) {
+                                plot_ui.line(l);
+                            }
+                        });
+                    first_resp.get_or_insert(resp);
+                }
+
+                if self.settings.show_distance_per_week {
+                    ui.heading("Distance Per Week");
+                    let resp = Plot::new("distance_per_week_plot")
+                        .width(size.x)
+                        .height(size.y)
+                        .x_axis_formatter(day_formatter)
+                        .x_axis_label("Week")
+                        .y_axis_label("Distance (mi)")
+                        .legend(Legend::default())
+                        .show(ui, |plot_ui| {
+                            plot_ui.bar_chart(distance_per_week_bar(
+                                filtered,
+                                ex_for_bars,
+                                self.settings.start_date,
+                                self.settings.end_date,
+                            ));
+                        });
+                    first_resp.get_or_insert(resp);
+                }
+
+                if self.settings.show_hold_time {
+                    ui.heading("Hold Time Per Day");
+                    let resp = Plot::new("hold_time_plot")
+                        .width(size.x)
+                        .height(size.y)
+                        .x_axis_formatter(day_formatter)
+                        .x_axis_label("Date")
+                        .y_axis_label("Seconds")
+                        .legend(Legend::default())
+                        .show(ui, |plot_ui| {
+                            plot_ui.bar_chart(hold_time_bar(
+                                filtered,
+                                ex_for_bars,
+                                self.settings.start_date,
+                                self.settings.end_date,
+                            ));
+                        });
+                    first_resp.get_or_insert(resp);
+                }
+
+                if self.settings.show_bodyweight_load {
+                    let x_label = match self.settings.x_axis {
+                        XAxis::Date => "Date",
+                        XAxis::WorkoutIndex => "Workout",
+                    };
+                    let unit_label = match self.settings.weight_unit {
+                        WeightUnit::Kg => "kg",
+                        WeightUnit::Lbs => "lbs",
+                    };
+                    ui.heading("Bodyweight-Adjusted Load");
+                    match self.settings.body_weight {
+                        Some(bw) => {
+                            let resp = Plot::new("bodyweight_load_plot")
+                                .width(size.x)
+                                .height(size.y)
+                                .x_axis_formatter(date_formatter)
+                                .x_axis_label(x_label)
+                                .y_axis_label(format!("Load ({unit_label})"))
+                                .legend(Legend::default())
+                                .show(ui, |plot_ui| {
+                                    for l in bodyweight_load_line(
+                                        filtered,
+                                        sel,
+                                        bw,
+                                        self.settings.start_date,
+                                        self.settings.end_date,
+                                        self.settings.x_axis,
+                                        self.settings.weight_unit,
+                                    ) {
+                                        plot_ui.line(l);
+                                    }
+                                });
+                            first_resp.get_or_insert(resp);
+                        }
+                        None => {
+                            ui.label("Set your body weight in the settings to see this plot.");
+                        }
+                    }
+                }
+
                 if self.settings.show_weight_reps_scatter {
                     let unit_label = match self.settings.weight_unit {
                         WeightUnit::Kg => "kg",
@@ -2085,104 +2974,1168 @@ impl MyApp {
                             self.settings_dirty = true;
                         }
                     });
-                    let f = self.settings.weight_unit.factor();
-                    let table_height = size.y.min(150.0);
-                    let render_table = |ui: &mut egui::Ui| {
-                        egui::Grid::new("weekly_summary_table")
+                    let f = self.settings.weight_unit.factor();
+                    let table_height = size.y.min(150.0);
+                    let render_table = |ui: &mut egui::Ui| {
+                        egui::Grid::new("weekly_summary_table")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("Year");
+                                ui.label("Week");
+                                ui.label("Sets");
+                                ui.label("Volume");
+                                ui.label("ACWR");
+                                ui.end_row();
+                                for w in &weeks {
+                                    ui.label(w.year.to_string());
+                                    ui.label(format!("{:02}", w.week));
+                                    ui.label(w.total_sets.to_string());
+                                    ui.label(format!("{:.1}", w.total_volume * f));
+                                    if let Some(r) = w.acwr {
+                                        if w.over_threshold {
+                                            ui.label(
+                                                RichText::new(format!("{r:.2} ⚠"))
+                                                    .color(Color32::RED),
+                                            );
+                                        } else {
+                                            ui.label(format!("{r:.2}"));
+                                        }
+                                    } else {
+                                        ui.label("-");
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    };
+                    if !self.show_weekly_summary_table_window {
+                        egui::ScrollArea::vertical()
+                            .max_height(table_height)
+                            .show(ui, |ui| {
+                                render_table(ui);
+                            });
+                    }
+                    let plot_height = (size.y
+                        - if self.show_weekly_summary_table_window {
+                            0.0
+                        } else {
+                            table_height
+                        })
+                    .max(50.0);
+                    let resp = Plot::new("weekly_summary_plot")
+                        .width(size.x)
+                        .height(plot_height)
+                        .x_axis_formatter(move |mark, _chars, _| {
+                            let idx = mark.value.round() as usize;
+                            weeks_for_axis
+                                .get(idx)
+                                .map(|w| format!("{}-{:02}", w.year, w.week))
+                                .unwrap_or_else(|| format!("{:.0}", mark.value))
+                        })
+                        .x_axis_label("Week")
+                        .y_axis_label(format!("Volume ({unit_label}) / Sets"))
+                        .legend(Legend::default())
+                        .show(ui, |plot_ui| {
+                            plot_ui.bar_chart(bars);
+                            plot_ui.line(line);
+                        });
+
+                    first_resp.get_or_insert(resp);
+                    if self.show_weekly_summary_table_window {
+                        let ctx = ui.ctx();
+                        let mut open = self.show_weekly_summary_table_window;
+                        egui::Window::new("Weekly Summary Table")
+                            .open(&mut open)
+                            .resizable(true)
+                            .vscroll(true)
+                            .show(ctx, |ui| {
+                                render_table(ui);
+                            });
+                        self.show_weekly_summary_table_window = open;
+                        if self.settings.show_weekly_summary_table_window
+                            != self.show_weekly_summary_table_window
+                        {
+                            self.settings.show_weekly_summary_table_window =
+                                self.show_weekly_summary_table_window;
+                            self.settings_dirty = true;
+                        }
+                    }
+                }
+            });
+        });
+
+        first_resp.unwrap_or_else(|| {
+            ui.heading("No Data");
+            Plot::new("empty_plot")
+                .width(size.x)
+                .height(size.y)
+                .x_axis_label("")
+                .y_axis_label("")
+                .show(ui, |_ui| {})
+        })
+    }
+
+    /// Collapsible group of exercises in the exercise menu with bulk
+    /// selection buttons.
+    fn exercise_group_menu(
+        &mut self,
+        ui: &mut egui::Ui,
+        id: impl std::hash::Hash,
+        name: &str,
+        group_exs: &[String],
+        top_matches: &HashSet<String>,
+    ) {
+        let all_selected = self.selected_exercises.is_all()
+            || group_exs
+                .iter()
+                .all(|e| self.selected_exercises.contains(e));
+        let mut label = name.to_string();
+        if all_selected {
+            label.push_str(" ✓");
+        }
+        egui::CollapsingHeader::new(label)
+            .id_source(id)
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("Select All").clicked() {
+                        if self.selected_exercises.is_all() || self.selected_exercises.is_none() {
+                            self.selected_exercises =
+                                ExerciseSelection::Selected(group_exs.to_vec());
+                        } else {
+                            for ex in group_exs {
+                                if !self.selected_exercises.contains(ex) {
+                                    self.selected_exercises.push(ex.clone());
+                                }
+                            }
+                        }
+                        self.update_selected_stats();
+                    }
+                    if ui.button("Deselect All").clicked() {
+                        if self.selected_exercises.is_all() {
+                            let mut all: Vec<String> = unique_exercises(
+                                &self.workouts,
+                                self.settings.start_date,
+                                self.settings.end_date,
+                            );
+                            all.retain(|e| !group_exs.contains(e));
+                            self.selected_exercises = ExerciseSelection::Selected(all);
+                        } else if self.selected_exercises.is_none() {
+                            // nothing to do
+                        } else {
+                            self.selected_exercises.retain(|e| !group_exs.contains(e));
+                        }
+                        self.update_selected_stats();
+                    }
+                });
+                for ex in group_exs {
+                    let mut sel = self.selected_exercises.contains(ex);
+                    let label = if top_matches.contains(ex) && !self.search_query.is_empty() {
+                        RichText::new(ex).color(egui::Color32::LIGHT_GREEN)
+                    } else {
+                        RichText::new(ex)
+                    };
+                    if ui.add(egui::Checkbox::new(&mut sel, label)).changed() {
+                        if sel {
+                            self.selected_exercises.push(ex.clone());
+                        } else {
+                            self.selected_exercises.retain(|e| e != ex);
+                        }
+                        self.update_selected_stats();
+                    }
+                }
+            });
+    }
+
+    /// Exercises whose recent sessions show no progress or a drop in estimated
+    /// 1RM.
+    fn draw_stalled_lifts(&mut self, ui: &mut egui::Ui) {
+        let mut changed = false;
+        ui.horizontal(|ui| {
+            ui.label("Compare windows of");
+            let mut len = self.settings.plateau.length.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut len).desired_width(30.0))
+                .changed()
+                && let Ok(v) = len.parse::<u32>()
+            {
+                self.settings.plateau.length = v.max(1);
+                changed = true;
+            }
+            let window = &mut self.settings.plateau.window;
+            egui::ComboBox::from_id_source("plateau_window")
+                .selected_text(match window {
+                    PlateauWindow::Weeks => "weeks",
+                    PlateauWindow::Sessions => "sessions",
+                })
+                .show_ui(ui, |ui| {
+                    changed |= ui
+                        .selectable_value(window, PlateauWindow::Weeks, "weeks")
+                        .changed();
+                    changed |= ui
+                        .selectable_value(window, PlateauWindow::Sessions, "sessions")
+                        .changed();
+                });
+            ui.label("Regression below (%):");
+            let mut pct = self.settings.plateau.regression_pct.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut pct).desired_width(30.0))
+                .changed()
+                && let Ok(v) = pct.parse::<f32>()
+            {
+                self.settings.plateau.regression_pct = v.max(0.0);
+                changed = true;
+            }
+        });
+        if changed {
+            self.settings_dirty = true;
+        }
+        ui.checkbox(&mut self.stalled_show_past, "Include past plateaus");
+        ui.label(
+            RichText::new(
+                "Each session's window is compared with the one before it. Stalled: \
+                 neither the best estimated 1RM nor the heaviest set went up. \
+                 Regressed: the 1RM fell by more than the threshold.",
+            )
+            .small(),
+        );
+
+        let entries = self.filtered_entries();
+        let mut plateaus = analysis::detect_plateaus(
+            &entries,
+            self.formulas(),
+            self.settings.start_date,
+            self.settings.end_date,
+            self.settings.plateau,
+        );
+        if !self.stalled_show_past {
+            plateaus.retain(|p| p.ongoing);
+        }
+        if plateaus.is_empty() {
+            ui.label("No stalled lifts.");
+            return;
+        }
+        plateaus.sort_by_key(|p| (p.kind != PlateauKind::Regressed, !p.ongoing, p.start));
+
+        let factor = self.settings.weight_unit.factor();
+        let unit = match self.settings.weight_unit {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lbs => "lbs",
+        };
+        let mut show: Option<String> = None;
+        egui::ScrollArea::vertical().show(ui, |ui| {
+            egui::Grid::new("stalled_lifts_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Exercise");
+                    ui.label("Status");
+                    ui.label("Since");
+                    ui.label("Until");
+                    ui.label("Sessions");
+                    ui.label(format!("1RM Before ({unit})"));
+                    ui.label(format!("Best 1RM ({unit})"));
+                    ui.label("Change");
+                    ui.end_row();
+                    for p in &plateaus {
+                        if ui
+                            .link(&p.exercise)
+                            .on_hover_text("Plot this exercise")
+                            .clicked()
+                        {
+                            show = Some(p.exercise.clone());
+                        }
+                        let color = match p.kind {
+                            PlateauKind::Stalled => Color32::from_rgb(255, 165, 0),
+                            PlateauKind::Regressed => Color32::RED,
+                        };
+                        ui.label(RichText::new(p.kind.label()).color(color));
+                        ui.label(p.start.format("%Y-%m-%d").to_string());
+                        if p.ongoing {
+                            ui.label("now");
+                        } else {
+                            ui.label(p.end.format("%Y-%m-%d").to_string());
+                        }
+                        ui.label(p.sessions.to_string());
+                        ui.label(format!("{:.1}", p.reference * factor));
+                        ui.label(format!("{:.1}", p.best * factor));
+                        ui.label(format!("{:+.1}%", p.change_pct()));
+                        ui.end_row();
+                    }
+                });
+        });
+        if let Some(ex) = show {
+            self.selected_exercises = ExerciseSelection::Selected(vec![ex]);
+            self.update_selected_stats();
+        }
+    }
+
+    /// Error of every rep formula against tested singles, with a per-exercise
+    /// formula picker.
+    fn draw_calibration(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Predict from the previous (weeks):");
+            let mut w = self.settings.calibration_weeks.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut w).desired_width(30.0))
+                .changed()
+                && let Ok(v) = w.parse::<u32>()
+            {
+                self.settings.calibration_weeks = v.max(1);
+                self.settings_dirty = true;
+            }
+        });
+        let entries = self.filtered_entries();
+        let calibrations = analysis::calibrate_formulas(
+            &entries,
+            self.settings.start_date,
+            self.settings.end_date,
+            self.settings.calibration_weeks,
+        );
+        ui.label(
+            RichText::new(
+                "Mean absolute error in percent of each tested single, predicted from \
+                 the best estimate of the sets before it. The best fit is green.",
+            )
+            .small(),
+        );
+        if calibrations.is_empty() {
+            ui.label("No tested singles with earlier sets of 2-12 reps.");
+            return;
+        }
+
+        let mut changed = false;
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("calibration_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Exercise");
+                    ui.label("Tests");
+                    for f in analysis::REP_FORMULAS {
+                        ui.label(format!("{f:?}"));
+                    }
+                    ui.label("Use");
+                    ui.end_row();
+                    for cal in &calibrations {
+                        ui.label(&cal.exercise);
+                        ui.label(cal.tests.to_string());
+                        for f in analysis::REP_FORMULAS {
+                            match cal.fits.iter().find(|fit| fit.formula == f) {
+                                Some(fit) => {
+                                    let mut text =
+                                        RichText::new(format!("{:.1}%", fit.mean_abs_error));
+                                    if cal.best() == Some(f) {
+                                        text = text.color(Color32::GREEN).strong();
+                                    }
+                                    ui.label(text).on_hover_text(format!(
+                                        "Bias {:+.1}% over {} tests",
+                                        fit.bias, fit.samples
+                                    ));
+                                }
+                                None => {
+                                    ui.label("-");
+                                }
+                            }
+                        }
+                        let current = self.settings.preferred_formulas.get(&cal.exercise).copied();
+                        let mut choice = current;
+                        ui.horizontal(|ui| {
+                            egui::ComboBox::from_id_source(("preferred_formula", &cal.exercise))
+                                .selected_text(
+                                    choice.map_or("Global".to_string(), |f| format!("{f:?}")),
+                                )
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut choice, None, "Global");
+                                    for f in analysis::REP_FORMULAS {
+                                        ui.selectable_value(&mut choice, Some(f), format!("{f:?}"));
+                                    }
+                                });
+                            if let Some(best) = cal.best()
+                                && choice != Some(best)
+                                && ui.button("Use Best").clicked()
+                            {
+                                choice = Some(best);
+                            }
+                        });
+                        if choice != current {
+                            match choice {
+                                Some(f) => {
+                                    self.settings
+                                        .preferred_formulas
+                                        .insert(cal.exercise.clone(), f);
+                                }
+                                None => {
+                                    self.settings.preferred_formulas.remove(&cal.exercise);
+                                }
+                            }
+                            changed = true;
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+        if !self.settings.preferred_formulas.is_empty() && ui.button("Clear Preferences").clicked()
+        {
+            self.settings.preferred_formulas.clear();
+            changed = true;
+        }
+        if changed {
+            self.preferred_formulas =
+                plotting::preferred_formulas(&self.settings.preferred_formulas);
+            self.settings_dirty = true;
+        }
+    }
+
+    /// Actual and estimated rep maxes for 1-20 reps, one row per selected
+    /// exercise, with stale cells dimmed and soft cells highlighted.
+    fn draw_rep_max_matrix(&mut self, ui: &mut egui::Ui) {
+        let entries = self.filtered_selected_entries();
+        let today = Local::now().date_naive();
+        let stale_before =
+            today.checked_sub_months(chrono::Months::new(self.settings.rep_max_stale_months));
+        let matrix: Vec<(String, Vec<analysis::RepMaxCell>)> =
+            unique_exercises(&entries, self.settings.start_date, self.settings.end_date)
+                .into_iter()
+                .map(|ex| {
+                    let cells = analysis::rep_max_matrix(
+                        &entries,
+                        &ex,
+                        self.formulas(),
+                        self.settings.start_date,
+                        self.settings.end_date,
+                        stale_before,
+                    );
+                    (ex, cells)
+                })
+                .filter(|(_, cells)| cells.iter().any(|c| c.actual.is_some()))
+                .collect();
+
+        ui.horizontal(|ui| {
+            ui.label("Stale after (months):");
+            let mut m = self.settings.rep_max_stale_months.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut m).desired_width(30.0))
+                .changed()
+                && let Ok(v) = m.parse::<u32>()
+            {
+                self.settings.rep_max_stale_months = v;
+                self.settings_dirty = true;
+            }
+            if ui.button("Export").clicked()
+                && let Some(path) = FileDialog::new()
+                    .add_filter("JSON", &["json"])
+                    .add_filter("CSV", &["csv"])
+                    .save_file()
+            {
+                let is_csv = path
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| e.eq_ignore_ascii_case("csv"));
+                let result = if is_csv {
+                    save_rep_max_csv(&path, &matrix).map_err(|e| e.to_string())
+                } else {
+                    save_rep_max_json(&path, &matrix).map_err(|e| e.to_string())
+                };
+                if let Err(e) = result {
+                    log::error!("Failed to export rep maxes: {e}");
+                }
+            }
+        });
+        ui.label(
+            RichText::new(format!(
+                "Each cell shows the best weight / the weight estimated from the best {:?} 1RM. \
+                 Orange cells trail the estimate by more than {:.0}%; grey cells are stale.",
+                self.settings.one_rm_formula,
+                analysis::SOFT_REP_MAX_GAP * 100.0
+            ))
+            .small(),
+        );
+        if matrix.is_empty() {
+            ui.label("No weighted sets for the selected exercises.");
+            return;
+        }
+
+        let f = self.settings.weight_unit.factor();
+        egui::ScrollArea::both().show(ui, |ui| {
+            egui::Grid::new("rep_max_grid")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Exercise");
+                    for reps in analysis::REP_MAX_RANGE {
+                        ui.label(format!("{reps}RM"));
+                    }
+                    ui.end_row();
+                    for (ex, cells) in &matrix {
+                        ui.label(ex);
+                        for c in cells {
+                            let actual = c
+                                .actual
+                                .map(|w| format!("{:.0}", w * f))
+                                .unwrap_or_else(|| "-".into());
+                            let est = c
+                                .estimated
+                                .map(|w| format!("{:.0}", w * f))
+                                .unwrap_or_else(|| "-".into());
+                            let mut text = RichText::new(format!("{actual} / {est}"));
+                            if c.soft {
+                                text = text.color(Color32::from_rgb(255, 165, 0));
+                            }
+                            if c.stale {
+                                text = text.weak().italics();
+                            }
+                            let resp = ui.label(text);
+                            if let Some(d) = c.date {
+                                resp.on_hover_text(format!("Set on {}", d.format("%Y-%m-%d")));
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+        });
+    }
+
+    /// Describe a record event, e.g. `Bench: Weight 225.0 lbs (was 215.0)`.
+    fn format_pr(&self, ev: &analysis::PrEvent) -> String {
+        let f = self.settings.weight_unit.factor();
+        let unit_label = match self.settings.weight_unit {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lbs => "lbs",
+        };
+        let amount = |v: f32| match ev.kind {
+            PrKind::Reps => format!("{v:.0}"),
+            _ => format!("{:.1}", v * f),
+        };
+        let mut text = match ev.kind {
+            PrKind::Reps => format!(
+                "{}: {} reps at {:.1} {unit_label}",
+                ev.exercise,
+                amount(ev.value),
+                ev.weight.unwrap_or_default() * f
+            ),
+            _ => format!(
+                "{}: {} {} {unit_label}",
+                ev.exercise,
+                ev.kind.label(),
+                amount(ev.value)
+            ),
+        };
+        if let (Some(w), Some(r), false) = (ev.weight, ev.reps, ev.kind == PrKind::Reps) {
+            text.push_str(&format!(" ({:.1} × {r})", w * f));
+        }
+        match ev.previous {
+            Some(p) => text.push_str(&format!(", was {}", amount(p))),
+            None => text.push_str(", first time"),
+        }
+        text
+    }
+
+    /// "PRs this week" feed and timeline of dated record events, shown above
+    /// the best-value table in the Personal Records window.
+    fn draw_pr_history(&mut self, ui: &mut egui::Ui, entries: &[WorkoutEntry]) {
+        let events = analysis::pr_events(
+            entries,
+            self.formulas(),
+            self.settings.start_date,
+            self.settings.end_date,
+        );
+        let today = Local::now().date_naive();
+        let monday = today - chrono::Duration::days(today.weekday().num_days_from_monday() as i64);
+        egui::CollapsingHeader::new("PRs This Week")
+            .default_open(true)
+            .show(ui, |ui| {
+                let this_week: Vec<&analysis::PrEvent> = events
+                    .iter()
+                    .filter(|ev| ev.is_improvement() && ev.date >= monday && ev.date <= today)
+                    .collect();
+                if this_week.is_empty() {
+                    ui.label("No records this week yet.");
+                }
+                for ev in this_week.into_iter().rev() {
+                    ui.label(format!("{}  {}", ev.date.format("%a"), self.format_pr(ev)));
+                }
+            });
+        egui::CollapsingHeader::new("Timeline")
+            .default_open(false)
+            .show(ui, |ui| {
+                Plot::new("pr_timeline")
+                    .height(120.0)
+                    .x_axis_formatter(|mark, _, _| {
+                        NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32)
+                            .map(|d| d.format("%Y-%m-%d").to_string())
+                            .unwrap_or_default()
+                    })
+                    .y_axis_label("PRs per week")
+                    .show(ui, |plot_ui| {
+                        plot_ui.bar_chart(plotting::pr_timeline_bar(&events));
+                    });
+                ui.horizontal(|ui| {
+                    ui.label("Kind:");
+                    egui::ComboBox::from_id_source("pr_kind_filter")
+                        .selected_text(self.pr_kind_filter.map_or("All", |k| k.label()))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut self.pr_kind_filter, None, "All");
+                            for k in [
+                                PrKind::Weight,
+                                PrKind::Reps,
+                                PrKind::EstOneRm,
+                                PrKind::SetVolume,
+                                PrKind::SessionVolume,
+                            ] {
+                                ui.selectable_value(&mut self.pr_kind_filter, Some(k), k.label());
+                            }
+                        });
+                    ui.checkbox(&mut self.pr_show_baselines, "Include first sessions");
+                });
+                egui::ScrollArea::vertical()
+                    .id_source("pr_timeline_list")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        for ev in events.iter().rev().filter(|ev| {
+                            (self.pr_show_baselines || ev.is_improvement())
+                                && self.pr_kind_filter.is_none_or(|k| k == ev.kind)
+                        }) {
+                            ui.label(format!(
+                                "{}  {}",
+                                ev.date.format("%Y-%m-%d"),
+                                self.format_pr(ev)
+                            ));
+                        }
+                    });
+            });
+        ui.separator();
+    }
+
+    /// Contribution-style calendar of training days with weekly streaks and
+    /// adherence to the session target. Clicking a day lists its sets in the
+    /// entry details window.
+    fn draw_calendar(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Color by:");
+            egui::ComboBox::from_id_source("calendar_metric")
+                .selected_text(format!("{:?}", self.settings.calendar_metric))
+                .show_ui(ui, |ui| {
+                    for m in [
+                        CalendarMetric::Volume,
+                        CalendarMetric::Sets,
+                        CalendarMetric::Sessions,
+                    ] {
+                        if ui
+                            .selectable_value(
+                                &mut self.settings.calendar_metric,
+                                m,
+                                format!("{m:?}"),
+                            )
+                            .changed()
+                        {
+                            self.settings_dirty = true;
+                        }
+                    }
+                });
+            ui.label("Target sessions/week:");
+            let mut t = self.settings.weekly_session_target.to_string();
+            if ui
+                .add(egui::TextEdit::singleline(&mut t).desired_width(30.0))
+                .changed()
+                && let Ok(v) = t.parse::<u32>()
+            {
+                self.settings.weekly_session_target = v.max(1);
+                self.settings_dirty = true;
+            }
+        });
+
+        let entries = self.filtered_entries();
+        let today = Local::now().date_naive();
+        let c = analysis::consistency(
+            &entries,
+            self.settings.start_date,
+            self.settings.end_date,
+            self.settings.weekly_session_target,
+            today,
+        );
+        ui.horizontal(|ui| {
+            ui.label(format!("Current streak: {} weeks", c.current_streak));
+            ui.separator();
+            ui.label(format!("Longest streak: {} weeks", c.longest_streak));
+            ui.separator();
+            ui.label(format!(
+                "On target: {}/{} weeks ({:.0}%)",
+                c.weeks_on_target,
+                c.weeks,
+                c.adherence() * 100.0
+            ));
+            ui.separator();
+            ui.label(format!("{:.1} sessions/week", c.sessions_per_week()));
+        });
+
+        let last = self.settings.end_date.unwrap_or(today);
+        let first = self
+            .settings
+            .start_date
+            .filter(|s| *s <= last)
+            .unwrap_or(last - chrono::Duration::days(364));
+        let metric = self.settings.calendar_metric;
+        let days = analysis::daily_totals(&entries, Some(first), Some(last), metric);
+        let f = self.settings.weight_unit.factor() as f64;
+        let unit_label = match self.settings.weight_unit {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lbs => "lbs",
+        };
+        let clicked = egui::ScrollArea::horizontal()
+            .show(ui, |ui| {
+                draw_calendar_heatmap(ui, &days, first, last, |v| match metric {
+                    CalendarMetric::Volume => format!("{:.0} {unit_label}", v * f),
+                    CalendarMetric::Sets => format!("{v:.0} sets"),
+                    CalendarMetric::Sessions => format!("{v:.0} sessions"),
+                })
+            })
+            .inner;
+        if let Some(d) = clicked {
+            let day = d.format("%Y-%m-%d").to_string();
+            let day_entries: Vec<WorkoutEntry> =
+                entries.into_iter().filter(|e| e.date == day).collect();
+            if !day_entries.is_empty() {
+                self.point_entries = day_entries;
+                self.show_point_details = true;
+            }
+        }
+    }
+
+    /// Histograms and trends of session duration, density and pace, plus
+    /// averages by time of day and weekday.
+    fn draw_session_analytics(&self, ui: &mut egui::Ui) {
+        let workouts = self.filtered_sessions();
+        if !workouts.iter().any(|w| w.duration().is_some()) {
+            ui.label("No sessions with a start and end time.");
+            return;
+        }
+        let (start, end) = (self.settings.start_date, self.settings.end_date);
+        let unit = self.settings.weight_unit;
+        let unit_label = match unit {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lbs => "lbs",
+        };
+        let width = if self.settings.full_width_plots {
+            ui.available_width()
+        } else {
+            self.settings.plot_width
+        };
+        let x_axis = self.settings.x_axis;
+        let ma = self
+            .settings
+            .show_smoothed
+            .then_some(self.settings.ma_window);
+
+        egui::CollapsingHeader::new("Time of Day")
+            .default_open(true)
+            .show(ui, |ui| {
+                let by_time =
+                    analysis::performance_by_time_of_day(&workouts, start, end, self.formulas());
+                let rows: Vec<(String, &analysis::SessionGroupStats)> =
+                    by_time.iter().map(|(t, s)| (format!("{t:?}"), s)).collect();
+                self.session_group_grid(ui, "time_of_day_grid", &rows);
+                ui.label(
+                    RichText::new(
+                        "Strength is each set's estimated 1RM relative to that exercise's average; \
+                         1.00 is a typical session.",
+                    )
+                    .small(),
+                );
+            });
+        egui::CollapsingHeader::new("Weekday")
+            .default_open(true)
+            .show(ui, |ui| {
+                let by_day =
+                    analysis::performance_by_weekday(&workouts, start, end, self.formulas());
+                let rows: Vec<(String, &analysis::SessionGroupStats)> = by_day
+                    .iter()
+                    .map(|(d, s)| {
+                        let name = chrono::Weekday::try_from(*d as u8)
+                            .map(|w| format!("{w:?}"))
+                            .unwrap_or_default();
+                        (name, s)
+                    })
+                    .collect();
+                self.session_group_grid(ui, "weekday_grid", &rows);
+            });
+
+        ui.heading("Session Duration");
+        Plot::new("session_duration_hist")
+            .width(width)
+            .height(self.settings.plot_height)
+            .x_axis_label("Minutes")
+            .y_axis_label("Sessions")
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(session_histogram(
+                    &workouts,
+                    HistogramMetric::Duration {
+                        bin: self.settings.duration_bin_size as f64,
+                    },
+                    start,
+                    end,
+                    unit,
+                ));
+            });
+        ui.heading("Session Density");
+        Plot::new("session_density_hist")
+            .width(width)
+            .height(self.settings.plot_height)
+            .x_axis_label(format!("Volume per minute ({unit_label})"))
+            .y_axis_label("Sessions")
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(session_histogram(
+                    &workouts,
+                    HistogramMetric::Density {
+                        bin: self.settings.density_bin_size as f64,
+                    },
+                    start,
+                    end,
+                    unit,
+                ));
+            });
+
+        let x_label = match x_axis {
+            XAxis::Date => "Date",
+            XAxis::WorkoutIndex => "Workout",
+        };
+        for (metric, id, title, y_label) in [
+            (
+                SessionMetric::Duration,
+                "session_duration_trend",
+                "Duration Over Time",
+                "Minutes".to_string(),
+            ),
+            (
+                SessionMetric::Density,
+                "session_density_trend",
+                "Density Over Time",
+                format!("Volume per minute ({unit_label})"),
+            ),
+            (
+                SessionMetric::SetsPerHour,
+                "session_pace_trend",
+                "Sets per Hour Over Time",
+                "Sets per hour".to_string(),
+            ),
+        ] {
+            ui.heading(title);
+            Plot::new(id)
+                .width(width)
+                .height(self.settings.plot_height)
+                .x_axis_formatter(move |mark, _chars, _| {
+                    if x_axis == XAxis::Date {
+                        NaiveDate::from_num_days_from_ce_opt(mark.value.round() as i32)
+                            .map(|d| d.format("%Y-%m-%d").to_string())
+                            .unwrap_or_else(|| format!("{:.0}", mark.value))
+                    } else {
+                        format!("{:.0}", mark.value)
+                    }
+                })
+                .x_axis_label(x_label)
+                .y_axis_label(y_label)
+                .legend(Legend::default())
+                .show(ui, |plot_ui| {
+                    for l in session_metric_line(&workouts, metric, start, end, x_axis, unit, ma) {
+                        plot_ui.line(l);
+                    }
+                });
+        }
+    }
+
+    fn session_group_grid(
+        &self,
+        ui: &mut egui::Ui,
+        id: &str,
+        rows: &[(String, &analysis::SessionGroupStats)],
+    ) {
+        let f = self.settings.weight_unit.factor() as f64;
+        let fmt = |v: Option<f64>, digits: usize| {
+            v.map(|v| format!("{v:.digits$}"))
+                .unwrap_or_else(|| "-".into())
+        };
+        egui::Grid::new(id).striped(true).show(ui, |ui| {
+            ui.label("");
+            ui.label("Sessions");
+            ui.label("Avg min");
+            ui.label("Density");
+            ui.label("Sets/h");
+            ui.label("Strength");
+            ui.end_row();
+            for (name, s) in rows {
+                ui.label(name);
+                ui.label(s.sessions.to_string());
+                ui.label(fmt(s.avg_minutes, 0));
+                ui.label(fmt(s.avg_density.map(|d| d * f), 1));
+                ui.label(fmt(s.avg_sets_per_hour, 1));
+                ui.label(fmt(s.relative_strength, 2));
+                ui.end_row();
+            }
+        });
+    }
+
+    /// Session list with the selected workout's exercises and sets laid out
+    /// like Hevy's workout view.
+    fn draw_sessions(&mut self, ui: &mut egui::Ui) {
+        let f = self.settings.weight_unit.factor();
+        let unit_label = match self.settings.weight_unit {
+            WeightUnit::Kg => "kg",
+            WeightUnit::Lbs => "lbs",
+        };
+        ui.horizontal(|ui| {
+            ui.label("Filter:");
+            ui.text_edit_singleline(&mut self.session_filter);
+        });
+        let query = self.session_filter.to_lowercase();
+        ui.horizontal_top(|ui| {
+            egui::ScrollArea::vertical()
+                .id_source("session_list")
+                .max_width(220.0)
+                .show(ui, |ui| {
+                    for w in &self.sessions {
+                        if !query.is_empty()
+                            && !w.title.to_lowercase().contains(&query)
+                            && !w
+                                .exercises
+                                .iter()
+                                .any(|x| x.name.to_lowercase().contains(&query))
+                        {
+                            continue;
+                        }
+                        let selected = self.selected_session.as_deref() == Some(w.id.as_str());
+                        let label = format!("{}  {}\n{} sets", w.date, w.title, w.set_count());
+                        if ui.selectable_label(selected, label).clicked() {
+                            self.selected_session = Some(w.id.clone());
+                        }
+                    }
+                });
+            ui.separator();
+            let Some(w) = self
+                .selected_session
+                .as_ref()
+                .and_then(|id| self.sessions.iter().find(|w| &w.id == id))
+            else {
+                ui.label("Select a session.");
+                return;
+            };
+            egui::ScrollArea::vertical()
+                .id_source("session_detail")
+                .show(ui, |ui| {
+                    ui.heading(&w.title);
+                    let when = w
+                        .start
+                        .map(|s| s.format("%A, %d %b %Y, %H:%M").to_string())
+                        .unwrap_or_else(|| w.date.clone());
+                    ui.label(when);
+                    ui.horizontal(|ui| {
+                        if let Some(d) = w.duration() {
+                            ui.label(format!(
+                                "Duration: {}",
+                                format_duration(d.num_seconds() as f32)
+                            ));
+                        }
+                        ui.label(format!("Volume: {:.0} {unit_label}", w.volume() * f));
+                        ui.label(format!("Sets: {}", w.set_count()));
+                    });
+                    if let Some(desc) = &w.description {
+                        ui.label(RichText::new(desc).italics());
+                    }
+                    let mut prev_superset: Option<&str> = None;
+                    for (i, x) in w.exercises.iter().enumerate() {
+                        ui.separator();
+                        if let Some(id) = x.superset_id.as_deref()
+                            && prev_superset != Some(id)
+                        {
+                            ui.label(
+                                RichText::new(format!("Superset {id}"))
+                                    .color(Color32::LIGHT_BLUE)
+                                    .small(),
+                            );
+                        }
+                        prev_superset = x.superset_id.as_deref();
+                        ui.label(RichText::new(&x.name).strong());
+                        if let Some(notes) = &x.notes {
+                            ui.label(RichText::new(notes).weak());
+                        }
+                        egui::Grid::new(("session_sets", &w.id, i))
                             .striped(true)
                             .show(ui, |ui| {
-                                ui.label("Year");
-                                ui.label("Week");
-                                ui.label("Sets");
-                                ui.label("Volume");
-                                ui.label("ACWR");
+                                ui.label("Set");
+                                ui.label("Performed");
+                                ui.label("RPE");
                                 ui.end_row();
-                                for w in &weeks {
-                                    ui.label(w.year.to_string());
-                                    ui.label(format!("{:02}", w.week));
-                                    ui.label(w.total_sets.to_string());
-                                    ui.label(format!("{:.1}", w.total_volume * f));
-                                    if let Some(r) = w.acwr {
-                                        if w.over_threshold {
-                                            ui.label(
-                                                RichText::new(format!("{r:.2} ⚠"))
-                                                    .color(Color32::RED),
-                                            );
-                                        } else {
-                                            ui.label(format!("{r:.2}"));
+                                let mut number = 0;
+                                for set in &x.sets {
+                                    let kind = set.raw.set_type.as_deref().unwrap_or("normal");
+                                    let label = match kind.to_lowercase().as_str() {
+                                        "warmup" => "W".to_string(),
+                                        "dropset" => "D".to_string(),
+                                        "failure" => "F".to_string(),
+                                        _ => {
+                                            number += 1;
+                                            number.to_string()
                                         }
-                                    } else {
-                                        ui.label("-");
+                                    };
+                                    ui.label(label);
+                                    let mut parts = Vec::new();
+                                    match (set.weight, set.reps) {
+                                        (Some(wt), Some(r)) => {
+                                            parts.push(format!("{:.1} {unit_label} × {r}", wt * f))
+                                        }
+                                        (None, Some(r)) => parts.push(format!("{r} reps")),
+                                        (Some(wt), None) => {
+                                            parts.push(format!("{:.1} {unit_label}", wt * f))
+                                        }
+                                        (None, None) => {}
+                                    }
+                                    if let Some(d) = set.distance() {
+                                        parts.push(format!("{d:.2} mi"));
+                                    }
+                                    if let Some(secs) = set.duration() {
+                                        parts.push(format_duration(secs));
                                     }
+                                    ui.label(parts.join(" · "));
+                                    ui.label(
+                                        set.raw
+                                            .rpe
+                                            .map(|r| format!("{r}"))
+                                            .unwrap_or_else(|| "-".into()),
+                                    );
                                     ui.end_row();
                                 }
                             });
-                    };
-                    if !self.show_weekly_summary_table_window {
-                        egui::ScrollArea::vertical()
-                            .max_height(table_height)
-                            .show(ui, |ui| {
-                                render_table(ui);
-                            });
                     }
-                    let plot_height = (size.y
-                        - if self.show_weekly_summary_table_window {
-                            0.0
-                        } else {
-                            table_height
-                        })
-                    .max(50.0);
-                    let resp = Plot::new("weekly_summary_plot")
-                        .width(size.x)
-                        .height(plot_height)
-                        .x_axis_formatter(move |mark, _chars, _| {
-                            let idx = mark.value.round() as usize;
-                            weeks_for_axis
-                                .get(idx)
-                                .map(|w| format!("{}-{:02}", w.year, w.week))
-                                .unwrap_or_else(|| format!("{:.0}", mark.value))
-                        })
-                        .x_axis_label("Week")
-                        .y_axis_label(format!("Volume ({unit_label}) / Sets"))
-                        .legend(Legend::default())
-                        .show(ui, |plot_ui| {
-                            plot_ui.bar_chart(bars);
-                            plot_ui.line(line);
-                        });
+                });
+        });
+    }
 
-                    first_resp.get_or_insert(resp);
-                    if self.show_weekly_summary_table_window {
-                        let ctx = ui.ctx();
-                        let mut open = self.show_weekly_summary_table_window;
-                        egui::Window::new("Weekly Summary Table")
-                            .open(&mut open)
-                            .resizable(true)
-                            .vscroll(true)
-                            .show(ctx, |ui| {
-                                render_table(ui);
-                            });
-                        self.show_weekly_summary_table_window = open;
-                        if self.settings.show_weekly_summary_table_window
-                            != self.show_weekly_summary_table_window
-                        {
-                            self.settings.show_weekly_summary_table_window =
-                                self.show_weekly_summary_table_window;
-                            self.settings_dirty = true;
+    /// Table and chart of one week's hard sets per muscle, flagged against
+    /// each muscle's volume landmarks.
+    fn draw_hard_sets(&mut self, ui: &mut egui::Ui) {
+        use chrono::Datelike;
+
+        let weeks = analysis::weekly_hard_sets(
+            &self.filtered_entries(),
+            None,
+            None,
+            self.settings.hard_set_min_rpe,
+            self.settings.muscle_credit(),
+        );
+        let today = Local::now().date_naive().iso_week();
+        let current = (today.year(), today.week());
+        let selected = self.hard_sets_week.unwrap_or(current);
+        let week_label = |(y, w): (i32, u32)| {
+            if (y, w) == current {
+                format!("{y}-W{w:02} (current)")
+            } else {
+                format!("{y}-W{w:02}")
+            }
+        };
+        ui.horizontal(|ui| {
+            ui.label("Week:");
+            egui::ComboBox::from_id_source("hard_sets_week")
+                .selected_text(week_label(selected))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.hard_sets_week, None, week_label(current));
+                    for w in weeks.iter().rev() {
+                        let key = (w.year, w.week);
+                        if key != current {
+                            ui.selectable_value(
+                                &mut self.hard_sets_week,
+                                Some(key),
+                                week_label(key),
+                            );
                         }
                     }
-                }
-            });
+                });
+            ui.label("Min RPE:");
+            let mut min = self
+                .settings
+                .hard_set_min_rpe
+                .map(|v| format!("{:.1}", v))
+                .unwrap_or_default();
+            if ui
+                .add(egui::TextEdit::singleline(&mut min).desired_width(40.0))
+                .changed()
+            {
+                self.settings.hard_set_min_rpe = min.trim().parse().ok();
+                self.settings_dirty = true;
+            }
         });
 
-        first_resp.unwrap_or_else(|| {
-            ui.heading("No Data");
-            Plot::new("empty_plot")
-                .width(size.x)
-                .height(size.y)
-                .x_axis_label("")
-                .y_axis_label("")
-                .show(ui, |_ui| {})
-        })
+        let week_sets = weeks
+            .into_iter()
+            .find(|w| (w.year, w.week) == selected)
+            .map(|w| w.sets)
+            .unwrap_or_default();
+        let mut muscles: BTreeSet<String> =
+            body_parts::primary_muscle_groups().into_iter().collect();
+        muscles.extend(week_sets.keys().cloned());
+        muscles.extend(self.settings.volume_landmarks.keys().cloned());
+        let rows: Vec<(String, f64, VolumeLandmarks)> = muscles
+            .into_iter()
+            .map(|m| {
+                let sets = week_sets.get(&m).copied().unwrap_or(0.0);
+                let landmarks = self
+                    .settings
+                    .volume_landmarks
+                    .get(&m)
+                    .copied()
+                    .unwrap_or_default();
+                (m, sets, landmarks)
+            })
+            .collect();
+
+        egui::ScrollArea::vertical()
+            .max_height(250.0)
+            .show(ui, |ui| {
+                egui::Grid::new("hard_sets_table")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("Muscle");
+                        ui.label("Sets");
+                        ui.label("MEV");
+                        ui.label("MAV");
+                        ui.label("MRV");
+                        ui.label("Status");
+                        ui.end_row();
+                        for (muscle, sets, landmarks) in &rows {
+                            let status = landmarks.status(*sets);
+                            ui.label(muscle);
+                            ui.label(format!("{sets:.1}"));
+                            let mut edited = *landmarks;
+                            for value in [&mut edited.mev, &mut edited.mav, &mut edited.mrv] {
+                                let mut text = format!("{value}");
+                                if ui
+                                    .add(egui::TextEdit::singleline(&mut text).desired_width(40.0))
+                                    .changed()
+                                    && let Ok(v) = text.trim().parse::<f32>()
+                                {
+                                    *value = v.max(0.0);
+                                }
+                            }
+                            if edited != *landmarks {
+                                self.settings
+                                    .volume_landmarks
+                                    .insert(muscle.clone(), edited);
+                                self.settings_dirty = true;
+                            }
+                            ui.label(RichText::new(status.label()).color(dose_color(status)));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        let names: Vec<String> = rows.iter().map(|(m, _, _)| m.clone()).collect();
+        let (bars, mev, mrv) = hard_sets_chart(&rows);
+        Plot::new("hard_sets_plot")
+            .height(200.0)
+            .x_axis_formatter(move |mark, _, _| {
+                names.get(mark.value as usize).cloned().unwrap_or_default()
+            })
+            .x_axis_label("Muscle")
+            .y_axis_label("Hard sets")
+            .legend(Legend::default())
+            .show(ui, |plot_ui| {
+                plot_ui.bar_chart(bars);
+                plot_ui.points(mev);
+                plot_ui.points(mrv);
+            });
     }
 
     fn draw_overall_analysis(
@@ -2236,9 +4189,19 @@ impl MyApp {
                 }
             });
 
-        let (bars, body_parts) =
-            body_part_distribution(entries, self.settings.start_date, self.settings.end_date);
-        let pie = body_part_pie(entries, self.settings.start_date, self.settings.end_date);
+        let credit = self.settings.muscle_credit();
+        let (bars, body_parts) = body_part_distribution(
+            entries,
+            self.settings.start_date,
+            self.settings.end_date,
+            credit,
+        );
+        let pie = body_part_pie(
+            entries,
+            self.settings.start_date,
+            self.settings.end_date,
+            credit,
+        );
         let bp_for_axis = body_parts.clone();
         ui.heading("Sets per Body Part");
         Plot::new("overall_sets_per_body_part")
@@ -2351,8 +4314,11 @@ impl App for MyApp {
                     LoadMessage::Progress(p) => {
                         self.loading_progress = p;
                     }
-                    LoadMessage::Done(entries) => {
-                        self.workouts = entries;
+                    LoadMessage::Done(entries, report) => {
+                        self.show_import_report = !report.skipped.is_empty();
+                        self.import_report = Some(report);
+                        store::append(&entries);
+                        store::merge_into(&mut self.workouts, entries);
                         self.stats = compute_stats(
                             &self.workouts,
                             self.settings.start_date,
@@ -2370,6 +4336,27 @@ impl App for MyApp {
                         self.toast_start = Some(Instant::now());
                         self.loading = false;
                     }
+                    LoadMessage::Merged(entries, report) => {
+                        self.workouts = entries;
+                        store::replace_all(&self.workouts);
+                        self.stats = compute_stats(
+                            &self.workouts,
+                            self.settings.start_date,
+                            self.settings.end_date,
+                        );
+                        self.update_filter_values();
+                        self.last_loaded = Some("merged CSVs".to_string());
+                        info!(
+                            "Merged {} files into {} sets with {} conflicts",
+                            report.sources,
+                            report.total_sets,
+                            report.conflicts.len()
+                        );
+                        self.merge_report = Some(report);
+                        self.show_merge_report = true;
+                        self.toast_start = Some(Instant::now());
+                        self.loading = false;
+                    }
                     LoadMessage::Error(e) => {
                         log::error!("Failed to load CSV: {e}");
                         self.loading = false;
@@ -2383,14 +4370,86 @@ impl App for MyApp {
             }
         }
 
-        if self.loading {
+        if self.loading || self.show_import_report {
             egui::Window::new("Loading CSV")
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.label("Parsing CSV...");
-                    ui.add(egui::ProgressBar::new(self.loading_progress).show_percentage());
+                    if self.loading {
+                        ui.label("Parsing CSV...");
+                        ui.add(egui::ProgressBar::new(self.loading_progress).show_percentage());
+                        return;
+                    }
+                    let Some(report) = &self.import_report else {
+                        return;
+                    };
+                    ui.label(format!(
+                        "Imported {} of {} rows",
+                        report.imported, report.total_rows
+                    ));
+                    if let Some(format) = report.date_format {
+                        ui.label(format!("Date format: {}", format.label()));
+                    }
+                    for (reason, count) in report.counts() {
+                        egui::CollapsingHeader::new(format!("{}: {count}", reason.label()))
+                            .id_source(reason)
+                            .show(ui, |ui| {
+                                for row in report.samples(reason, 5) {
+                                    ui.label(format!("Line {}: {}", row.line, row.value));
+                                }
+                            });
+                    }
+                    ui.horizontal(|ui| {
+                        if ui.button("Export Report").clicked()
+                            && let Some(path) = FileDialog::new()
+                                .add_filter("CSV", &["csv"])
+                                .add_filter("JSON", &["json"])
+                                .save_file()
+                        {
+                            match path
+                                .extension()
+                                .and_then(|e| e.to_str())
+                                .map(|s| s.to_lowercase())
+                            {
+                                Some(ext) if ext == "csv" => {
+                                    if let Err(e) = save_import_report_csv(&path, report) {
+                                        log::error!("Failed to export import report: {e}");
+                                    }
+                                }
+                                _ => {
+                                    if let Err(e) = save_import_report_json(&path, report) {
+                                        log::error!("Failed to export import report: {e}");
+                                    }
+                                }
+                            }
+                        }
+                        if ui.button("Close").clicked() {
+                            self.show_import_report = false;
+                        }
+                    });
+                });
+        }
+
+        if self.poll_sync() {
+            ctx.request_repaint();
+        }
+
+        if self.syncing {
+            egui::Window::new("Syncing with Hevy")
+                .collapsible(false)
+                .resizable(false)
+                .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                .show(ctx, |ui| {
+                    ui.label(self.sync_task);
+                    ui.add(egui::ProgressBar::new(self.sync_progress).show_percentage());
+                    let cancelling = self.sync_cancel.load(Ordering::Relaxed);
+                    if ui
+                        .add_enabled(!cancelling, egui::Button::new("Cancel"))
+                        .clicked()
+                    {
+                        self.sync_cancel.store(true, Ordering::Relaxed);
+                    }
                 });
         }
 
@@ -2458,6 +4517,56 @@ impl App for MyApp {
                             self.settings.show_mapping = self.show_mapping;
                             self.settings_dirty = true;
                         }
+                        if ui
+                            .checkbox(&mut self.show_hard_sets_window, "Weekly Hard Sets")
+                            .changed()
+                        {
+                            self.settings.show_hard_sets_window = self.show_hard_sets_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_sessions_window, "Workout Sessions")
+                            .changed()
+                        {
+                            self.settings.show_sessions_window = self.show_sessions_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_session_analytics_window, "Session Analytics")
+                            .changed()
+                        {
+                            self.settings.show_session_analytics_window =
+                                self.show_session_analytics_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_calendar_window, "Training Calendar")
+                            .changed()
+                        {
+                            self.settings.show_calendar_window = self.show_calendar_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_rep_max_window, "Rep Max Matrix")
+                            .changed()
+                        {
+                            self.settings.show_rep_max_window = self.show_rep_max_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_calibration_window, "1RM Calibration")
+                            .changed()
+                        {
+                            self.settings.show_calibration_window = self.show_calibration_window;
+                            self.settings_dirty = true;
+                        }
+                        if ui
+                            .checkbox(&mut self.show_stalled_window, "Stalled Lifts")
+                            .changed()
+                        {
+                            self.settings.show_stalled_window = self.show_stalled_window;
+                            self.settings_dirty = true;
+                        }
                     });
                     if ui.button("Usage Tips").clicked() {
                         self.show_about = true;
@@ -2465,6 +4574,8 @@ impl App for MyApp {
                     }
                     if ui.button("Reload Mappings").clicked() {
                         exercise_mapping::load();
+                        exercise_catalog::load();
+                        rpe_table::load();
                         self.stats = compute_stats(
                             &self.workouts,
                             self.settings.start_date,
@@ -2481,7 +4592,7 @@ impl App for MyApp {
                             {
                                 let mut exercises = analysis::aggregate_exercise_stats(
                                     &self.workouts,
-                                    self.settings.one_rm_formula,
+                                    self.formulas(),
                                     self.settings.start_date,
                                     self.settings.end_date,
                                 )
@@ -2546,7 +4657,7 @@ impl App for MyApp {
                             {
                                 let prs_map = analysis::personal_records(
                                     &self.workouts,
-                                    self.settings.one_rm_formula,
+                                    self.formulas(),
                                     self.settings.start_date,
                                     self.settings.end_date,
                                 );
@@ -2583,7 +4694,7 @@ impl App for MyApp {
                                 let entries = self.filtered_entries();
                                 let mut recs: Vec<_> = analysis::personal_records(
                                     &entries,
-                                    self.settings.one_rm_formula,
+                                    self.formulas(),
                                     self.settings.start_date,
                                     self.settings.end_date,
                                 )
@@ -2638,7 +4749,16 @@ impl App for MyApp {
                     }
                 }
 
-                if ui.button("Sync").clicked() {
+                if ui.button("Import and Merge").clicked()
+                    && let Some(paths) = FileDialog::new().add_filter("CSV", &["csv"]).pick_files()
+                {
+                    self.start_merging(paths);
+                }
+
+                if ui
+                    .add_enabled(!self.syncing, egui::Button::new("Sync"))
+                    .clicked()
+                {
                     self.sync_from_hevy();
                 }
 
@@ -2671,6 +4791,12 @@ impl App for MyApp {
                             body_parts::body_part_for(ex).unwrap_or_else(|| "Other".to_string());
                         by_body_part.entry(part).or_default().push(ex.clone());
                     }
+                    let mut by_category: BTreeMap<String, Vec<String>> = BTreeMap::new();
+                    for (ex, _dist) in &exercises {
+                        for cat in body_parts::categories_for(ex) {
+                            by_category.entry(cat).or_default().push(ex.clone());
+                        }
+                    }
                     let top_matches: HashSet<String> =
                         exercises.iter().take(3).map(|(e, _)| e.clone()).collect();
 
@@ -2686,78 +4812,34 @@ impl App for MyApp {
                             self.update_selected_stats();
                             ui.close_menu();
                         }
-                        if ui.button("None").clicked() {
-                            self.selected_exercises = ExerciseSelection::None;
-                            self.update_selected_stats();
-                            ui.close_menu();
-                        }
-                        ui.separator();
-                        for (part, exs) in &by_body_part {
-                            let group_exs = exs.clone();
-                            let all_selected = self.selected_exercises.is_all()
-                                || group_exs
-                                    .iter()
-                                    .all(|e| self.selected_exercises.contains(e));
-                            let mut label = part.clone();
-                            if all_selected {
-                                label.push_str(" ✓");
-                            }
-                            ui.collapsing(label, |ui| {
-                                ui.horizontal(|ui| {
-                                    if ui.button("Select All").clicked() {
-                                        if self.selected_exercises.is_all()
-                                            || self.selected_exercises.is_none()
-                                        {
-                                            self.selected_exercises =
-                                                ExerciseSelection::Selected(group_exs.clone());
-                                        } else {
-                                            for ex in &group_exs {
-                                                if !self.selected_exercises.contains(ex) {
-                                                    self.selected_exercises.push(ex.clone());
-                                                }
-                                            }
-                                        }
-                                        self.update_selected_stats();
-                                    }
-                                    if ui.button("Deselect All").clicked() {
-                                        if self.selected_exercises.is_all() {
-                                            let mut all: Vec<String> = unique_exercises(
-                                                &self.workouts,
-                                                self.settings.start_date,
-                                                self.settings.end_date,
-                                            );
-                                            all.retain(|e| !group_exs.contains(e));
-                                            self.selected_exercises =
-                                                ExerciseSelection::Selected(all);
-                                        } else if self.selected_exercises.is_none() {
-                                            // nothing to do
-                                        } else {
-                                            self.selected_exercises
-                                                .retain(|e| !group_exs.contains(e));
-                                        }
-                                        self.update_selected_stats();
-                                    }
-                                });
-                                for ex in &group_exs {
-                                    let mut sel = self.selected_exercises.contains(ex);
-                                    let label = if top_matches.contains(ex)
-                                        && !self.search_query.is_empty()
-                                    {
-                                        RichText::new(ex).color(egui::Color32::LIGHT_GREEN)
-                                    } else {
-                                        RichText::new(ex)
-                                    };
-                                    if ui.add(egui::Checkbox::new(&mut sel, label)).changed() {
-                                        if sel {
-                                            self.selected_exercises.push(ex.clone());
-                                        } else {
-                                            self.selected_exercises.retain(|e| e != ex);
-                                        }
-                                        self.update_selected_stats();
-                                    }
-                                }
-                            });
-                        }
+                        if ui.button("None").clicked() {
+                            self.selected_exercises = ExerciseSelection::None;
+                            self.update_selected_stats();
+                            ui.close_menu();
+                        }
+                        ui.separator();
+                        for (part, exs) in &by_body_part {
+                            self.exercise_group_menu(
+                                ui,
+                                ("body_part", part),
+                                part,
+                                exs,
+                                &top_matches,
+                            );
+                        }
+                        if !by_category.is_empty() {
+                            ui.separator();
+                            ui.label("Categories");
+                            for (cat, exs) in &by_category {
+                                self.exercise_group_menu(
+                                    ui,
+                                    ("category", cat),
+                                    cat,
+                                    exs,
+                                    &top_matches,
+                                );
+                            }
+                        }
                     });
                     let _ = ctx.input(|i| i.pointer.interact_pos());
                     resp.response
@@ -2873,7 +4955,7 @@ impl App for MyApp {
                             let filtered = self.filtered_entries();
                             let mut stats = analysis::aggregate_exercise_stats(
                                 &filtered,
-                                self.settings.one_rm_formula,
+                                self.formulas(),
                                 self.settings.start_date,
                                 self.settings.end_date,
                             )
@@ -3252,6 +5334,8 @@ impl App for MyApp {
                         .column(egui_extras::Column::auto())
                         .column(egui_extras::Column::auto())
                         .column(egui_extras::Column::auto())
+                        .column(egui_extras::Column::auto())
+                        .column(egui_extras::Column::auto())
                         .header(row_height, |mut header| {
                             header.col(|ui| {
                                 MyApp::sort_button(
@@ -3289,6 +5373,12 @@ impl App for MyApp {
                                     &mut sort_ascending,
                                 )
                             });
+                            header.col(|ui| {
+                                ui.label("Duration");
+                            });
+                            header.col(|ui| {
+                                ui.label("Distance");
+                            });
                         })
                         .body(|mut body| {
                             for e in entries {
@@ -3301,10 +5391,32 @@ impl App for MyApp {
                                     });
                                     row.col(|ui| {
                                         let f = self.settings.weight_unit.factor();
-                                        ui.label(format!("{:.1}", e.weight.unwrap() * f));
+                                        ui.label(
+                                            e.weight
+                                                .map(|w| format!("{:.1}", w * f))
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(
+                                            e.reps
+                                                .map(|r| r.to_string())
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(
+                                            e.duration()
+                                                .map(format_duration)
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
                                     });
                                     row.col(|ui| {
-                                        ui.label(e.reps.unwrap().to_string());
+                                        ui.label(
+                                            e.distance()
+                                                .map(|d| format!("{d:.2} mi"))
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
                                     });
                                 });
                             }
@@ -3333,6 +5445,8 @@ impl App for MyApp {
                         .column(egui_extras::Column::auto())
                         .column(egui_extras::Column::auto())
                         .column(egui_extras::Column::auto())
+                        .column(egui_extras::Column::auto())
+                        .column(egui_extras::Column::auto())
                         .header(row_height, |mut header| {
                             header.col(|ui| {
                                 ui.label("Date");
@@ -3346,6 +5460,12 @@ impl App for MyApp {
                             header.col(|ui| {
                                 ui.label("Reps");
                             });
+                            header.col(|ui| {
+                                ui.label("Duration");
+                            });
+                            header.col(|ui| {
+                                ui.label("Distance");
+                            });
                         })
                         .body(|mut body| {
                             for e in entries {
@@ -3358,10 +5478,32 @@ impl App for MyApp {
                                     });
                                     row.col(|ui| {
                                         let f = self.settings.weight_unit.factor();
-                                        ui.label(format!("{:.1}", e.weight.unwrap() * f));
+                                        ui.label(
+                                            e.weight
+                                                .map(|w| format!("{:.1}", w * f))
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(
+                                            e.reps
+                                                .map(|r| r.to_string())
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
+                                    });
+                                    row.col(|ui| {
+                                        ui.label(
+                                            e.duration()
+                                                .map(format_duration)
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
                                     });
                                     row.col(|ui| {
-                                        ui.label(e.reps.unwrap().to_string());
+                                        ui.label(
+                                            e.distance()
+                                                .map(|d| format!("{d:.2} mi"))
+                                                .unwrap_or_else(|| "-".into()),
+                                        );
                                     });
                                 });
                             }
@@ -3370,6 +5512,59 @@ impl App for MyApp {
             self.show_point_details = open;
         }
 
+        if self.show_merge_report {
+            let mut open = self.show_merge_report;
+            if let Some(report) = &self.merge_report {
+                let f = self.settings.weight_unit.factor();
+                let unit = match self.settings.weight_unit {
+                    WeightUnit::Lbs => "lbs",
+                    WeightUnit::Kg => "kg",
+                };
+                let version = |v: &store::SetVersion| {
+                    format!(
+                        "{} {unit} x {} ({})",
+                        v.weight
+                            .map(|w| format!("{:.1}", w * f))
+                            .unwrap_or_else(|| "-".into()),
+                        v.reps.map(|r| r.to_string()).unwrap_or_else(|| "-".into()),
+                        v.source
+                    )
+                };
+                egui::Window::new("Merge Summary")
+                    .open(&mut open)
+                    .vscroll(true)
+                    .show(ctx, |ui| {
+                        ui.label(format!("Sources merged: {}", report.sources));
+                        ui.label(format!("Sets after merge: {}", report.total_sets));
+                        ui.label(format!("Duplicate sets removed: {}", report.duplicates));
+                        ui.label(format!("Conflicts: {}", report.conflicts.len()));
+                        if report.conflicts.is_empty() {
+                            return;
+                        }
+                        ui.separator();
+                        egui::Grid::new("merge_conflicts")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("Workout start");
+                                ui.label("Exercise");
+                                ui.label("Set");
+                                ui.label("Kept");
+                                ui.label("Discarded");
+                                ui.end_row();
+                                for c in &report.conflicts {
+                                    ui.label(&c.start_time);
+                                    ui.label(&c.exercise);
+                                    ui.label(c.set_index.to_string());
+                                    ui.label(version(&c.kept));
+                                    ui.label(version(&c.discarded));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+            self.show_merge_report = open;
+        }
+
         if self.show_exercise_popup {
             let mut open = self.show_exercise_popup;
             egui::Window::new("Exercises")
@@ -3679,13 +5874,13 @@ impl App for MyApp {
                     let entries = self.filtered_selected_entries();
                     let stats_map = analysis::aggregate_exercise_stats(
                         &entries,
-                        self.settings.one_rm_formula,
+                        self.formulas(),
                         self.settings.start_date,
                         self.settings.end_date,
                     );
                     let rec_map = analysis::personal_records(
                         &entries,
-                        self.settings.one_rm_formula,
+                        self.formulas(),
                         self.settings.start_date,
                         self.settings.end_date,
                     );
@@ -3768,9 +5963,10 @@ impl App for MyApp {
                 .resizable(true)
                 .show(ctx, |ui| {
                     let entries = self.filtered_entries();
+                    self.draw_pr_history(ui, &entries);
                     let mut recs: Vec<_> = analysis::personal_records(
                         &entries,
-                        self.settings.one_rm_formula,
+                        self.formulas(),
                         self.settings.start_date,
                         self.settings.end_date,
                     )
@@ -3868,6 +6064,9 @@ impl App for MyApp {
                                 .column(egui_extras::Column::initial(150.0).resizable(true))
                                 .column(egui_extras::Column::initial(100.0).resizable(true))
                                 .column(egui_extras::Column::initial(150.0).resizable(true))
+                                .column(egui_extras::Column::initial(80.0).resizable(true))
+                                .column(egui_extras::Column::initial(80.0).resizable(true))
+                                .column(egui_extras::Column::initial(80.0).resizable(true))
                                 .header(row_height, |mut header| {
                                     header.col(|ui| {
                                         ui.label("");
@@ -3881,10 +6080,20 @@ impl App for MyApp {
                                     header.col(|ui| {
                                         ui.label("Secondary");
                                     });
+                                    header.col(|ui| {
+                                        ui.label("Type");
+                                    });
+                                    header.col(|ui| {
+                                        ui.label("Equipment");
+                                    });
+                                    header.col(|ui| {
+                                        ui.label("Difficulty");
+                                    });
                                 })
                                 .body(|mut body| {
                                     for e in &list {
                                         let mapping = exercise_mapping::get(e).unwrap_or_default();
+                                        let resolved = body_parts::resolve_info(e);
                                         body.row(row_height, |mut row| {
                                             row.col(|ui| {
                                                 let mut sel = self.mapping_exercises.contains(e);
@@ -3905,6 +6114,31 @@ impl App for MyApp {
                                             row.col(|ui| {
                                                 ui.label(mapping.secondary.join(", "));
                                             });
+                                            row.col(|ui| {
+                                                if let Some(r) = resolved
+                                                    && let Some(kind) = r.kind
+                                                {
+                                                    ui.label(inferred_label(
+                                                        kind,
+                                                        r.kind_inferred,
+                                                    ));
+                                                }
+                                            });
+                                            row.col(|ui| {
+                                                if let Some(r) = resolved
+                                                    && let Some(eq) = r.equipment
+                                                {
+                                                    ui.label(inferred_label(
+                                                        eq,
+                                                        r.equipment_inferred,
+                                                    ));
+                                                }
+                                            });
+                                            row.col(|ui| {
+                                                if let Some(d) = resolved.and_then(|r| r.difficulty) {
+                                                    ui.label(format!("{:?}", d));
+                                                }
+                                            });
                                         });
                                     }
                                 });
@@ -3930,7 +6164,10 @@ impl App for MyApp {
                         render_secondary_checkboxes(ui, &muscles, &mut self.mapping_entry);
                         ui.horizontal(|ui| {
                             ui.label("Category:");
-                            ui.text_edit_singleline(&mut self.mapping_entry.category);
+                            ui.text_edit_singleline(&mut self.mapping_entry.category)
+                                .on_hover_text(
+                                    "Movement pattern or exercise family, e.g. \"Horizontal Push, Bench Variants\"",
+                                );
                         });
                         ui.horizontal(|ui| {
                             if ui.button("Save Mapping").clicked() {
@@ -3946,6 +6183,89 @@ impl App for MyApp {
                                 self.mapping_dirty = true;
                             }
                         });
+                        ui.separator();
+                        ui.horizontal(|ui| {
+                            ui.label("Type:");
+                            egui::ComboBox::from_id_source("catalog_kind")
+                                .selected_text(format!("{:?}", self.catalog_entry.kind))
+                                .show_ui(ui, |ui| {
+                                    for k in body_parts::ALL_EXERCISE_TYPES {
+                                        ui.selectable_value(
+                                            &mut self.catalog_entry.kind,
+                                            k,
+                                            format!("{:?}", k),
+                                        );
+                                    }
+                                });
+                            ui.label("Equipment:");
+                            egui::ComboBox::from_id_source("catalog_equipment")
+                                .selected_text(match self.catalog_entry.equipment {
+                                    Some(e) => format!("{:?}", e),
+                                    None => "Unknown".to_string(),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut self.catalog_entry.equipment,
+                                        None,
+                                        "Unknown",
+                                    );
+                                    for e in body_parts::ALL_EQUIPMENT {
+                                        ui.selectable_value(
+                                            &mut self.catalog_entry.equipment,
+                                            Some(e),
+                                            format!("{:?}", e),
+                                        );
+                                    }
+                                });
+                            ui.label("Difficulty:");
+                            egui::ComboBox::from_id_source("catalog_difficulty")
+                                .selected_text(match self.catalog_entry.difficulty {
+                                    Some(d) => format!("{:?}", d),
+                                    None => "Unknown".to_string(),
+                                })
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(
+                                        &mut self.catalog_entry.difficulty,
+                                        None,
+                                        "Unknown",
+                                    );
+                                    for d in body_parts::ALL_DIFFICULTIES {
+                                        ui.selectable_value(
+                                            &mut self.catalog_entry.difficulty,
+                                            Some(d),
+                                            format!("{:?}", d),
+                                        );
+                                    }
+                                });
+                        });
+                        ui.horizontal(|ui| {
+                            if ui.button("Save Info").clicked() {
+                                for ex in &self.mapping_exercises {
+                                    exercise_catalog::set(ex.clone(), self.catalog_entry);
+                                }
+                                self.mapping_dirty = true;
+                            }
+                            if ui.button("Remove Info").clicked() {
+                                for ex in &self.mapping_exercises {
+                                    exercise_catalog::remove(ex);
+                                }
+                                self.mapping_dirty = true;
+                            }
+                            if ui
+                                .button("Confirm Inferred")
+                                .on_hover_text(
+                                    "Store the guessed type and equipment in the catalog",
+                                )
+                                .clicked()
+                            {
+                                for ex in &self.mapping_exercises {
+                                    if let Some(info) = body_parts::info_for(ex) {
+                                        exercise_catalog::set(ex.clone(), info);
+                                    }
+                                }
+                                self.mapping_dirty = true;
+                            }
+                        });
                     }
                     ui.horizontal(|ui| {
                         if ui.button("Export Mapping").clicked() {
@@ -3976,6 +6296,37 @@ impl App for MyApp {
                                 }
                             }
                         }
+                        if ui.button("Export Catalog").clicked()
+                            && let Some(path) =
+                                FileDialog::new().add_filter("JSON", &["json"]).save_file()
+                            && let Err(e) = exercise_catalog::export_all(&path)
+                        {
+                            log::error!("Failed to export catalog: {e}");
+                        }
+                        if ui.button("Import Catalog").clicked()
+                            && let Some(paths) =
+                                FileDialog::new().add_filter("JSON", &["json"]).pick_files()
+                        {
+                            match exercise_catalog::merge_files(&paths) {
+                                Ok(()) => {
+                                    log::info!("Merged {} catalog files", paths.len());
+                                    self.mapping_message = Some("Catalog merged".to_string());
+                                    self.mapping_dirty = true;
+                                }
+                                Err(e) => {
+                                    log::error!("Failed to merge catalog: {e}");
+                                    self.mapping_message =
+                                        Some(format!("Failed to merge catalog: {e}"));
+                                }
+                            }
+                            self.mapping_toast_start = Some(Instant::now());
+                        }
+                        if ui
+                            .add_enabled(!self.syncing, egui::Button::new("Fetch from Hevy"))
+                            .clicked()
+                        {
+                            self.fetch_exercise_templates();
+                        }
                         if ui.button("Export Exercise List").clicked() {
                             let list = unique_exercises(&self.workouts, None, None);
                             if let Some(path) =
@@ -3994,22 +6345,301 @@ impl App for MyApp {
                             }
                         }
                     });
-                    if ui.button("Open Config Directory").clicked() {
-                        if let Some(dir) = dirs::config_dir() {
-                            let _ = open::that(dir);
-                        }
-                    }
+                    ui.collapsing("Aliases", |ui| {
+                        if self.mapping_exercises.len() > 1 {
+                            ui.horizontal(|ui| {
+                                egui::ComboBox::from_id_source("alias_canonical")
+                                    .selected_text(if self.alias_canonical.is_empty() {
+                                        "Canonical name"
+                                    } else {
+                                        &self.alias_canonical
+                                    })
+                                    .show_ui(ui, |ui| {
+                                        for ex in &self.mapping_exercises {
+                                            ui.selectable_value(
+                                                &mut self.alias_canonical,
+                                                ex.clone(),
+                                                ex,
+                                            );
+                                        }
+                                    });
+                                if ui
+                                    .add_enabled(
+                                        self.mapping_exercises.contains(&self.alias_canonical),
+                                        egui::Button::new("Merge Selected"),
+                                    )
+                                    .clicked()
+                                {
+                                    for ex in &self.mapping_exercises {
+                                        if *ex != self.alias_canonical {
+                                            exercise_mapping::set_alias(ex, &self.alias_canonical);
+                                        }
+                                    }
+                                    self.mapping_exercises.clear();
+                                    self.aliases_changed();
+                                }
+                            });
+                        } else {
+                            ui.label("Select two or more exercises to merge them.");
+                        }
+                        let mut aliases: Vec<(String, String)> =
+                            exercise_mapping::aliases().into_iter().collect();
+                        aliases.sort();
+                        egui::Grid::new("alias_grid").striped(true).show(ui, |ui| {
+                            for (alias, canonical) in &aliases {
+                                ui.label(alias);
+                                ui.label(format!("\u{2192} {canonical}"));
+                                if ui.button("Remove").clicked() {
+                                    exercise_mapping::remove_alias(alias);
+                                    self.aliases_changed();
+                                }
+                                ui.end_row();
+                            }
+                        });
+                        ui.separator();
+                        if ui.button("Find Likely Duplicates").clicked() {
+                            let names: Vec<String> = self
+                                .workouts
+                                .iter()
+                                .map(|e| e.exercise.clone())
+                                .collect::<BTreeSet<_>>()
+                                .into_iter()
+                                .collect();
+                            self.alias_suggestions = exercise_utils::likely_duplicates(&names);
+                            if self.alias_suggestions.is_empty() {
+                                self.mapping_message = Some("No likely duplicates found".into());
+                                self.mapping_toast_start = Some(Instant::now());
+                            }
+                        }
+                        let mut merged = None;
+                        egui::Grid::new("alias_suggestions")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (i, (a, b, score)) in self.alias_suggestions.iter().enumerate()
+                                {
+                                    ui.label(format!("{a} / {b} ({:.0}%)", score * 100.0));
+                                    if ui.button(format!("Keep \"{a}\"")).clicked() {
+                                        exercise_mapping::set_alias(b, a);
+                                        merged = Some(i);
+                                    }
+                                    if ui.button(format!("Keep \"{b}\"")).clicked() {
+                                        exercise_mapping::set_alias(a, b);
+                                        merged = Some(i);
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        if let Some(i) = merged {
+                            self.alias_suggestions.remove(i);
+                            self.aliases_changed();
+                        }
+                    });
+                    if ui.button("Open Config Directory").clicked() {
+                        if let Some(dir) = dirs::config_dir() {
+                            let _ = open::that(dir);
+                        }
+                    }
+                });
+            self.show_mapping = open;
+            if self.settings.show_mapping != self.show_mapping {
+                self.settings.show_mapping = self.show_mapping;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_hard_sets_window {
+            let mut open = self.show_hard_sets_window;
+            egui::Window::new("Weekly Hard Sets")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(500.0)
+                .show(ctx, |ui| {
+                    self.draw_hard_sets(ui);
+                });
+            self.show_hard_sets_window = open;
+            if self.settings.show_hard_sets_window != self.show_hard_sets_window {
+                self.settings.show_hard_sets_window = self.show_hard_sets_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_sessions_window {
+            let mut open = self.show_sessions_window;
+            egui::Window::new("Workout Sessions")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(600.0)
+                .default_height(400.0)
+                .show(ctx, |ui| {
+                    self.draw_sessions(ui);
+                });
+            self.show_sessions_window = open;
+            if self.settings.show_sessions_window != self.show_sessions_window {
+                self.settings.show_sessions_window = self.show_sessions_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_session_analytics_window {
+            let mut open = self.show_session_analytics_window;
+            egui::Window::new("Session Analytics")
+                .open(&mut open)
+                .resizable(true)
+                .vscroll(true)
+                .show(ctx, |ui| {
+                    self.draw_session_analytics(ui);
+                });
+            self.show_session_analytics_window = open;
+            if self.settings.show_session_analytics_window != self.show_session_analytics_window {
+                self.settings.show_session_analytics_window = self.show_session_analytics_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_calendar_window {
+            let mut open = self.show_calendar_window;
+            egui::Window::new("Training Calendar")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(780.0)
+                .show(ctx, |ui| {
+                    self.draw_calendar(ui);
+                });
+            self.show_calendar_window = open;
+            if self.settings.show_calendar_window != self.show_calendar_window {
+                self.settings.show_calendar_window = self.show_calendar_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_rep_max_window {
+            let mut open = self.show_rep_max_window;
+            egui::Window::new("Rep Max Matrix")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(700.0)
+                .show(ctx, |ui| {
+                    self.draw_rep_max_matrix(ui);
+                });
+            self.show_rep_max_window = open;
+            if self.settings.show_rep_max_window != self.show_rep_max_window {
+                self.settings.show_rep_max_window = self.show_rep_max_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_calibration_window {
+            let mut open = self.show_calibration_window;
+            egui::Window::new("1RM Calibration")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(700.0)
+                .show(ctx, |ui| {
+                    self.draw_calibration(ui);
+                });
+            self.show_calibration_window = open;
+            if self.settings.show_calibration_window != self.show_calibration_window {
+                self.settings.show_calibration_window = self.show_calibration_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_stalled_window {
+            let mut open = self.show_stalled_window;
+            egui::Window::new("Stalled Lifts")
+                .open(&mut open)
+                .resizable(true)
+                .default_width(600.0)
+                .show(ctx, |ui| {
+                    self.draw_stalled_lifts(ui);
+                });
+            self.show_stalled_window = open;
+            if self.settings.show_stalled_window != self.show_stalled_window {
+                self.settings.show_stalled_window = self.show_stalled_window;
+                self.settings_dirty = true;
+            }
+        }
+
+        if self.show_template_review {
+            let mut open = self.show_template_review;
+            let mut apply = false;
+            egui::Window::new("Review Exercise Templates")
+                .open(&mut open)
+                .vscroll(true)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} unmapped exercises matched a Hevy template.",
+                        self.template_proposals.len()
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button("Select All").clicked() {
+                            for p in &mut self.template_proposals {
+                                p.accepted = true;
+                            }
+                        }
+                        if ui.button("Deselect All").clicked() {
+                            for p in &mut self.template_proposals {
+                                p.accepted = false;
+                            }
+                        }
+                    });
+                    egui::Grid::new("template_review_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.label("");
+                            ui.label("Exercise");
+                            ui.label("Primary");
+                            ui.label("Secondary");
+                            ui.label("Equipment");
+                            ui.label("Type");
+                            ui.end_row();
+                            for p in &mut self.template_proposals {
+                                ui.checkbox(&mut p.accepted, "");
+                                ui.label(&p.exercise).on_hover_text(&p.template);
+                                ui.label(&p.mapping.primary);
+                                ui.label(p.mapping.secondary.join(", "));
+                                ui.label(
+                                    p.info
+                                        .equipment
+                                        .map(|e| format!("{:?}", e))
+                                        .unwrap_or_default(),
+                                );
+                                ui.label(format!("{:?}", p.info.kind));
+                                ui.end_row();
+                            }
+                        });
+                    ui.horizontal(|ui| {
+                        if ui.button("Apply Selected").clicked() {
+                            apply = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            self.show_template_review = false;
+                        }
+                    });
                 });
-            self.show_mapping = open;
-            if self.settings.show_mapping != self.show_mapping {
-                self.settings.show_mapping = self.show_mapping;
-                self.settings_dirty = true;
+            if apply {
+                let mut applied = 0;
+                for p in self.template_proposals.iter().filter(|p| p.accepted) {
+                    exercise_mapping::set(p.exercise.clone(), p.mapping.clone());
+                    if exercise_catalog::get(&p.exercise).is_none() {
+                        exercise_catalog::set(p.exercise.clone(), p.info);
+                    }
+                    applied += 1;
+                }
+                self.mapping_dirty = true;
+                self.mapping_message = Some(format!("Mapped {applied} exercises from Hevy"));
+                self.mapping_toast_start = Some(Instant::now());
+                self.template_proposals.clear();
+                self.show_template_review = false;
+            } else if self.show_template_review {
+                self.show_template_review = open;
             }
         }
 
         if self.show_settings {
             let prev_start = self.settings.start_date;
             let prev_end = self.settings.end_date;
+            let mut clear_store = false;
             egui::Window::new("Settings")
                 .default_width(400.0)
                 .open(&mut self.show_settings)
@@ -4117,8 +6747,8 @@ impl App for MyApp {
                                                 }
                                                 if ui
                                                     .checkbox(
-                                                        &mut self.settings.show_exercise_volume,
-                                                        "Show Exercise Volume",
+                                                        &mut self.settings.show_category_volume,
+                                                        "Show Category Volume",
                                                     )
                                                     .changed()
                                                 {
@@ -4126,6 +6756,15 @@ impl App for MyApp {
                                                 }
                                                 ui.end_row();
 
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_exercise_volume,
+                                                        "Show Exercise Volume",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
                                                 if ui
                                                     .checkbox(
                                                         &mut self.settings.show_weekly_summary,
@@ -4135,6 +6774,8 @@ impl App for MyApp {
                                                 {
                                                     self.settings_dirty = true;
                                                 }
+                                                ui.end_row();
+
                                                 if ui
                                                     .checkbox(
                                                         &mut self.settings.show_rpe,
@@ -4235,6 +6876,17 @@ impl App for MyApp {
                                                 }
                                                 ui.end_row();
 
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_plateaus,
+                                                        "Shade plateaus",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
+                                                ui.end_row();
+
                                                 if ui
                                                     .checkbox(
                                                         &mut self.settings.show_rpe_trend,
@@ -4244,6 +6896,46 @@ impl App for MyApp {
                                                 {
                                                     self.settings_dirty = true;
                                                 }
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_pace,
+                                                        "Show Pace",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
+                                                ui.end_row();
+
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_distance_per_week,
+                                                        "Show Distance per week",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_hold_time,
+                                                        "Show Hold Time",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
+                                                ui.end_row();
+
+                                                if ui
+                                                    .checkbox(
+                                                        &mut self.settings.show_bodyweight_load,
+                                                        "Show Bodyweight Load",
+                                                    )
+                                                    .changed()
+                                                {
+                                                    self.settings_dirty = true;
+                                                }
                                                 ui.label("");
                                                 ui.end_row();
                                             },
@@ -4440,6 +7132,30 @@ impl App for MyApp {
                                         }
                                     });
                                     ui.end_row();
+
+                                    ui.horizontal(|ui| {
+                                        ui.label("Duration bin (min):");
+                                        let mut b =
+                                            format!("{:.0}", self.settings.duration_bin_size);
+                                        if ui.text_edit_singleline(&mut b).changed()
+                                            && let Ok(v) = b.parse::<f32>()
+                                        {
+                                            self.settings.duration_bin_size = v.max(1.0);
+                                            self.settings_dirty = true;
+                                        }
+                                    });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Density bin:");
+                                        let mut b =
+                                            format!("{:.0}", self.settings.density_bin_size);
+                                        if ui.text_edit_singleline(&mut b).changed()
+                                            && let Ok(v) = b.parse::<f32>()
+                                        {
+                                            self.settings.density_bin_size = v.max(1.0);
+                                            self.settings_dirty = true;
+                                        }
+                                    });
+                                    ui.end_row();
                                 });
                             });
 
@@ -4519,6 +7235,7 @@ impl App for MyApp {
                                                             OneRmFormula::OConner => "O'Conner",
                                                             OneRmFormula::Wathan => "Wathan",
                                                             OneRmFormula::Lander => "Lander",
+                                                            OneRmFormula::Rpe => "RPE table",
                                                         },
                                                     )
                                                     .show_ui(ui, |ui| {
@@ -4557,11 +7274,20 @@ impl App for MyApp {
                                                             OneRmFormula::Lander,
                                                             "Lander",
                                                         );
+                                                        ui.selectable_value(
+                                                            &mut self.settings.one_rm_formula,
+                                                            OneRmFormula::Rpe,
+                                                            "RPE table",
+                                                        );
                                                     });
                                                     if prev != self.settings.one_rm_formula {
                                                         self.settings_dirty = true;
                                                     }
                                                 });
+                                                if self.settings.one_rm_formula == OneRmFormula::Rpe
+                                                {
+                                                    draw_rpe_table_editor(ui);
+                                                }
                                                 ui.horizontal(|ui| {
                                                     ui.label("X Axis:");
                                                     let prev = self.settings.x_axis;
@@ -4642,6 +7368,23 @@ impl App for MyApp {
                                                         self.settings_dirty = true;
                                                     }
                                                 });
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Body weight:");
+                                                    let f = self.settings.weight_unit.factor();
+                                                    let mut bw = self
+                                                        .settings
+                                                        .body_weight
+                                                        .map(|w| format!("{:.1}", w * f))
+                                                        .unwrap_or_default();
+                                                    if ui.text_edit_singleline(&mut bw).changed() {
+                                                        self.settings.body_weight = bw
+                                                            .trim()
+                                                            .parse::<f32>()
+                                                            .ok()
+                                                            .map(|w| w / f);
+                                                        self.settings_dirty = true;
+                                                    }
+                                                });
                                             });
                                         egui::CollapsingHeader::new("Aggregation")
                                             .default_open(true)
@@ -4728,6 +7471,66 @@ impl App for MyApp {
                                                         self.settings_dirty = true;
                                                     }
                                                 });
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Muscle credit:");
+                                                    let prev = self.settings.weighted_muscle_credit;
+                                                    egui::ComboBox::from_id_source(
+                                                        "muscle_credit_setting",
+                                                    )
+                                                    .selected_text(
+                                                        if self.settings.weighted_muscle_credit {
+                                                            "Weighted"
+                                                        } else {
+                                                            "Primary only"
+                                                        },
+                                                    )
+                                                    .show_ui(ui, |ui| {
+                                                        ui.selectable_value(
+                                                            &mut self
+                                                                .settings
+                                                                .weighted_muscle_credit,
+                                                            false,
+                                                            "Primary only",
+                                                        );
+                                                        ui.selectable_value(
+                                                            &mut self
+                                                                .settings
+                                                                .weighted_muscle_credit,
+                                                            true,
+                                                            "Weighted",
+                                                        );
+                                                    });
+                                                    if prev != self.settings.weighted_muscle_credit
+                                                    {
+                                                        self.settings_dirty = true;
+                                                    }
+                                                    if self.settings.weighted_muscle_credit {
+                                                        ui.label("Primary:");
+                                                        let mut p = format!(
+                                                            "{}",
+                                                            self.settings.primary_credit
+                                                        );
+                                                        if ui.text_edit_singleline(&mut p).changed()
+                                                            && let Ok(v) = p.parse::<f32>()
+                                                        {
+                                                            self.settings.primary_credit =
+                                                                v.max(0.0);
+                                                            self.settings_dirty = true;
+                                                        }
+                                                        ui.label("Secondary:");
+                                                        let mut s = format!(
+                                                            "{}",
+                                                            self.settings.secondary_credit
+                                                        );
+                                                        if ui.text_edit_singleline(&mut s).changed()
+                                                            && let Ok(v) = s.parse::<f32>()
+                                                        {
+                                                            self.settings.secondary_credit =
+                                                                v.max(0.0);
+                                                            self.settings_dirty = true;
+                                                        }
+                                                    }
+                                                });
                                             });
                                     });
                             });
@@ -4938,6 +7741,32 @@ impl App for MyApp {
                                                         self.show_exercise_popup = true;
                                                     }
                                                 });
+                                                ui.horizontal(|ui| {
+                                                    ui.label("Category:");
+                                                    let prev =
+                                                        self.settings.category_filter.clone();
+                                                    egui::ComboBox::from_id_source(
+                                                        "category_filter_combo",
+                                                    )
+                                                    .selected_text(prev.as_deref().unwrap_or("All"))
+                                                    .show_ui(ui, |ui| {
+                                                        ui.selectable_value(
+                                                            &mut self.settings.category_filter,
+                                                            None::<String>,
+                                                            "All",
+                                                        );
+                                                        for c in body_parts::all_categories() {
+                                                            ui.selectable_value(
+                                                                &mut self.settings.category_filter,
+                                                                Some(c.clone()),
+                                                                &c,
+                                                            );
+                                                        }
+                                                    });
+                                                    if prev != self.settings.category_filter {
+                                                        self.settings_dirty = true;
+                                                    }
+                                                });
                                                 ui.horizontal(|ui| {
                                                     ui.label("Exercise type:");
                                                     let prev = self.settings.exercise_type_filter;
@@ -5124,6 +7953,27 @@ impl App for MyApp {
                                         self.settings_dirty = true;
                                     }
                                     ui.end_row();
+                                    ui.label("CSV date format:");
+                                    let prev = self.settings.date_format;
+                                    egui::ComboBox::from_id_source("date_format_combo")
+                                        .selected_text(prev.label())
+                                        .show_ui(ui, |ui| {
+                                            for f in DateFormat::ALL {
+                                                ui.selectable_value(
+                                                    &mut self.settings.date_format,
+                                                    f,
+                                                    f.label(),
+                                                );
+                                            }
+                                        });
+                                    if prev != self.settings.date_format {
+                                        self.settings_dirty = true;
+                                    }
+                                    ui.end_row();
+                                    if ui.button("Clear Stored Workouts").clicked() {
+                                        clear_store = true;
+                                    }
+                                    ui.end_row();
                                 });
                             });
 
@@ -5138,11 +7988,71 @@ impl App for MyApp {
                                         self.settings_dirty = true;
                                     }
                                     ui.end_row();
+                                    ui.label("Sync mode:");
+                                    let prev = self.settings.sync_mode;
+                                    egui::ComboBox::from_id_source("sync_mode_combo")
+                                        .selected_text(match prev {
+                                            sync::SyncMode::NewWorkouts => "New workouts",
+                                            sync::SyncMode::Events => "Edits & deletions",
+                                        })
+                                        .show_ui(ui, |ui| {
+                                            ui.selectable_value(
+                                                &mut self.settings.sync_mode,
+                                                sync::SyncMode::NewWorkouts,
+                                                "New workouts",
+                                            );
+                                            ui.selectable_value(
+                                                &mut self.settings.sync_mode,
+                                                sync::SyncMode::Events,
+                                                "Edits & deletions",
+                                            );
+                                        });
+                                    if prev != self.settings.sync_mode {
+                                        self.settings_dirty = true;
+                                    }
+                                    ui.end_row();
+                                    ui.label("Max attempts:");
+                                    let mut attempts =
+                                        self.settings.sync_retry.max_attempts.to_string();
+                                    if ui.text_edit_singleline(&mut attempts).changed()
+                                        && let Ok(v) = attempts.trim().parse::<u32>()
+                                    {
+                                        self.settings.sync_retry.max_attempts = v.max(1);
+                                        self.settings_dirty = true;
+                                    }
+                                    ui.end_row();
+                                    ui.label("Retry delay (ms):");
+                                    let mut delay =
+                                        self.settings.sync_retry.base_delay_ms.to_string();
+                                    if ui.text_edit_singleline(&mut delay).changed()
+                                        && let Ok(v) = delay.trim().parse::<u64>()
+                                    {
+                                        self.settings.sync_retry.base_delay_ms = v;
+                                        self.settings_dirty = true;
+                                    }
+                                    ui.end_row();
+                                    if ui
+                                        .checkbox(
+                                            &mut self.settings.sync_templates,
+                                            "Map new exercises from Hevy templates",
+                                        )
+                                        .changed()
+                                    {
+                                        self.settings_dirty = true;
+                                    }
+                                    ui.end_row();
                                 });
                             });
                     });
                 });
 
+            if clear_store {
+                store::clear();
+                self.workouts.clear();
+                self.stats = BasicStats::default();
+                self.update_filter_values();
+            }
+
             if (self.settings.start_date != prev_start || self.settings.end_date != prev_end)
                 && !self.workouts.is_empty()
             {
@@ -5204,6 +8114,7 @@ impl App for MyApp {
         if self.mapping_dirty {
             exercise_mapping::save();
             exercise_mapping::load();
+            exercise_catalog::save();
             ctx.request_repaint();
             self.mapping_dirty = false;
         }
@@ -5247,12 +8158,19 @@ mod tests {
         s.weight_bin_size = 5.0;
         s.show_volume_histogram = true;
         s.volume_bin_size = 150.0;
+        s.duration_bin_size = 15.0;
+        s.density_bin_size = 40.0;
         s.show_rpe_histogram = true;
         s.rpe_bin_size = 0.5;
         s.show_weight_reps_scatter = true;
         s.show_crosshair = true;
         s.show_rpe = true;
         s.show_rpe_trend = true;
+        s.show_pace = true;
+        s.show_distance_per_week = true;
+        s.show_hold_time = true;
+        s.show_bodyweight_load = true;
+        s.body_weight = Some(180.0);
         s.show_weight_trend = true;
         s.show_volume_trend = true;
         s.show_weight_forecast = true;
@@ -5269,6 +8187,7 @@ mod tests {
         s.set_type_filter = Some("working".into());
         s.superset_filter = Some("A".into());
         s.body_part_filter = Some("Chest".into());
+        s.category_filter = Some("Horizontal Push".into());
         s.exercise_type_filter = Some(ExerciseType::Compound);
         s.min_rpe = Some(6.0);
         s.max_rpe = Some(9.0);
@@ -5284,6 +8203,7 @@ mod tests {
         s.show_body_part_volume = true;
         s.show_body_part_distribution = true;
         s.show_body_part_trend = true;
+        s.show_category_volume = true;
         s.show_exercise_volume = true;
         s.show_weekly_summary = true;
         s.show_exercise_stats = true;
@@ -5296,12 +8216,50 @@ mod tests {
         s.show_stats_window = true;
         s.show_overall_analysis_window = true;
         s.show_mapping = true;
+        s.show_hard_sets_window = true;
+        s.show_sessions_window = true;
+        s.show_session_analytics_window = true;
+        s.show_calendar_window = true;
+        s.show_rep_max_window = true;
+        s.rep_max_stale_months = 12;
+        s.show_calibration_window = true;
+        s.show_stalled_window = true;
+        s.show_plateaus = false;
+        s.plateau = PlateauSettings {
+            window: PlateauWindow::Sessions,
+            length: 6,
+            regression_pct: 3.0,
+        };
+        s.calibration_weeks = 6;
+        s.preferred_formulas
+            .insert("Squat".into(), OneRmFormula::Brzycki);
+        s.calendar_metric = CalendarMetric::Sessions;
+        s.weekly_session_target = 4;
+        s.hard_set_min_rpe = Some(7.0);
+        s.volume_landmarks.insert(
+            "Chest".into(),
+            VolumeLandmarks {
+                mev: 8.0,
+                mav: 14.0,
+                mrv: 20.0,
+            },
+        );
         s.body_part_volume_aggregation = VolumeAggregation::Monthly;
+        s.weighted_muscle_credit = true;
+        s.primary_credit = 1.0;
+        s.secondary_credit = 0.25;
         s.auto_load_last = false;
         s.last_file = Some("/tmp/test.csv".into());
         s.check_prs = true;
         s.github_repo = Some("user/repo".into());
         s.last_pr = Some(5);
+        s.date_format = DateFormat::MonthDayNumeric;
+        s.sync_mode = sync::SyncMode::Events;
+        s.sync_retry = sync::RetryPolicy {
+            max_attempts: 2,
+            base_delay_ms: 250,
+        };
+        s.sync_templates = false;
         s.selected_exercises = ExerciseSelection::Selected(vec!["Bench".into()]);
         s.table_filter = "bench".into();
         s.sort_column = SortColumn::Weight;
@@ -5463,7 +8421,7 @@ mod tests {
     fn parse_workout_csv_basic() {
         let data = "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_lbs,reps,distance_miles,duration_seconds,rpe\n\
 Week 12 - Lower - Strength,\"26 Jul 2025, 07:06\",\"26 Jul 2025, 08:11\",desc,\"Lying Leg Curl (Machine)\",,,0,warmup,100,10,,,\n";
-        let entries = parse_workout_csv(data.as_bytes(), None).unwrap();
+        let entries = parse_workout_csv(data.as_bytes(), DateFormat::Auto, None).unwrap();
         assert_eq!(entries.len(), 1);
         let e = &entries[0];
         assert_eq!(e.date, "2025-07-26");
@@ -5479,7 +8437,7 @@ Week 12 - Lower - Strength,\"26 Jul 2025, 07:06\",\"26 Jul 2025, 08:11\",desc,\"
     fn parse_workout_csv_weight_kg() {
         let data = "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_kg,reps,distance_miles,duration_seconds,rpe\n\
 Week 1 - Upper,\"27 Jul 2025, 07:00\",,desc,Bench Press,,,0,working,50,8,,,\n";
-        let entries = parse_workout_csv(data.as_bytes(), None).unwrap();
+        let entries = parse_workout_csv(data.as_bytes(), DateFormat::Auto, None).unwrap();
         assert_eq!(entries.len(), 1);
         let e = &entries[0];
         assert_eq!(e.date, "2025-07-27");
@@ -5491,15 +8449,95 @@ Week 1 - Upper,\"27 Jul 2025, 07:00\",,desc,Bench Press,,,0,working,50,8,,,\n";
     }
 
     #[test]
-    fn parse_workout_csv_skips_missing() {
+    fn parse_workout_csv_keeps_bodyweight_and_cardio() {
+        let data = "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_lbs,reps,distance_miles,duration_seconds,rpe\n\
+Week 1,\"01 Jan 2024, 10:00\",,desc,Pull Up,,,0,working,,8,,,\n\
+Week 1,\"01 Jan 2024, 10:05\",,desc,Bench Press,,,1,working,135,,,,\n\
+Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n\
+Week 1,\"01 Jan 2024, 10:15\",,desc,Plank,,,0,working,,,,60,\n\
+Week 1,\"01 Jan 2024, 10:20\",,desc,Running,,,0,working,,,3.1,1500,\n";
+        let entries = parse_workout_csv(data.as_bytes(), DateFormat::Auto, None).unwrap();
+        assert_eq!(entries.len(), 4);
+        assert_eq!(entries[0].weight, None);
+        assert_eq!(entries[0].reps, Some(8));
+        assert!(entries[0].is_bodyweight());
+        assert_eq!(entries[1].weight, Some(135.0));
+        assert_eq!(entries[1].reps, Some(5));
+        assert!(!entries[1].is_bodyweight());
+        assert_eq!(entries[2].duration(), Some(60.0));
+        assert_eq!(entries[2].volume(), None);
+        assert_eq!(entries[3].distance(), Some(3.1));
+    }
+
+    #[test]
+    fn bodyweight_load_adds_or_subtracts_weight() {
+        let entry = |exercise: &str, weight: Option<f32>| WorkoutEntry {
+            date: "2024-01-01".into(),
+            start: None,
+            exercise: exercise.into(),
+            weight,
+            reps: Some(8),
+            raw: RawWorkoutRow::default(),
+        };
+        assert_eq!(entry("Chin Up", None).bodyweight_load(180.0), Some(180.0));
+        assert_eq!(
+            entry("Chin Up (Weighted)", Some(25.0)).bodyweight_load(180.0),
+            Some(205.0)
+        );
+        assert_eq!(
+            entry("Pull Up (Assisted)", Some(40.0)).bodyweight_load(180.0),
+            Some(140.0)
+        );
+    }
+
+    #[test]
+    fn parse_workout_csv_reports_skips() {
         let data = "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_lbs,reps,distance_miles,duration_seconds,rpe\n\
 Week 1,\"01 Jan 2024, 10:00\",,desc,Bench Press,,,0,working,,5,,,\n\
 Week 1,\"01 Jan 2024, 10:05\",,desc,Bench Press,,,1,working,135,,,,\n\
-Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
-        let entries = parse_workout_csv(data.as_bytes(), None).unwrap();
+Week 1,\"2024/01/01\",,desc,Bench Press,,,2,working,135,5,,,\n\
+Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,three,working,135,5,,,\n\
+Week 1,\"01 Jan 2024, 10:15\",,desc,Bench Press,,,4,working,135,5,,,\n";
+        let (entries, report) =
+            parse_workout_csv_with_report(data.as_bytes(), DateFormat::Auto, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(report.total_rows, 5);
+        assert_eq!(report.imported, 2);
+        let counts = report.counts();
+        assert_eq!(counts[&SkipReason::NoMeasurement], 1);
+        assert_eq!(counts[&SkipReason::BadDate], 1);
+        assert_eq!(counts[&SkipReason::Malformed], 1);
+        let bad_date = report.samples(SkipReason::BadDate, 5);
+        assert_eq!(bad_date[0].line, 4);
+        assert_eq!(bad_date[0].value, "2024/01/01");
+        assert_eq!(report.samples(SkipReason::NoMeasurement, 5)[0].line, 3);
+    }
+
+    #[test]
+    fn parse_workout_csv_detects_and_overrides_date_format() {
+        let data = "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_lbs,reps,distance_miles,duration_seconds,rpe\n\
+Week 1,\"03/02/2024 18:30\",,desc,Bench Press,,,0,working,135,5,,,\n\
+Week 1,\"26. Juli 2025, 07:06\",,desc,Squat,,,0,working,225,5,,,\n\
+Week 1,\"04/02/2024 18:30\",,desc,Bench Press,,,0,working,140,5,,,\n";
+        let (entries, report) =
+            parse_workout_csv_with_report(data.as_bytes(), DateFormat::Auto, None).unwrap();
+        assert_eq!(report.date_format, Some(DateFormat::DayMonthNumeric));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].date, "2024-02-03");
+        assert_eq!(
+            entries[0].start,
+            chrono::NaiveDate::from_ymd_opt(2024, 2, 3).and_then(|d| d.and_hms_opt(18, 30, 0))
+        );
+        assert_eq!(report.counts()[&SkipReason::BadDate], 1);
+
+        let entries =
+            parse_workout_csv(data.as_bytes(), DateFormat::MonthDayNumeric, None).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].date, "2024-03-02");
+
+        let entries = parse_workout_csv(data.as_bytes(), DateFormat::DayMonthName, None).unwrap();
         assert_eq!(entries.len(), 1);
-        assert_eq!(entries[0].weight, Some(135.0));
-        assert_eq!(entries[0].reps, Some(5));
+        assert_eq!(entries[0].date, "2025-07-26");
     }
 
     #[test]
@@ -5510,6 +8548,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5517,6 +8556,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Squat".into(),
                 weight: Some(150.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5539,6 +8579,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5546,6 +8587,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Lying Leg Curl (Machine)".into(),
                 weight: Some(100.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5568,6 +8610,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5575,6 +8618,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Push-Up".into(),
                 weight: Some(0.0),
                 reps: Some(15),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5597,6 +8641,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5604,6 +8649,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Lying Leg Curl (Machine)".into(),
                 weight: Some(100.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5625,6 +8671,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5632,6 +8679,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Squat".into(),
                 weight: Some(150.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5653,6 +8701,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     exercise_notes: Some("#tempo slow".into()),
                     ..RawWorkoutRow::default()
@@ -5663,6 +8712,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     exercise_notes: Some("fast".into()),
                     ..RawWorkoutRow::default()
@@ -5693,6 +8743,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(45.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow {
                     set_type: Some("warmup".into()),
                     ..RawWorkoutRow::default()
@@ -5703,6 +8754,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     set_type: Some("working".into()),
                     ..RawWorkoutRow::default()
@@ -5727,6 +8779,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -5734,6 +8787,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(200.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -5759,6 +8813,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(45.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow {
                     set_type: Some("warmup".into()),
                     title: Some("W1".into()),
@@ -5771,6 +8826,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     set_type: Some("working".into()),
                     title: Some("W1".into()),
@@ -5783,6 +8839,7 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
                 exercise: "Bench".into(),
                 weight: Some(105.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     set_type: Some("working".into()),
                     title: Some("W2".into()),
@@ -5866,4 +8923,170 @@ Week 1,\"01 Jan 2024, 10:10\",,desc,Bench Press,,,2,working,135,5,,,\n";
         let settings = Settings::default();
         assert!(!settings.show_weekly_summary_table_window);
     }
+
+    #[test]
+    fn sync_error_shows_toast() {
+        let mut app = MyApp {
+            syncing: true,
+            ..Default::default()
+        };
+        let err = sync::SyncError::Other("connection reset".into());
+        app.finish_sync(SyncMessage::Error(err));
+        assert!(!app.syncing);
+        assert_eq!(
+            app.pr_message.as_deref(),
+            Some("Sync failed: connection reset")
+        );
+        assert!(app.pr_toast_start.is_some());
+    }
+
+    #[test]
+    fn alias_change_regroups_stats() {
+        let entry = |exercise: &str| WorkoutEntry {
+            date: "2024-01-01".into(),
+            start: None,
+            exercise: exercise.into(),
+            weight: Some(100.0),
+            reps: Some(5),
+            raw: RawWorkoutRow::default(),
+        };
+        let mut app = MyApp::default();
+        app.workouts = vec![
+            entry("Alias Refresh Press"),
+            entry("Alias Refresh Prss"),
+            entry("Alias Refresh Prss"),
+        ];
+        app.update_selected_stats();
+        assert_eq!(
+            app.stats.most_common_exercise.as_deref(),
+            Some("Alias Refresh Prss")
+        );
+
+        exercise_mapping::set_alias("Alias Refresh Prss", "Alias Refresh Press");
+        app.aliases_changed();
+        exercise_mapping::remove_alias("Alias Refresh Prss");
+
+        assert_eq!(
+            app.stats.most_common_exercise.as_deref(),
+            Some("Alias Refresh Press")
+        );
+        assert!(app.mapping_dirty);
+    }
+
+    #[test]
+    fn template_fetch_after_sync_keeps_its_receiver() {
+        use httpmock::prelude::*;
+        use std::env;
+
+        let _guard = ENV_MUTEX.lock().unwrap();
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/exercise_templates");
+            then.status(200)
+                .body(r#"{"page":1,"page_count":1,"exercise_templates":[]}"#);
+        });
+
+        let dir = tempfile::tempdir().unwrap();
+        let prev_config = env::var_os("XDG_CONFIG_HOME");
+        unsafe {
+            env::set_var("XDG_CONFIG_HOME", dir.path());
+        }
+
+        let mut app = MyApp::default();
+        app.settings.hevy_api_key = Some("key".into());
+        app.settings.sync_templates = true;
+        app.templates_url = server.url("/v1/exercise_templates");
+        app.workouts = vec![WorkoutEntry {
+            date: "2024-01-01".into(),
+            start: None,
+            exercise: "Template Pump Test (Machine)".into(),
+            weight: Some(100.0),
+            reps: Some(10),
+            raw: RawWorkoutRow::default(),
+        }];
+        let (tx, rx) = mpsc::channel();
+        tx.send(SyncMessage::Workouts(Vec::new())).unwrap();
+        drop(tx);
+        app.syncing = true;
+        app.sync_rx = Some(rx);
+
+        assert!(app.poll_sync());
+        assert_eq!(app.sync_task, "Downloading exercise templates...");
+        // The templates arrive only if their receiver survived the poll.
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while app.poll_sync() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert!(!app.syncing);
+        m.assert_hits(1);
+
+        if let Some(val) = prev_config {
+            unsafe {
+                env::set_var("XDG_CONFIG_HOME", val);
+            }
+        } else {
+            unsafe {
+                env::remove_var("XDG_CONFIG_HOME");
+            }
+        }
+    }
+
+    #[test]
+    fn templates_open_review_for_unmapped_exercises() {
+        let mut app = MyApp {
+            syncing: true,
+            ..Default::default()
+        };
+        app.workouts = vec![WorkoutEntry {
+            date: "2024-01-01".into(),
+            start: None,
+            exercise: "Template Review Test (Machine)".into(),
+            weight: Some(100.0),
+            reps: Some(10),
+            raw: RawWorkoutRow::default(),
+        }];
+        let template = sync::ExerciseTemplate {
+            id: "1".into(),
+            title: "Template Review Test (Machine)".into(),
+            kind: Some("weight_reps".into()),
+            primary_muscle_group: Some("hamstrings".into()),
+            secondary_muscle_groups: Vec::new(),
+            equipment: Some("machine".into()),
+        };
+        app.finish_sync(SyncMessage::Templates(vec![template]));
+        assert!(!app.syncing);
+        assert!(app.show_template_review);
+        assert_eq!(app.template_proposals.len(), 1);
+        assert_eq!(app.template_proposals[0].mapping.primary, "Hamstrings");
+    }
+
+    #[test]
+    fn filtered_sessions_reuse_loaded_sessions() {
+        let mut app = MyApp::default();
+        app.settings.exclude_warmups = true;
+        let set = |exercise: &str, set_type: &str| WorkoutEntry {
+            date: "2024-01-01".into(),
+            start: None,
+            exercise: exercise.into(),
+            weight: Some(100.0),
+            reps: Some(5),
+            raw: RawWorkoutRow {
+                workout_id: Some("w1".into()),
+                set_type: Some(set_type.into()),
+                ..RawWorkoutRow::default()
+            },
+        };
+        app.workouts = vec![
+            set("Squat", "warmup"),
+            set("Squat", "normal"),
+            set("Curl", "warmup"),
+        ];
+        app.update_filter_values();
+
+        let sessions = app.filtered_sessions();
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, "w1");
+        assert_eq!(sessions[0].exercises.len(), 1);
+        assert_eq!(sessions[0].set_count(), 1);
+    }
 }
@@ -1,18 +1,23 @@
 use chrono::{Datelike, NaiveDate};
 use egui::epaint::Hsva;
 use egui::{Align2, Color32, FontId, Pos2, Sense, Shape, Stroke, Ui, Vec2};
-use egui_plot::{Bar, BarChart, HLine, Line, PlotPoints, PlotUi, Points, VLine};
+use egui_plot::{Bar, BarChart, HLine, Line, PlotPoints, PlotUi, Points, Polygon, VLine};
 
-use crate::body_parts::body_part_for;
+use crate::body_parts::categories_for;
 use crate::exercise_mapping;
+use crate::rpe_table;
+use crate::workout::{Workout, build_workouts};
 use crate::{
     WeightUnit, WorkoutEntry,
     analysis::{
+        DoseStatus, MuscleCredit, Plateau, PlateauKind, PrEvent, PrKind, VolumeLandmarks,
         WeeklySummary, aggregate_rep_counts, aggregate_sets_by_body_part, linear_projection,
+        pr_events,
     },
     exercise_utils::normalize_exercise,
 };
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 
 /// Available formulas for estimating a one-rep max.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -43,6 +48,12 @@ pub enum OneRmFormula {
     ///
     /// Undefined when `reps >= 1.013 / 0.0267123` (~37.9).
     Lander,
+    /// `weight / percentage`, with the percentage for the set's reps and RPE
+    /// looked up in the editable [`rpe_table`](crate::rpe_table).
+    ///
+    /// Sets without an RPE, or outside the table, use the table's fallback
+    /// rep formula.
+    Rpe,
 }
 
 impl OneRmFormula {
@@ -73,6 +84,66 @@ impl OneRmFormula {
                     Some(weight / denom)
                 }
             }
+            OneRmFormula::Rpe => rpe_table::fallback().estimate(weight, reps),
+        }
+    }
+
+    /// Estimate a one-rep max for a set, taking its `rpe` into account when
+    /// the formula is [`OneRmFormula::Rpe`]. Other formulas ignore it.
+    pub fn estimate_set(self, weight: f64, reps: u32, rpe: Option<f32>) -> Option<f64> {
+        if self == OneRmFormula::Rpe
+            && let Some(pct) = rpe.and_then(|r| rpe_table::percentage(reps, r))
+        {
+            return Some(weight * 100.0 / pct as f64);
+        }
+        self.estimate(weight, reps)
+    }
+}
+
+/// Per-exercise formula overrides, keyed by normalized exercise name.
+pub type PreferredFormulas = HashMap<String, OneRmFormula>;
+
+/// Key the formulas preferred in the settings by normalized exercise name.
+pub fn preferred_formulas(preferred: &BTreeMap<String, OneRmFormula>) -> PreferredFormulas {
+    preferred
+        .iter()
+        .map(|(ex, f)| (normalize_exercise(ex), *f))
+        .collect()
+}
+
+/// Picks the 1RM formula for each exercise: its preferred formula when one
+/// is set, otherwise `default`.
+#[derive(Debug, Clone, Copy)]
+pub struct FormulaChoice<'a> {
+    pub default: OneRmFormula,
+    preferred: Option<&'a PreferredFormulas>,
+}
+
+impl<'a> FormulaChoice<'a> {
+    pub fn with_preferred(default: OneRmFormula, preferred: &'a PreferredFormulas) -> Self {
+        Self {
+            default,
+            preferred: Some(preferred),
+        }
+    }
+
+    /// The formula to use for `exercise`.
+    pub fn for_exercise(self, exercise: &str) -> OneRmFormula {
+        match self.preferred {
+            Some(map) if !map.is_empty() => map
+                .get(&normalize_exercise(exercise))
+                .copied()
+                .unwrap_or(self.default),
+            _ => self.default,
+        }
+    }
+}
+
+impl From<OneRmFormula> for FormulaChoice<'_> {
+    fn from(default: OneRmFormula) -> Self {
+        Self {
+            default,
+            preferred: None,
         }
     }
 }
@@ -132,6 +203,21 @@ pub enum HistogramMetric {
     Rpe { bin: f64 },
     /// Histogram of repetition counts.
     Reps { bin: f64 },
+    /// Histogram of session lengths in minutes.
+    Duration { bin: f64 },
+    /// Histogram of session volume per minute.
+    Density { bin: f64 },
+}
+
+/// Per-session value plotted by [`session_metric_line`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionMetric {
+    /// Session length in minutes.
+    Duration,
+    /// Volume per minute.
+    Density,
+    /// Sets completed per hour.
+    SetsPerHour,
 }
 
 /// Slice of a pie chart with metadata.
@@ -161,16 +247,43 @@ pub struct Record {
 pub struct LineWithMarker {
     pub line: Line,
     pub points: Vec<[f64; 2]>,
+    /// Date of each point in `points`, when known.
+    pub dates: Vec<NaiveDate>,
     pub max_point: Option<[f64; 2]>,
     pub label: Option<String>,
     pub records: Vec<Record>,
 }
 
+/// Events of `kind` for `exercise`, waiting to be matched to their sets.
+fn events_for<'a>(events: &'a [PrEvent], exercise: &str, kind: PrKind) -> Vec<&'a PrEvent> {
+    let ex_norm = normalize_exercise(exercise);
+    events
+        .iter()
+        .filter(|ev| ev.kind == kind && normalize_exercise(&ev.exercise) == ex_norm)
+        .collect()
+}
+
+/// Remove the event set by `e` from `pending`, returning whether there was
+/// one.
+fn take_event(pending: &mut Vec<&PrEvent>, e: &WorkoutEntry, date: NaiveDate) -> bool {
+    match pending
+        .iter()
+        .position(|ev| ev.date == date && ev.weight == e.weight && ev.reps == e.reps)
+    {
+        Some(i) => {
+            pending.remove(i);
+            true
+        }
+        None => false,
+    }
+}
+
 /// Generate a line plot of weight over time for one or more exercises.
 ///
 /// Only entries for the listed `exercises` within the optional date range are
 /// used. Invalid dates are ignored. A separate line is returned for each
-/// exercise.
+/// exercise. Record markers are the weight or set-volume events from
+/// [`pr_events`].
 pub fn weight_over_time_line(
     entries: &[WorkoutEntry],
     exercises: &[String],
@@ -182,10 +295,18 @@ pub fn weight_over_time_line(
     ma_window: Option<usize>,
     method: SmoothingMethod,
 ) -> Vec<LineWithMarker> {
+    let kind = match y_axis {
+        YAxis::Weight => PrKind::Weight,
+        YAxis::Volume => PrKind::SetVolume,
+    };
+    // The formula only affects 1RM events, which are not used here.
+    let events = pr_events(entries, OneRmFormula::Epley.into(), start, end);
     let mut lines = Vec::new();
     for exercise in exercises {
         let mut points = Vec::new();
+        let mut dates = Vec::new();
         let mut records = Vec::new();
+        let mut pending = events_for(&events, exercise, kind);
         let mut idx = 0usize;
         let mut max_val = f64::NEG_INFINITY;
         let mut max_point = None;
@@ -205,25 +326,28 @@ pub fn weight_over_time_line(
                         XAxis::WorkoutIndex => idx as f64,
                     };
                     let f = unit.factor() as f64;
-                    let y = match y_axis {
-                        YAxis::Weight => e.weight.unwrap() as f64 * f,
-                        YAxis::Volume => e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64,
+                    let (Some(weight), Some(reps)) = (e.weight, e.reps) else {
+                        continue;
                     };
-                    let cmp = match y_axis {
-                        YAxis::Weight => e.weight.unwrap() as f64 * f,
-                        YAxis::Volume => y,
+                    let weight = weight as f64 * f;
+                    let y = match y_axis {
+                        YAxis::Weight => weight,
+                        YAxis::Volume => weight * reps as f64,
                     };
-                    if cmp > max_val {
-                        max_val = cmp;
+                    if y > max_val {
+                        max_val = y;
                         max_point = Some([x, y]);
+                    }
+                    if take_event(&mut pending, e, d) {
                         records.push(Record {
                             point: [x, y],
                             date: d,
-                            weight: e.weight.unwrap() as f64 * f,
-                            reps: e.reps.unwrap() as u32,
+                            weight,
+                            reps,
                         });
                     }
                     points.push([x, y]);
+                    dates.push(d);
                     idx += 1;
                 }
             }
@@ -231,6 +355,7 @@ pub fn weight_over_time_line(
         lines.push(LineWithMarker {
             line: Line::new(PlotPoints::from(points.clone())).name(exercise),
             points: points.clone(),
+            dates,
             max_point,
             label: Some(match y_axis {
                 YAxis::Weight => "Max Weight".to_string(),
@@ -251,6 +376,7 @@ pub fn weight_over_time_line(
                     line: Line::new(PlotPoints::from(smooth_points.clone()))
                         .name(format!("{exercise} MA")),
                     points: smooth_points,
+                    dates: Vec::new(),
                     max_point: None,
                     label: None,
                     records: Vec::new(),
@@ -266,11 +392,11 @@ pub fn weight_over_time_line(
 ///
 /// The estimation is performed for each set using the supplied
 /// [`OneRmFormula`]. Only sets for `exercise` within the optional date range are
-/// included.
+/// included. Record markers are the 1RM events from [`pr_events`].
 pub fn estimated_1rm_line(
     entries: &[WorkoutEntry],
     exercises: &[String],
-    formula: OneRmFormula,
+    formula: FormulaChoice<'_>,
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
     x_axis: XAxis,
@@ -278,10 +404,14 @@ pub fn estimated_1rm_line(
     ma_window: Option<usize>,
     method: SmoothingMethod,
 ) -> Vec<LineWithMarker> {
+    let events = pr_events(entries, formula, start, end);
     let mut lines = Vec::new();
     for exercise in exercises {
         let mut points = Vec::new();
+        let mut dates = Vec::new();
         let mut records = Vec::new();
+        let mut pending = events_for(&events, exercise, PrKind::EstOneRm);
+        let formula = formula.for_exercise(exercise);
         let mut idx = 0usize;
         let mut max_est = f64::NEG_INFINITY;
         let mut max_point = None;
@@ -297,8 +427,11 @@ pub fn estimated_1rm_line(
             if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
                 if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
                     let f = unit.factor() as f64;
-                    let weight = e.weight.unwrap() as f64 * f;
-                    let est = match formula.estimate(weight, e.reps.unwrap()) {
+                    let (Some(weight), Some(reps)) = (e.weight, e.reps) else {
+                        continue;
+                    };
+                    let weight = weight as f64 * f;
+                    let est = match formula.estimate_set(weight, reps, e.raw.rpe) {
                         Some(v) => v,
                         None => continue,
                     };
@@ -309,14 +442,17 @@ pub fn estimated_1rm_line(
                     if est > max_est {
                         max_est = est;
                         max_point = Some([x, est]);
+                    }
+                    if take_event(&mut pending, e, d) {
                         records.push(Record {
                             point: [x, est],
                             date: d,
                             weight,
-                            reps: e.reps.unwrap() as u32,
+                            reps,
                         });
                     }
                     points.push([x, est]);
+                    dates.push(d);
                     idx += 1;
                 }
             }
@@ -324,6 +460,7 @@ pub fn estimated_1rm_line(
         lines.push(LineWithMarker {
             line: Line::new(PlotPoints::from(points.clone())).name(exercise),
             points: points.clone(),
+            dates,
             max_point,
             label: Some("Max 1RM".to_string()),
             records,
@@ -341,6 +478,7 @@ pub fn estimated_1rm_line(
                     line: Line::new(PlotPoints::from(smooth_points.clone()))
                         .name(format!("{exercise} MA")),
                     points: smooth_points,
+                    dates: Vec::new(),
                     max_point: None,
                     label: None,
                     records: Vec::new(),
@@ -351,6 +489,55 @@ pub fn estimated_1rm_line(
     lines
 }
 
+/// Shaded bands behind `exercise`'s `line` for each of its `plateaus`,
+/// spanning the points dated within the plateau and the line's full height.
+/// Stalls are orange and regressions red.
+pub fn plateau_bands(line: &LineWithMarker, exercise: &str, plateaus: &[Plateau]) -> Vec<Polygon> {
+    let (lo, hi) = line
+        .points
+        .iter()
+        .fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), p| {
+            (lo.min(p[1]), hi.max(p[1]))
+        });
+    let ex_norm = normalize_exercise(exercise);
+    let mut bands = Vec::new();
+    for p in plateaus
+        .iter()
+        .filter(|p| normalize_exercise(&p.exercise) == ex_norm)
+    {
+        let xs: Vec<f64> = line
+            .points
+            .iter()
+            .zip(&line.dates)
+            .filter(|(_, d)| **d >= p.start && **d <= p.end)
+            .map(|(pt, _)| pt[0])
+            .collect();
+        let (Some(x0), Some(x1)) = (
+            xs.iter().copied().reduce(f64::min),
+            xs.iter().copied().reduce(f64::max),
+        ) else {
+            continue;
+        };
+        let (x0, x1) = (x0 - 0.5, x1 + 0.5);
+        let color = match p.kind {
+            PlateauKind::Stalled => Color32::from_rgb(255, 165, 0),
+            PlateauKind::Regressed => Color32::RED,
+        };
+        bands.push(
+            Polygon::new(PlotPoints::from(vec![
+                [x0, lo],
+                [x1, lo],
+                [x1, hi],
+                [x0, hi],
+            ]))
+            .fill_color(color.gamma_multiply(0.15))
+            .stroke(Stroke::NONE)
+            .name(p.kind.label()),
+        );
+    }
+    bands
+}
+
 /// Build a histogram of rep counts for the selected exercises.
 ///
 /// The x-axis represents the number of reps performed and the bar height shows
@@ -373,7 +560,9 @@ pub fn rep_histogram(
 /// Build a histogram of the chosen `metric` across `entries`.
 ///
 /// Only entries within the optional date range are considered. Weights and
-/// volumes are converted using `unit`.
+/// volumes are converted using `unit`. Duration and density are counted per
+/// session, grouping the entries with [`build_workouts`] (see
+/// [`session_histogram`]).
 pub fn histogram(
     entries: &[WorkoutEntry],
     metric: HistogramMetric,
@@ -381,6 +570,51 @@ pub fn histogram(
     end: Option<NaiveDate>,
     unit: WeightUnit,
 ) -> BarChart {
+    if matches!(
+        metric,
+        HistogramMetric::Duration { .. } | HistogramMetric::Density { .. }
+    ) {
+        return session_histogram(&build_workouts(entries), metric, start, end, unit);
+    }
+    let f = unit.factor() as f64;
+    let values = entries
+        .iter()
+        .filter(|e| date_in_range(e, start, end).is_some())
+        .filter_map(|e| match metric {
+            HistogramMetric::Weight { .. } => e.weight.map(|w| w as f64 * f),
+            HistogramMetric::Volume { .. } => e.volume().map(|v| v as f64 * f),
+            HistogramMetric::Rpe { .. } => e.raw.rpe.map(|r| r as f64),
+            _ => e.reps.map(|r| r as f64),
+        });
+    bin_values(values, metric)
+}
+
+/// Build a histogram of session duration or density across `workouts`.
+///
+/// Sessions outside the optional date range or without an end time are
+/// skipped; set metrics produce an empty chart. Density is converted using
+/// `unit`.
+pub fn session_histogram(
+    workouts: &[Workout],
+    metric: HistogramMetric,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    unit: WeightUnit,
+) -> BarChart {
+    let f = unit.factor() as f64;
+    let values = workouts
+        .iter()
+        .filter(|w| workout_in_range(w, start, end).is_some())
+        .filter_map(|w| match metric {
+            HistogramMetric::Duration { .. } => w.minutes(),
+            HistogramMetric::Density { .. } => w.density().map(|d| d * f),
+            _ => None,
+        });
+    bin_values(values, metric)
+}
+
+/// Count `values` into bins of the size configured for `metric`.
+fn bin_values(values: impl Iterator<Item = f64>, metric: HistogramMetric) -> BarChart {
     use std::collections::BTreeMap;
 
     let (bin_size, name) = match metric {
@@ -388,27 +622,14 @@ pub fn histogram(
         HistogramMetric::Volume { bin } => (bin, "Volume"),
         HistogramMetric::Rpe { bin } => (bin, "RPE"),
         HistogramMetric::Reps { bin } => (bin, "Reps"),
+        HistogramMetric::Duration { bin } => (bin, "Duration"),
+        HistogramMetric::Density { bin } => (bin, "Density"),
     };
-    let f = unit.factor() as f64;
     let mut map: BTreeMap<i64, usize> = BTreeMap::new();
-    for e in entries {
-        if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
-            if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                let val = match metric {
-                    HistogramMetric::Weight { .. } => Some(e.weight.unwrap() as f64 * f),
-                    HistogramMetric::Volume { .. } => {
-                        Some(e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64)
-                    }
-                    HistogramMetric::Rpe { .. } => e.raw.rpe.map(|r| r as f64),
-                    HistogramMetric::Reps { .. } => Some(e.reps.unwrap() as f64),
-                };
-                if let Some(v) = val {
-                    if bin_size > 0.0 {
-                        let idx = (v / bin_size).floor() as i64;
-                        *map.entry(idx).or_insert(0) += 1;
-                    }
-                }
-            }
+    if bin_size > 0.0 {
+        for v in values {
+            let idx = (v / bin_size).floor() as i64;
+            *map.entry(idx).or_insert(0) += 1;
         }
     }
 
@@ -434,7 +655,10 @@ pub fn sets_per_day_bar(
 ) -> BarChart {
     let mut map: std::collections::BTreeMap<NaiveDate, usize> = std::collections::BTreeMap::new();
     for e in entries {
-        if exercise.map(|ex| ex == e.exercise).unwrap_or(true) {
+        if exercise
+            .map(|ex| normalize_exercise(ex) == normalize_exercise(&e.exercise))
+            .unwrap_or(true)
+        {
             if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
                 if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
                     *map.entry(d).or_insert(0) += 1;
@@ -450,28 +674,30 @@ pub fn sets_per_day_bar(
     BarChart::new(bars).name("Sets")
 }
 
-/// Create a bar chart showing the distribution of sets by primary body part.
+/// Create a bar chart showing the distribution of sets by body part.
 ///
 /// Entries outside the optional date range are ignored. The resulting chart
-/// contains one bar per body part with the height equal to the number of sets.
+/// contains one bar per body part with the height equal to the number of sets
+/// credited to it under `credit`.
 pub fn body_part_distribution(
     entries: &[WorkoutEntry],
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
+    credit: MuscleCredit,
 ) -> (BarChart, Vec<String>) {
     use std::collections::BTreeMap;
 
-    let map = aggregate_sets_by_body_part(entries, start, end);
+    let map = aggregate_sets_by_body_part(entries, start, end, credit);
     let mut bars = Vec::new();
     let mut body_parts = Vec::new();
     for (idx, (part, count)) in BTreeMap::from_iter(map).into_iter().enumerate() {
-        bars.push(Bar::new(idx as f64, count as f64));
+        bars.push(Bar::new(idx as f64, count));
         body_parts.push(part);
     }
     (BarChart::new(bars).name("Body Parts"), body_parts)
 }
 
-/// Create a pie chart showing the distribution of sets by primary body part.
+/// Create a pie chart showing the distribution of sets by body part.
 ///
 /// Returns a [`PieChart`] where each slice corresponds to a body part and
 /// contains metadata about the slice.
@@ -479,25 +705,26 @@ pub fn body_part_pie(
     entries: &[WorkoutEntry],
     start: Option<NaiveDate>,
     end: Option<NaiveDate>,
+    credit: MuscleCredit,
 ) -> PieChart {
     use std::collections::BTreeMap;
     use std::f64::consts::TAU;
 
-    let map = aggregate_sets_by_body_part(entries, start, end);
-    let total: f64 = map.values().sum::<usize>() as f64;
+    let map = aggregate_sets_by_body_part(entries, start, end, credit);
+    let total: f64 = map.values().sum();
     let mut angle = 0.0;
     let mut slices = Vec::new();
     let parts = map.len().max(1);
     for (idx, (part, count)) in BTreeMap::from_iter(map).into_iter().enumerate() {
         let sweep = if total > 0.0 {
-            (count as f64 / total) * TAU
+            (count / total) * TAU
         } else {
             0.0
         };
         let color: Color32 = Hsva::new(idx as f32 / parts as f32, 0.8, 0.8, 1.0).into();
         slices.push(PieSlice {
             label: part,
-            value: count as f64,
+            value: count,
             start: angle,
             sweep,
             color,
@@ -577,6 +804,137 @@ pub fn draw_pie_chart(ui: &mut Ui, chart: &PieChart, size: Vec2) -> Option<Strin
     None
 }
 
+/// Bar chart of how many records were beaten in each ISO week.
+///
+/// Bars are placed on the Monday of each week. First-time baselines are not
+/// counted.
+pub fn pr_timeline_bar(events: &[PrEvent]) -> BarChart {
+    let mut weeks: std::collections::BTreeMap<NaiveDate, usize> = std::collections::BTreeMap::new();
+    for ev in events.iter().filter(|ev| ev.is_improvement()) {
+        let monday =
+            ev.date - chrono::Duration::days(ev.date.weekday().num_days_from_monday() as i64);
+        *weeks.entry(monday).or_insert(0) += 1;
+    }
+    let bars: Vec<Bar> = weeks
+        .into_iter()
+        .map(|(d, n)| Bar::new(d.num_days_from_ce() as f64, n as f64).width(6.0))
+        .collect();
+    BarChart::new(bars).name("PRs")
+}
+
+/// Color of a calendar day holding `value` when the busiest day holds `max`.
+///
+/// Days are split into four shades of green like a contribution graph; days
+/// without training are grey.
+pub fn heat_color(value: f64, max: f64) -> Color32 {
+    const SHADES: [Color32; 4] = [
+        Color32::from_rgb(14, 68, 41),
+        Color32::from_rgb(0, 109, 50),
+        Color32::from_rgb(38, 166, 65),
+        Color32::from_rgb(57, 211, 83),
+    ];
+    if value <= 0.0 || max <= 0.0 {
+        return Color32::from_gray(45);
+    }
+    let level = ((value / max) * 4.0).ceil() as usize;
+    SHADES[level.clamp(1, 4) - 1]
+}
+
+/// Draw a contribution-style calendar from `first` to `last` and return the
+/// day that was clicked.
+///
+/// Each column is a week starting on Monday. Hovering a day shows its value
+/// formatted with `format`.
+pub fn draw_calendar_heatmap(
+    ui: &mut Ui,
+    days: &std::collections::BTreeMap<NaiveDate, f64>,
+    first: NaiveDate,
+    last: NaiveDate,
+    format: impl Fn(f64) -> String,
+) -> Option<NaiveDate> {
+    const CELL: f32 = 12.0;
+    const GAP: f32 = 2.0;
+    const LABEL: f32 = 28.0;
+    let start = first - chrono::Duration::days(first.weekday().num_days_from_monday() as i64);
+    let weeks = ((last - start).num_days() / 7 + 1).max(1) as usize;
+    let size = Vec2::new(
+        LABEL + weeks as f32 * (CELL + GAP),
+        16.0 + 7.0 * (CELL + GAP),
+    );
+    let (rect, response) = ui.allocate_exact_size(size, Sense::click());
+    let painter = ui.painter();
+    let max = days
+        .range(first..=last)
+        .map(|(_, v)| *v)
+        .fold(0.0, f64::max);
+    let origin = rect.min + Vec2::new(LABEL, 16.0);
+    let font = FontId::proportional(10.0);
+
+    for (row, name) in [(0, "Mon"), (2, "Wed"), (4, "Fri")] {
+        painter.text(
+            Pos2::new(
+                rect.min.x,
+                origin.y + row as f32 * (CELL + GAP) + CELL / 2.0,
+            ),
+            Align2::LEFT_CENTER,
+            name,
+            font.clone(),
+            ui.visuals().text_color(),
+        );
+    }
+    let cell_rect = |d: NaiveDate| {
+        let offset = (d - start).num_days();
+        let min = origin
+            + Vec2::new(
+                (offset / 7) as f32 * (CELL + GAP),
+                (offset % 7) as f32 * (CELL + GAP),
+            );
+        egui::Rect::from_min_size(min, Vec2::splat(CELL))
+    };
+    let mut d = first;
+    while d <= last {
+        if d.day() == 1 || d == first {
+            painter.text(
+                Pos2::new(cell_rect(d).min.x, rect.min.y),
+                Align2::LEFT_TOP,
+                d.format("%b").to_string(),
+                font.clone(),
+                ui.visuals().text_color(),
+            );
+        }
+        let value = days.get(&d).copied().unwrap_or(0.0);
+        painter.rect_filled(cell_rect(d), 2.0, heat_color(value, max));
+        d += chrono::Duration::days(1);
+    }
+
+    let day_at = |pos: Pos2| {
+        let v = pos - origin;
+        if v.x < 0.0 || v.y < 0.0 {
+            return None;
+        }
+        let (col, row) = ((v.x / (CELL + GAP)) as i64, (v.y / (CELL + GAP)) as i64);
+        if row > 6 {
+            return None;
+        }
+        let d = start + chrono::Duration::days(col * 7 + row);
+        (first..=last).contains(&d).then_some(d)
+    };
+    let clicked = if response.clicked() {
+        response.interact_pointer_pos().and_then(day_at)
+    } else {
+        None
+    };
+    if let Some(d) = response.hover_pos().and_then(day_at) {
+        let value = days.get(&d).copied().unwrap_or(0.0);
+        response.on_hover_text_at_pointer(format!(
+            "{}\n{}",
+            d.format("%a %Y-%m-%d"),
+            format(value)
+        ));
+    }
+    clicked
+}
+
 /// Generate scatter points of weight versus repetitions for the selected
 /// `exercises`.
 ///
@@ -685,8 +1043,8 @@ fn training_volume_points(
             if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
                 let f = unit.factor() as f64;
                 let entry = map.entry(d).or_insert((0.0, 0.0));
-                entry.0 += e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64; // volume
-                entry.1 += e.weight.unwrap() as f64 * f; // total weight
+                entry.0 += e.volume().unwrap_or(0.0) as f64 * f; // volume
+                entry.1 += e.weight.unwrap_or(0.0) as f64 * f; // total weight
             }
         }
     }
@@ -730,8 +1088,8 @@ pub fn aggregated_volume_points(
                         let f = unit.factor() as f64;
                         let key = (d.iso_week().year(), d.iso_week().week());
                         let entry = map.entry(key).or_insert((0.0, 0.0));
-                        entry.0 += e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64;
-                        entry.1 += e.weight.unwrap() as f64 * f;
+                        entry.0 += e.volume().unwrap_or(0.0) as f64 * f;
+                        entry.1 += e.weight.unwrap_or(0.0) as f64 * f;
                     }
                 }
             }
@@ -761,8 +1119,8 @@ pub fn aggregated_volume_points(
                         let f = unit.factor() as f64;
                         let key = (d.year(), d.month());
                         let entry = map.entry(key).or_insert((0.0, 0.0));
-                        entry.0 += e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64;
-                        entry.1 += e.weight.unwrap() as f64 * f;
+                        entry.0 += e.volume().unwrap_or(0.0) as f64 * f;
+                        entry.1 += e.weight.unwrap_or(0.0) as f64 * f;
                     }
                 }
             }
@@ -1021,6 +1379,7 @@ pub fn average_rpe_line(
         out.push(LineWithMarker {
             line,
             points,
+            dates: Vec::new(),
             max_point,
             label,
             records: Vec::new(),
@@ -1029,11 +1388,12 @@ pub fn average_rpe_line(
     out
 }
 
-/// Create a line plot of training volume per primary body part.
+/// Create a line plot of training volume per body part.
 ///
-/// Each body part is plotted separately. Entries outside the optional date
-/// range or without a known body part are skipped. Volume can be aggregated
-/// daily, weekly or monthly.
+/// Each body part is plotted separately, receiving its share of each set's
+/// volume under `credit`. Entries outside the optional date range or without a
+/// known body part are skipped. Volume can be aggregated daily, weekly or
+/// monthly.
 pub fn body_part_volume_line(
     entries: &[WorkoutEntry],
     start: Option<NaiveDate>,
@@ -1042,32 +1402,31 @@ pub fn body_part_volume_line(
     unit: WeightUnit,
     agg: VolumeAggregation,
     ma_window: Option<usize>,
+    credit: MuscleCredit,
 ) -> Vec<Line> {
     use std::collections::BTreeMap;
     let mut map: BTreeMap<String, BTreeMap<NaiveDate, f64>> = BTreeMap::new();
     for e in entries {
-        if let (Some(part), Ok(d)) = (
-            body_part_for(&e.exercise),
-            NaiveDate::parse_from_str(&e.date, "%Y-%m-%d"),
-        ) {
-            if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                let f = unit.factor() as f64;
-                let key_date = match agg {
-                    VolumeAggregation::Daily => d,
-                    VolumeAggregation::Weekly => NaiveDate::from_isoywd_opt(
-                        d.iso_week().year(),
-                        d.iso_week().week(),
-                        chrono::Weekday::Mon,
-                    )
-                    .unwrap_or(d),
-                    VolumeAggregation::Monthly => {
-                        NaiveDate::from_ymd_opt(d.year(), d.month(), 1).unwrap_or(d)
-                    }
-                };
-                *map.entry(part.to_string())
-                    .or_default()
-                    .entry(key_date)
-                    .or_insert(0.0) += e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64;
+        if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d")
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            let f = unit.factor() as f64;
+            let key_date = match agg {
+                VolumeAggregation::Daily => d,
+                VolumeAggregation::Weekly => NaiveDate::from_isoywd_opt(
+                    d.iso_week().year(),
+                    d.iso_week().week(),
+                    chrono::Weekday::Mon,
+                )
+                .unwrap_or(d),
+                VolumeAggregation::Monthly => {
+                    NaiveDate::from_ymd_opt(d.year(), d.month(), 1).unwrap_or(d)
+                }
+            };
+            let volume = e.volume().unwrap_or(0.0) as f64 * f;
+            for (part, share) in credit.credits(&e.exercise) {
+                *map.entry(part).or_default().entry(key_date).or_insert(0.0) += volume * share;
             }
         }
     }
@@ -1098,10 +1457,11 @@ pub fn body_part_volume_line(
     lines
 }
 
-/// Generate trend lines of training volume per primary body part.
+/// Generate trend lines of training volume per body part.
 ///
-/// Volume is aggregated by the specified period and converted to the desired
-/// `unit`. Only entries within the optional `start` and `end` dates and with a
+/// Volume is credited to body parts as in [`body_part_volume_line`],
+/// aggregated by the specified period and converted to the desired `unit`.
+/// Only entries within the optional `start` and `end` dates and with a
 /// known body part are considered. Each returned [`Line`] represents the
 /// trend for a single body part.
 pub fn body_part_volume_trend(
@@ -1110,33 +1470,32 @@ pub fn body_part_volume_trend(
     end: Option<NaiveDate>,
     unit: WeightUnit,
     agg: VolumeAggregation,
+    credit: MuscleCredit,
 ) -> Vec<Line> {
     use std::collections::BTreeMap;
 
     let mut map: BTreeMap<String, BTreeMap<NaiveDate, f64>> = BTreeMap::new();
     for e in entries {
-        if let (Some(part), Ok(d)) = (
-            body_part_for(&e.exercise),
-            NaiveDate::parse_from_str(&e.date, "%Y-%m-%d"),
-        ) {
-            if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                let f = unit.factor() as f64;
-                let key_date = match agg {
-                    VolumeAggregation::Daily => d,
-                    VolumeAggregation::Weekly => NaiveDate::from_isoywd_opt(
-                        d.iso_week().year(),
-                        d.iso_week().week(),
-                        chrono::Weekday::Mon,
-                    )
-                    .unwrap_or(d),
-                    VolumeAggregation::Monthly => {
-                        NaiveDate::from_ymd_opt(d.year(), d.month(), 1).unwrap_or(d)
-                    }
-                };
-                *map.entry(part.to_string())
-                    .or_default()
-                    .entry(key_date)
-                    .or_insert(0.0) += e.weight.unwrap() as f64 * f * e.reps.unwrap() as f64;
+        if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d")
+            && start.is_none_or(|s| d >= s)
+            && end.is_none_or(|e2| d <= e2)
+        {
+            let f = unit.factor() as f64;
+            let key_date = match agg {
+                VolumeAggregation::Daily => d,
+                VolumeAggregation::Weekly => NaiveDate::from_isoywd_opt(
+                    d.iso_week().year(),
+                    d.iso_week().week(),
+                    chrono::Weekday::Mon,
+                )
+                .unwrap_or(d),
+                VolumeAggregation::Monthly => {
+                    NaiveDate::from_ymd_opt(d.year(), d.month(), 1).unwrap_or(d)
+                }
+            };
+            let volume = e.volume().unwrap_or(0.0) as f64 * f;
+            for (part, share) in credit.credits(&e.exercise) {
+                *map.entry(part).or_default().entry(key_date).or_insert(0.0) += volume * share;
             }
         }
     }
@@ -1186,10 +1545,299 @@ pub fn exercise_volume_line(
     lines
 }
 
+/// Create one line of training volume per exercise category.
+///
+/// Categories come from [`categories_for`]; an exercise listed under several
+/// categories counts towards each. Volume is aggregated according to `agg`.
+pub fn category_volume_line(
+    entries: &[WorkoutEntry],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    x_axis: XAxis,
+    unit: WeightUnit,
+    agg: VolumeAggregation,
+    ma_window: Option<usize>,
+) -> Vec<Line> {
+    use std::collections::BTreeMap;
+    let mut groups: BTreeMap<String, Vec<WorkoutEntry>> = BTreeMap::new();
+    for e in entries {
+        for c in categories_for(&e.exercise) {
+            groups.entry(c).or_default().push(e.clone());
+        }
+    }
+    let mut lines = Vec::new();
+    for (category, group) in groups {
+        let points = aggregated_volume_points(&group, start, end, x_axis, YAxis::Volume, unit, agg);
+        if points.is_empty() {
+            continue;
+        }
+        lines.push(Line::new(PlotPoints::from(points.clone())).name(&category));
+        if let Some(w) = ma_window.filter(|w| *w > 1)
+            && points.len() > 1
+        {
+            let ma_pts = moving_average_points(&points, w);
+            lines.push(Line::new(PlotPoints::from(ma_pts)).name(format!("{category} MA")));
+        }
+    }
+    lines
+}
+
+/// Parse an entry's date and check it against the optional range.
+fn date_in_range(
+    e: &WorkoutEntry,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&e.date, "%Y-%m-%d")
+        .ok()
+        .filter(|d| start.is_none_or(|s| *d >= s) && end.is_none_or(|e2| *d <= e2))
+}
+
+/// Parse a session's date and check it against the optional range.
+fn workout_in_range(
+    w: &Workout,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(&w.date, "%Y-%m-%d")
+        .ok()
+        .filter(|d| start.is_none_or(|s| *d >= s) && end.is_none_or(|e2| *d <= e2))
+}
+
+/// Plot `metric` for every session in the range, oldest first, with an
+/// optional moving average.
+///
+/// Sessions without an end time are skipped. Density is converted using
+/// `unit`.
+pub fn session_metric_line(
+    workouts: &[Workout],
+    metric: SessionMetric,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    x_axis: XAxis,
+    unit: WeightUnit,
+    ma_window: Option<usize>,
+) -> Vec<Line> {
+    let f = unit.factor() as f64;
+    let mut sessions: Vec<(NaiveDate, f64)> = workouts
+        .iter()
+        .filter_map(|w| {
+            let d = workout_in_range(w, start, end)?;
+            let v = match metric {
+                SessionMetric::Duration => w.minutes()?,
+                SessionMetric::Density => w.density()? * f,
+                SessionMetric::SetsPerHour => w.sets_per_hour()?,
+            };
+            Some((d, v))
+        })
+        .collect();
+    sessions.sort_by_key(|(d, _)| *d);
+    let points: Vec<[f64; 2]> = sessions
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (d, v))| match x_axis {
+            XAxis::Date => [d.num_days_from_ce() as f64, v],
+            XAxis::WorkoutIndex => [idx as f64, v],
+        })
+        .collect();
+    let name = match metric {
+        SessionMetric::Duration => "Duration",
+        SessionMetric::Density => "Density",
+        SessionMetric::SetsPerHour => "Sets per Hour",
+    };
+    let mut lines = vec![Line::new(PlotPoints::from(points.clone())).name(name)];
+    if let Some(w) = ma_window.filter(|w| *w > 1)
+        && points.len() > 1
+    {
+        lines.push(
+            Line::new(PlotPoints::from(moving_average_points(&points, w)))
+                .name(format!("{name} MA")),
+        );
+    }
+    lines
+}
+
+/// Generate one line per exercise of pace in minutes per mile.
+///
+/// Only sets with both a distance and a duration contribute a point.
+pub fn pace_line(
+    entries: &[WorkoutEntry],
+    exercises: &[String],
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    x_axis: XAxis,
+) -> Vec<Line> {
+    let mut lines = Vec::new();
+    for exercise in exercises {
+        let ex_norm = normalize_exercise(exercise);
+        let mut sets: Vec<(NaiveDate, f64)> = entries
+            .iter()
+            .filter(|e| normalize_exercise(&e.exercise) == ex_norm)
+            .filter_map(|e| {
+                let d = date_in_range(e, start, end)?;
+                let pace = e.duration()? as f64 / 60.0 / e.distance()? as f64;
+                Some((d, pace))
+            })
+            .collect();
+        if sets.is_empty() {
+            continue;
+        }
+        sets.sort_by_key(|(d, _)| *d);
+        let points: Vec<[f64; 2]> = sets
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (d, pace))| match x_axis {
+                XAxis::Date => [d.num_days_from_ce() as f64, pace],
+                XAxis::WorkoutIndex => [idx as f64, pace],
+            })
+            .collect();
+        lines.push(Line::new(PlotPoints::from(points)).name(exercise));
+    }
+    lines
+}
+
+/// Create a bar chart of total distance in miles for each ISO week.
+///
+/// Bars are placed on the Monday of each week. When `exercise` is `Some`,
+/// only that exercise is counted.
+pub fn distance_per_week_bar(
+    entries: &[WorkoutEntry],
+    exercise: Option<&str>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> BarChart {
+    let mut map: std::collections::BTreeMap<NaiveDate, f64> = std::collections::BTreeMap::new();
+    for e in entries {
+        if exercise.is_none_or(|ex| normalize_exercise(ex) == normalize_exercise(&e.exercise))
+            && let Some(d) = date_in_range(e, start, end)
+            && let Some(dist) = e.distance()
+        {
+            let week = d - chrono::Duration::days(d.weekday().num_days_from_monday() as i64);
+            *map.entry(week).or_insert(0.0) += dist as f64;
+        }
+    }
+    let bars: Vec<Bar> = map
+        .into_iter()
+        .map(|(week, dist)| Bar::new(week.num_days_from_ce() as f64, dist).width(5.0))
+        .collect();
+    BarChart::new(bars).name("Distance")
+}
+
+/// Create a bar chart of total hold time in seconds per day.
+///
+/// Timed sets without a distance, such as planks or dead hangs, are summed.
+/// When `exercise` is `Some`, only that exercise is counted.
+pub fn hold_time_bar(
+    entries: &[WorkoutEntry],
+    exercise: Option<&str>,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+) -> BarChart {
+    let mut map: std::collections::BTreeMap<NaiveDate, f64> = std::collections::BTreeMap::new();
+    for e in entries {
+        if exercise.is_none_or(|ex| normalize_exercise(ex) == normalize_exercise(&e.exercise))
+            && e.distance().is_none()
+            && let Some(d) = date_in_range(e, start, end)
+            && let Some(secs) = e.duration()
+        {
+            *map.entry(d).or_insert(0.0) += secs as f64;
+        }
+    }
+    let bars: Vec<Bar> = map
+        .into_iter()
+        .map(|(d, secs)| Bar::new(d.num_days_from_ce() as f64, secs))
+        .collect();
+    BarChart::new(bars).name("Hold Time")
+}
+
+/// Generate one line per exercise of the bodyweight-adjusted load per set.
+///
+/// Only bodyweight and assisted exercises are plotted. `body_weight` is in
+/// lbs and the result is converted using `unit`.
+pub fn bodyweight_load_line(
+    entries: &[WorkoutEntry],
+    exercises: &[String],
+    body_weight: f32,
+    start: Option<NaiveDate>,
+    end: Option<NaiveDate>,
+    x_axis: XAxis,
+    unit: WeightUnit,
+) -> Vec<Line> {
+    let f = unit.factor() as f64;
+    let mut lines = Vec::new();
+    for exercise in exercises {
+        let ex_norm = normalize_exercise(exercise);
+        let mut sets: Vec<(NaiveDate, f64)> = entries
+            .iter()
+            .filter(|e| normalize_exercise(&e.exercise) == ex_norm && e.is_bodyweight())
+            .filter_map(|e| {
+                let d = date_in_range(e, start, end)?;
+                Some((d, e.bodyweight_load(body_weight)? as f64 * f))
+            })
+            .collect();
+        if sets.is_empty() {
+            continue;
+        }
+        sets.sort_by_key(|(d, _)| *d);
+        let points: Vec<[f64; 2]> = sets
+            .into_iter()
+            .enumerate()
+            .map(|(idx, (d, load))| match x_axis {
+                XAxis::Date => [d.num_days_from_ce() as f64, load],
+                XAxis::WorkoutIndex => [idx as f64, load],
+            })
+            .collect();
+        lines.push(Line::new(PlotPoints::from(points)).name(exercise));
+    }
+    lines
+}
+
+/// Colour used to flag a muscle's weekly dose.
+pub fn dose_color(status: DoseStatus) -> Color32 {
+    match status {
+        DoseStatus::Under => Color32::from_rgb(230, 180, 40),
+        DoseStatus::Productive => Color32::from_rgb(60, 170, 80),
+        DoseStatus::High => Color32::from_rgb(70, 130, 220),
+        DoseStatus::Over => Color32::from_rgb(220, 60, 60),
+    }
+}
+
+/// Bar chart of one week's hard sets per muscle with landmark markers.
+///
+/// Each bar is coloured by its [`DoseStatus`]. The returned points mark the
+/// MEV and MRV of every muscle at the bar's position.
+pub fn hard_sets_chart(muscles: &[(String, f64, VolumeLandmarks)]) -> (BarChart, Points, Points) {
+    let mut bars = Vec::new();
+    let mut mev = Vec::new();
+    let mut mrv = Vec::new();
+    for (idx, (muscle, sets, landmarks)) in muscles.iter().enumerate() {
+        let x = idx as f64;
+        bars.push(
+            Bar::new(x, *sets)
+                .name(muscle)
+                .fill(dose_color(landmarks.status(*sets))),
+        );
+        mev.push([x, landmarks.mev as f64]);
+        mrv.push([x, landmarks.mrv as f64]);
+    }
+    (
+        BarChart::new(bars).name("Hard Sets"),
+        Points::new(mev)
+            .name("MEV")
+            .shape(egui_plot::MarkerShape::Down)
+            .radius(5.0),
+        Points::new(mrv)
+            .name("MRV")
+            .shape(egui_plot::MarkerShape::Up)
+            .radius(5.0),
+    )
+}
+
 /// Return a sorted list of unique exercises found in the data.
 ///
-/// Only entries whose dates fall inside the optional range are inspected. The
-/// resulting vector is sorted alphabetically.
+/// Aliases are reported under their canonical name. Only entries whose dates
+/// fall inside the optional range are inspected. The resulting vector is
+/// sorted alphabetically.
 pub fn unique_exercises(
     entries: &[WorkoutEntry],
     start: Option<NaiveDate>,
@@ -1199,7 +1847,7 @@ pub fn unique_exercises(
     for e in entries {
         if let Ok(d) = NaiveDate::parse_from_str(&e.date, "%Y-%m-%d") {
             if start.map_or(true, |s| d >= s) && end.map_or(true, |e2| d <= e2) {
-                set.insert(e.exercise.clone());
+                set.insert(exercise_mapping::canonical_name(&e.exercise));
             }
         }
     }
@@ -1210,6 +1858,7 @@ pub fn unique_exercises(
 mod tests {
     use super::*;
     use crate::RawWorkoutRow;
+    use crate::test_util::set_entry;
     use egui_plot::{PlotGeometry, PlotItem};
 
     fn sample_entries() -> Vec<WorkoutEntry> {
@@ -1219,6 +1868,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     rpe: Some(8.0),
                     ..RawWorkoutRow::default()
@@ -1229,6 +1879,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(80.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     rpe: Some(7.0),
                     ..RawWorkoutRow::default()
@@ -1239,6 +1890,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(105.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow {
                     rpe: Some(9.0),
                     ..RawWorkoutRow::default()
@@ -1290,6 +1942,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight,
                 reps,
+                start: None,
                 raw: RawWorkoutRow::default(),
             }
         }
@@ -1315,6 +1968,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(weight),
                 reps: Some(reps),
+                start: None,
                 raw: RawWorkoutRow::default(),
             }
         }
@@ -1335,6 +1989,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(weight),
                 reps: Some(reps),
+                start: None,
                 raw: RawWorkoutRow::default(),
             }
         }
@@ -1559,6 +2214,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1566,6 +2222,7 @@ mod tests {
                 exercise: "Squat".into(),
                 weight: Some(80.0),
                 reps: Some(10),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -1599,6 +2256,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1606,6 +2264,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(90.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1613,6 +2272,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(110.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -1642,6 +2302,7 @@ mod tests {
                 exercise: "Deadlift".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1649,6 +2310,7 @@ mod tests {
                 exercise: "Deadlift".into(),
                 weight: Some(110.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1656,13 +2318,14 @@ mod tests {
                 exercise: "Deadlift".into(),
                 weight: Some(105.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
         let res = estimated_1rm_line(
             &entries,
             &["Deadlift".to_string()],
-            OneRmFormula::Epley,
+            OneRmFormula::Epley.into(),
             None,
             None,
             XAxis::WorkoutIndex,
@@ -1684,6 +2347,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(100.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
             WorkoutEntry {
@@ -1691,6 +2355,7 @@ mod tests {
                 exercise: "Bench".into(),
                 weight: Some(110.0),
                 reps: Some(5),
+                start: None,
                 raw: RawWorkoutRow::default(),
             },
         ];
@@ -1764,6 +2429,7 @@ mod tests {
             WeightUnit::Lbs,
             VolumeAggregation::Weekly,
             None,
+            MuscleCredit::PrimaryOnly,
         );
         let d = NaiveDate::parse_from_str("2024-01-01", "%Y-%m-%d").unwrap();
         let expected = vec![
@@ -1785,6 +2451,7 @@ mod tests {
             WeightUnit::Lbs,
             VolumeAggregation::Monthly,
             None,
+            MuscleCredit::PrimaryOnly,
         );
         let d = NaiveDate::parse_from_str("2024-01-01", "%Y-%m-%d").unwrap();
         let expected = vec![
@@ -1796,6 +2463,83 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_body_part_volume_line_weighted() {
+        exercise_mapping::set(
+            "Credit Test Row".into(),
+            exercise_mapping::MuscleMapping {
+                primary: "Back".into(),
+                secondary: vec!["Biceps".into()],
+                category: String::new(),
+            },
+        );
+        let mut entries = sample_entries();
+        entries.truncate(1);
+        entries[0].exercise = "Credit Test Row".into();
+        let lines = body_part_volume_line(
+            &entries,
+            None,
+            None,
+            XAxis::Date,
+            WeightUnit::Lbs,
+            VolumeAggregation::Daily,
+            None,
+            MuscleCredit::Weighted {
+                primary: 1.0,
+                secondary: 0.5,
+            },
+        );
+        exercise_mapping::remove("Credit Test Row");
+
+        let d = NaiveDate::parse_from_str("2024-01-01", "%Y-%m-%d").unwrap();
+        let x = d.num_days_from_ce() as f64;
+        let points: Vec<Vec<[f64; 2]>> = lines.into_iter().map(line_points).collect();
+        // Back gets the whole 500 lb set, Biceps half of it.
+        assert_eq!(points, vec![vec![[x, 500.0]], vec![[x, 250.0]]]);
+    }
+
+    #[test]
+    fn test_category_volume_line() {
+        exercise_mapping::set(
+            "Category Test Bench".into(),
+            exercise_mapping::MuscleMapping {
+                primary: "Chest".into(),
+                secondary: Vec::new(),
+                category: "Horizontal Push".into(),
+            },
+        );
+        exercise_mapping::set(
+            "Category Test Incline".into(),
+            exercise_mapping::MuscleMapping {
+                primary: "Chest".into(),
+                secondary: Vec::new(),
+                category: "Horizontal Push".into(),
+            },
+        );
+        let mut entries = sample_entries();
+        entries.truncate(2);
+        entries[0].exercise = "Category Test Bench".into();
+        entries[1].exercise = "Category Test Incline".into();
+        let lines = category_volume_line(
+            &entries,
+            None,
+            None,
+            XAxis::Date,
+            WeightUnit::Lbs,
+            VolumeAggregation::Daily,
+            None,
+        );
+        exercise_mapping::remove("Category Test Bench");
+        exercise_mapping::remove("Category Test Incline");
+
+        assert_eq!(lines.len(), 1);
+        let d = NaiveDate::parse_from_str("2024-01-01", "%Y-%m-%d").unwrap();
+        assert_eq!(
+            line_points(lines.into_iter().next().unwrap()),
+            vec![[d.num_days_from_ce() as f64, 900.0]]
+        );
+    }
+
     #[test]
     fn test_body_part_volume_trend() {
         exercise_mapping::load();
@@ -1805,6 +2549,7 @@ mod tests {
             None,
             WeightUnit::Lbs,
             VolumeAggregation::Daily,
+            MuscleCredit::PrimaryOnly,
         );
         // Only the "Quads" body part has two data points, so only one trend line is returned
         assert_eq!(lines.len(), 1);
@@ -1877,6 +2622,105 @@ mod tests {
         assert!((bounds.max()[1] - 3.0).abs() < 1e-6);
     }
 
+    fn cardio_entries() -> Vec<WorkoutEntry> {
+        let entry = |date: &str, exercise: &str, raw: RawWorkoutRow| WorkoutEntry {
+            date: date.into(),
+            exercise: exercise.into(),
+            weight: None,
+            reps: raw.reps,
+            start: None,
+            raw,
+        };
+        vec![
+            entry(
+                "2024-01-01",
+                "Running",
+                RawWorkoutRow {
+                    distance_miles: Some(2.0),
+                    duration_seconds: Some(1200.0),
+                    ..RawWorkoutRow::default()
+                },
+            ),
+            entry(
+                "2024-01-03",
+                "Running",
+                RawWorkoutRow {
+                    distance_miles: Some(3.0),
+                    duration_seconds: Some(1620.0),
+                    ..RawWorkoutRow::default()
+                },
+            ),
+            entry(
+                "2024-01-03",
+                "Plank",
+                RawWorkoutRow {
+                    duration_seconds: Some(60.0),
+                    ..RawWorkoutRow::default()
+                },
+            ),
+            entry(
+                "2024-01-03",
+                "Plank",
+                RawWorkoutRow {
+                    duration_seconds: Some(45.0),
+                    ..RawWorkoutRow::default()
+                },
+            ),
+            entry(
+                "2024-01-03",
+                "Pull Up",
+                RawWorkoutRow {
+                    reps: Some(8),
+                    ..RawWorkoutRow::default()
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_pace_line() {
+        let lines = pace_line(
+            &cardio_entries(),
+            &["Running".to_string(), "Plank".to_string()],
+            None,
+            None,
+            XAxis::WorkoutIndex,
+        );
+        assert_eq!(lines.len(), 1);
+        let pts = line_points(lines.into_iter().next().unwrap());
+        assert_eq!(pts, vec![[0.0, 10.0], [1.0, 9.0]]);
+    }
+
+    #[test]
+    fn test_distance_and_hold_time_bars() {
+        let entries = cardio_entries();
+        let distance = distance_per_week_bar(&entries, None, None, None);
+        // Both runs fall in the same ISO week.
+        assert!((PlotItem::bounds(&distance).max()[1] - 5.0).abs() < 1e-6);
+
+        let hold = hold_time_bar(&entries, None, None, None);
+        // Runs have a distance and are not counted as holds.
+        assert!((PlotItem::bounds(&hold).max()[1] - 105.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_bodyweight_load_line() {
+        let lines = bodyweight_load_line(
+            &cardio_entries(),
+            &["Pull Up".to_string(), "Running".to_string()],
+            180.0,
+            None,
+            None,
+            XAxis::WorkoutIndex,
+            WeightUnit::Lbs,
+        );
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            line_points(lines.into_iter().next().unwrap()),
+            vec![[0.0, 180.0]]
+        );
+    }
+
     #[test]
     fn test_weight_histogram_counts() {
         let entries = sample_entries();
@@ -1893,19 +2737,48 @@ mod tests {
         assert!((bounds.max()[1] - 2.0).abs() < 1e-6);
     }
 
+    #[test]
+    fn test_duration_histogram_counts_sessions() {
+        let session = |start: &str, end: &str| {
+            let mut e = set_entry("Squat", start, 100.0, 5);
+            e.raw.end_time = Some(end.into());
+            e
+        };
+        let entries = vec![
+            session("01 Jan 2024, 07:00", "01 Jan 2024, 08:00"),
+            session("01 Jan 2024, 07:00", "01 Jan 2024, 08:00"),
+            session("01 Jan 2024, 17:00", "01 Jan 2024, 17:40"),
+            session("01 Jan 2024, 19:00", "01 Jan 2024, 19:20"),
+        ];
+        let chart = histogram(
+            &entries,
+            HistogramMetric::Duration { bin: 30.0 },
+            None,
+            None,
+            WeightUnit::Lbs,
+        );
+        let bounds = PlotItem::bounds(&chart);
+        // The two sets at 07:00 form one session, so every bin holds one.
+        assert!((bounds.max()[1] - 1.0).abs() < 1e-6);
+        assert!((bounds.min()[0] - 0.0).abs() < 1e-6);
+        assert!((bounds.max()[0] - 90.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_body_part_distribution_counts() {
         use crate::analysis::aggregate_sets_by_body_part;
         use std::collections::HashMap;
         exercise_mapping::load();
         let entries = sample_entries();
-        let (chart, _) = body_part_distribution(&entries, None, None);
+        let (chart, _) = body_part_distribution(&entries, None, None, MuscleCredit::PrimaryOnly);
         let bounds = PlotItem::bounds(&chart);
 
-        let expected =
-            HashMap::from([("Chest".to_string(), 1usize), ("Quads".to_string(), 2usize)]);
+        let expected = HashMap::from([("Chest".to_string(), 1.0), ("Quads".to_string(), 2.0)]);
 
-        assert_eq!(aggregate_sets_by_body_part(&entries, None, None), expected);
+        assert_eq!(
+            aggregate_sets_by_body_part(&entries, None, None, MuscleCredit::PrimaryOnly),
+            expected
+        );
         assert!((bounds.max()[1] - 2.0).abs() < 1e-6);
     }
 
@@ -1913,7 +2786,7 @@ mod tests {
     fn test_body_part_pie_counts() {
         exercise_mapping::load();
         let entries = sample_entries();
-        let pie = body_part_pie(&entries, None, None);
+        let pie = body_part_pie(&entries, None, None, MuscleCredit::PrimaryOnly);
         let mut map = std::collections::HashMap::new();
         for s in &pie.slices {
             map.insert(s.label.clone(), s.value as usize);
@@ -1931,7 +2804,7 @@ mod tests {
         let res_e = estimated_1rm_line(
             &sample_entries(),
             &["Squat".to_string()],
-            OneRmFormula::Epley,
+            OneRmFormula::Epley.into(),
             None,
             None,
             XAxis::Date,
@@ -1952,7 +2825,7 @@ mod tests {
         let res_b = estimated_1rm_line(
             &sample_entries(),
             &["Squat".to_string()],
-            OneRmFormula::Brzycki,
+            OneRmFormula::Brzycki.into(),
             Some(d3),
             None,
             XAxis::Date,
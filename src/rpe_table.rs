@@ -0,0 +1,130 @@
+//! Editable reps × RPE percentage table used by [`OneRmFormula::Rpe`], stored
+//! as `rpe_table.json` next to the muscle mapping.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+
+use dirs_next as dirs;
+
+use crate::plotting::OneRmFormula;
+
+/// Percentages of 1RM from the RTS chart, one step per half RPE or rep:
+/// a single at RPE 10 is 100%, a double at RPE 10 or a single at RPE 9 is
+/// 95.5%, and so on.
+const RTS_STEPS: [f32; 30] = [
+    100.0, 97.8, 95.5, 93.9, 92.2, 90.7, 89.2, 87.8, 86.3, 85.0, 83.7, 82.4, 81.1, 79.9, 78.6,
+    77.4, 76.2, 75.1, 73.9, 72.3, 70.7, 69.4, 68.0, 66.7, 65.3, 64.0, 62.6, 61.3, 59.9, 58.6,
+];
+
+/// Percentage of 1RM for each rep count and RPE.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RpeTable {
+    /// RPE of each column, ascending.
+    pub rpes: Vec<f32>,
+    /// One row per rep count starting at a single, with a percentage for each
+    /// column in `rpes`.
+    pub rows: Vec<Vec<f32>>,
+    /// Rep formula used for sets without an RPE or outside the table.
+    pub fallback: OneRmFormula,
+}
+
+impl Default for RpeTable {
+    /// The RTS chart for 1-12 reps at RPE 6.5-10 with Epley as fallback.
+    fn default() -> Self {
+        let rpes: Vec<f32> = (0..8).map(|i| 6.5 + i as f32 * 0.5).collect();
+        let rows = (0..12)
+            .map(|rep| {
+                rpes.iter()
+                    .map(|rpe| RTS_STEPS[rep * 2 + ((10.0 - rpe) * 2.0) as usize])
+                    .collect()
+            })
+            .collect();
+        Self {
+            rpes,
+            rows,
+            fallback: OneRmFormula::Epley,
+        }
+    }
+}
+
+impl RpeTable {
+    /// Percentage of 1RM for `reps` at `rpe`, with the RPE rounded to the
+    /// nearest column. `None` when either lies outside the table.
+    pub fn percentage(&self, reps: u32, rpe: f32) -> Option<f32> {
+        let row = self.rows.get((reps as usize).checked_sub(1)?)?;
+        let col = self.rpes.iter().position(|r| (r - rpe).abs() <= 0.25)?;
+        row.get(col).copied().filter(|p| *p > 0.0)
+    }
+}
+
+static TABLE: Lazy<Mutex<RpeTable>> = Lazy::new(|| Mutex::new(RpeTable::default()));
+
+const FILE: &str = "rpe_table.json";
+
+fn path() -> Option<std::path::PathBuf> {
+    dirs::config_dir().map(|p| p.join(FILE))
+}
+
+/// Load the table, keeping the RTS default if no file has been saved.
+pub fn load() {
+    if let Some(p) = path()
+        && let Ok(data) = std::fs::read_to_string(&p)
+    {
+        match serde_json::from_str::<RpeTable>(&data) {
+            Ok(table) => set(table),
+            Err(e) => log::error!("Failed to parse RPE table: {e}"),
+        }
+    }
+}
+
+pub fn save() {
+    if let Some(p) = path() {
+        if let Some(parent) = p.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        if let Ok(data) = serde_json::to_string_pretty(&*TABLE.lock().unwrap()) {
+            let _ = std::fs::write(p, data);
+        }
+    }
+}
+
+pub fn get() -> RpeTable {
+    TABLE.lock().unwrap().clone()
+}
+
+/// Replace the table. A fallback of [`OneRmFormula::Rpe`] would recurse, so it
+/// is replaced with Epley.
+pub fn set(mut table: RpeTable) {
+    if table.fallback == OneRmFormula::Rpe {
+        table.fallback = OneRmFormula::Epley;
+    }
+    *TABLE.lock().unwrap() = table;
+}
+
+pub fn percentage(reps: u32, rpe: f32) -> Option<f32> {
+    TABLE.lock().unwrap().percentage(reps, rpe)
+}
+
+pub fn fallback() -> OneRmFormula {
+    TABLE.lock().unwrap().fallback
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_matches_rts_chart() {
+        let t = RpeTable::default();
+        assert_eq!(t.percentage(1, 10.0), Some(100.0));
+        assert_eq!(t.percentage(5, 8.0), Some(81.1));
+        assert_eq!(t.percentage(3, 7.5), Some(85.0));
+        assert_eq!(t.percentage(12, 6.5), Some(58.6));
+        // RPE 8.2 rounds to the RPE 8 column.
+        assert_eq!(t.percentage(5, 8.2), Some(81.1));
+        assert_eq!(t.percentage(13, 9.0), None);
+        assert_eq!(t.percentage(5, 5.0), None);
+        assert_eq!(t.percentage(0, 9.0), None);
+    }
+}
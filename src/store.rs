@@ -0,0 +1,576 @@
+//! Local workout store kept in the config directory next to the settings file.
+//!
+//! The store is an append-only JSON-lines file. Each line holds every set of
+//! one workout, keyed by its Hevy workout id. A later line for the same id
+//! replaces the earlier one, as does a CSV workout without an id for one with
+//! the same start time, so CSV imports and API syncs can both merge into the
+//! store by appending. Lines that fail to parse are moved to a `.corrupt`
+//! sidecar file rather than dropped.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+use dirs_next as dirs;
+
+use crate::WorkoutEntry;
+
+const FILE: &str = "multi_hevy_workouts.jsonl";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredWorkout {
+    id: String,
+    #[serde(default)]
+    entries: Vec<WorkoutEntry>,
+}
+
+pub fn path() -> Option<PathBuf> {
+    dirs::config_dir().map(|p| p.join(FILE))
+}
+
+/// Key identifying the workout an entry belongs to.
+///
+/// Synced entries carry their Hevy workout id. CSV exports do not, so the
+/// parsed start time is used instead (see [`start_key`]).
+pub fn workout_key(e: &WorkoutEntry) -> String {
+    e.raw.workout_id.clone().unwrap_or_else(|| start_key(e))
+}
+
+/// The workout's parsed start time to the minute, the precision of a Hevy
+/// CSV export, so CSV and API copies of one workout share it. Falls back to
+/// the raw timestamp for entries stored before start times were parsed.
+pub fn start_key(e: &WorkoutEntry) -> String {
+    match e.start {
+        Some(start) => start.format("%Y-%m-%d %H:%M").to_string(),
+        None => e.raw.start_time.clone(),
+    }
+}
+
+/// Group entries by workout key, keeping the order workouts first appear in.
+fn group_by_workout(entries: &[WorkoutEntry]) -> Vec<(String, Vec<WorkoutEntry>)> {
+    let mut groups: Vec<(String, Vec<WorkoutEntry>)> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for e in entries {
+        let key = workout_key(e);
+        match index.get(&key) {
+            Some(&i) => groups[i].1.push(e.clone()),
+            None => {
+                index.insert(key.clone(), groups.len());
+                groups.push((key, vec![e.clone()]));
+            }
+        }
+    }
+    groups
+}
+
+/// Contents of the store file as read by [`read`].
+#[derive(Debug, Default)]
+struct StoreContents {
+    entries: Vec<WorkoutEntry>,
+    /// Some line was superseded by a later one or stored under an old key.
+    stale: bool,
+    /// Lines that could not be parsed, kept verbatim.
+    corrupt: Vec<String>,
+}
+
+/// Read the store, returning the live entries, whether any line was
+/// superseded, and the lines that failed to parse.
+///
+/// A later line replaces earlier ones with the same key and, as in
+/// [`merge_into`], a workout without a Hevy id replaces or is replaced by a
+/// copy with the same start time.
+fn read(path: &Path) -> io::Result<StoreContents> {
+    let mut contents = StoreContents::default();
+    if !path.exists() {
+        return Ok(contents);
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let mut order: Vec<String> = Vec::new();
+    let mut workouts: HashMap<String, Vec<WorkoutEntry>> = HashMap::new();
+    // Stored keys by start key, with whether the workout has a Hevy id.
+    let mut by_start: HashMap<String, Vec<(String, bool)>> = HashMap::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let rec: StoredWorkout = match serde_json::from_str(&line) {
+            Ok(r) => r,
+            Err(e) => {
+                log::warn!("Invalid line {} in workout store: {e}", i + 1);
+                contents.corrupt.push(line);
+                continue;
+            }
+        };
+        let id = rec
+            .entries
+            .first()
+            .map(workout_key)
+            .unwrap_or(rec.id.clone());
+        if id != rec.id {
+            contents.stale = true;
+        }
+        let has_id = rec
+            .entries
+            .first()
+            .is_some_and(|e| e.raw.workout_id.is_some());
+        let start = rec.entries.first().map(start_key).unwrap_or_default();
+        let same_start = by_start.entry(start).or_default();
+        let replaced: Vec<String> = same_start
+            .iter()
+            .filter(|(key, other_has_id)| *key == id || !has_id || !*other_has_id)
+            .map(|(key, _)| key.clone())
+            .chain(workouts.contains_key(&id).then(|| id.clone()))
+            .collect();
+        if !replaced.is_empty() {
+            contents.stale = true;
+            same_start.retain(|(key, _)| !replaced.contains(key));
+            order.retain(|o| !replaced.contains(o));
+            for key in &replaced {
+                workouts.remove(key);
+            }
+        }
+        same_start.push((id.clone(), has_id));
+        order.push(id.clone());
+        workouts.insert(id, rec.entries);
+    }
+    contents.entries = order
+        .into_iter()
+        .filter_map(|id| workouts.remove(&id))
+        .flatten()
+        .collect();
+    Ok(contents)
+}
+
+/// Sidecar file that receives store lines which failed to parse.
+fn corrupt_path(path: &Path) -> PathBuf {
+    path.with_extension("jsonl.corrupt")
+}
+
+/// Append unparseable store lines to the `.corrupt` sidecar next to `path`.
+fn save_corrupt(path: &Path, lines: &[String]) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(corrupt_path(path))?;
+    for line in lines {
+        writeln!(file, "{line}")?;
+    }
+    file.flush()
+}
+
+/// Append the workouts contained in `entries` to the store at `path`,
+/// replacing any stored workout with the same key.
+pub fn append_to(path: &Path, entries: &[WorkoutEntry]) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for (id, entries) in group_by_workout(entries) {
+        let rec = StoredWorkout { id, entries };
+        let line = serde_json::to_string(&rec).map_err(io::Error::other)?;
+        writeln!(file, "{line}")?;
+    }
+    file.flush()
+}
+
+/// Rewrite the store at `path` so it holds exactly `entries`.
+pub fn replace_at(path: &Path, entries: &[WorkoutEntry]) -> io::Result<()> {
+    let tmp = path.with_extension("jsonl.tmp");
+    if tmp.exists() {
+        std::fs::remove_file(&tmp)?;
+    }
+    append_to(&tmp, entries)?;
+    std::fs::rename(tmp, path)
+}
+
+/// Load the local store, compacting it when superseded lines have piled up.
+pub fn load() -> Vec<WorkoutEntry> {
+    match path() {
+        Some(p) => load_at(&p),
+        None => Vec::new(),
+    }
+}
+
+/// Load the store at `path`.
+///
+/// Unparseable lines are moved to the `.corrupt` sidecar before the store is
+/// rewritten. If they cannot be saved there the store is left untouched.
+fn load_at(path: &Path) -> Vec<WorkoutEntry> {
+    let contents = match read(path) {
+        Ok(c) => c,
+        Err(e) => {
+            log::error!("Failed to read workout store: {e}");
+            return Vec::new();
+        }
+    };
+    let mut compact = contents.stale;
+    if !contents.corrupt.is_empty() {
+        match save_corrupt(path, &contents.corrupt) {
+            Ok(()) => {
+                log::warn!(
+                    "Moved {} invalid workout store lines to {}",
+                    contents.corrupt.len(),
+                    corrupt_path(path).display()
+                );
+                compact = true;
+            }
+            Err(e) => {
+                log::error!("Failed to save invalid workout store lines: {e}");
+                compact = false;
+            }
+        }
+    }
+    if compact && let Err(e) = replace_at(path, &contents.entries) {
+        log::warn!("Failed to compact workout store: {e}");
+    }
+    contents.entries
+}
+
+/// Merge entries into the local store.
+pub fn append(entries: &[WorkoutEntry]) {
+    if let Some(p) = path()
+        && let Err(e) = append_to(&p, entries)
+    {
+        log::error!("Failed to update workout store: {e}");
+    }
+}
+
+/// Replace the contents of the local store.
+pub fn replace_all(entries: &[WorkoutEntry]) {
+    if let Some(p) = path()
+        && let Err(e) = replace_at(&p, entries)
+    {
+        log::error!("Failed to rewrite workout store: {e}");
+    }
+}
+
+/// Remove the local store file.
+pub fn clear() {
+    if let Some(p) = path()
+        && p.exists()
+        && let Err(e) = std::fs::remove_file(p)
+    {
+        log::error!("Failed to clear workout store: {e}");
+    }
+}
+
+/// Merge `incoming` into `existing` in memory, replacing whole workouts that
+/// share a key with the incoming data. A workout without a Hevy id also
+/// replaces, or is replaced by, a copy with the same start time.
+pub fn merge_into(existing: &mut Vec<WorkoutEntry>, incoming: Vec<WorkoutEntry>) {
+    let keys: HashSet<String> = incoming.iter().map(workout_key).collect();
+    let starts: HashSet<String> = incoming.iter().map(start_key).collect();
+    let id_less_starts: HashSet<String> = incoming
+        .iter()
+        .filter(|e| e.raw.workout_id.is_none())
+        .map(start_key)
+        .collect();
+    existing.retain(|e| {
+        let start = start_key(e);
+        let same_start = match e.raw.workout_id {
+            Some(_) => id_less_starts.contains(&start),
+            None => starts.contains(&start),
+        };
+        !keys.contains(&workout_key(e)) && !same_start
+    });
+    existing.extend(incoming);
+}
+
+/// One version of a set seen while merging, tagged with where it came from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetVersion {
+    pub source: String,
+    pub weight: Option<f32>,
+    pub reps: Option<u32>,
+}
+
+/// The same set appeared in two sources with different weight or reps.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MergeConflict {
+    pub start_time: String,
+    pub exercise: String,
+    pub set_index: u32,
+    pub kept: SetVersion,
+    pub discarded: SetVersion,
+}
+
+/// Summary of a multi-file merge.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MergeReport {
+    pub sources: usize,
+    pub total_sets: usize,
+    pub duplicates: usize,
+    pub conflicts: Vec<MergeConflict>,
+}
+
+/// Union several sources at set level.
+///
+/// Sets are identified by workout start time (see [`start_key`]), exercise
+/// and set index. Later
+/// sources win, so pass them oldest first. Identical duplicates are dropped
+/// silently while differing ones are recorded as conflicts. Sets without an
+/// index cannot be matched and are always kept.
+pub fn merge_sets(sources: Vec<(String, Vec<WorkoutEntry>)>) -> (Vec<WorkoutEntry>, MergeReport) {
+    let mut merged: Vec<WorkoutEntry> = Vec::new();
+    let mut seen: HashMap<(String, String, u32), (usize, String)> = HashMap::new();
+    let mut report = MergeReport {
+        sources: sources.len(),
+        ..MergeReport::default()
+    };
+    for (source, entries) in sources {
+        for e in entries {
+            let Some(idx) = e.raw.set_index else {
+                merged.push(e);
+                continue;
+            };
+            let key = (start_key(&e), e.exercise.clone(), idx);
+            match seen.get_mut(&key) {
+                Some((pos, prev_source)) => {
+                    report.duplicates += 1;
+                    let prev = &merged[*pos];
+                    if prev.weight != e.weight || prev.reps != e.reps {
+                        report.conflicts.push(MergeConflict {
+                            start_time: key.0,
+                            exercise: key.1,
+                            set_index: idx,
+                            kept: SetVersion {
+                                source: source.clone(),
+                                weight: e.weight,
+                                reps: e.reps,
+                            },
+                            discarded: SetVersion {
+                                source: prev_source.clone(),
+                                weight: prev.weight,
+                                reps: prev.reps,
+                            },
+                        });
+                    }
+                    merged[*pos] = e;
+                    *prev_source = source.clone();
+                }
+                None => {
+                    seen.insert(key, (merged.len(), source.clone()));
+                    merged.push(e);
+                }
+            }
+        }
+    }
+    report.total_sets = merged.len();
+    (merged, report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RawWorkoutRow;
+
+    fn entry(id: &str, exercise: &str, weight: f32) -> WorkoutEntry {
+        WorkoutEntry {
+            date: "2024-01-01".into(),
+            exercise: exercise.into(),
+            weight: Some(weight),
+            reps: Some(5),
+            start: None,
+            raw: RawWorkoutRow {
+                workout_id: Some(id.into()),
+                start_time: "2024-01-01T10:00:00Z".into(),
+                exercise_title: exercise.into(),
+                ..RawWorkoutRow::default()
+            },
+        }
+    }
+
+    #[test]
+    fn later_lines_replace_earlier_workouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE);
+
+        append_to(
+            &path,
+            &[entry("a", "Bench", 100.0), entry("b", "Squat", 150.0)],
+        )
+        .unwrap();
+        append_to(
+            &path,
+            &[entry("a", "Bench", 105.0), entry("a", "Row", 80.0)],
+        )
+        .unwrap();
+
+        let StoreContents { entries, stale, .. } = read(&path).unwrap();
+        assert!(stale);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].exercise, "Squat");
+        assert_eq!(entries[1].weight, Some(105.0));
+        assert_eq!(entries[2].exercise, "Row");
+
+        replace_at(&path, &entries).unwrap();
+        let compacted = read(&path).unwrap();
+        assert!(!compacted.stale);
+        assert_eq!(compacted.entries.len(), 3);
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+    }
+
+    #[test]
+    fn invalid_lines_move_to_sidecar() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE);
+        append_to(&path, &[entry("a", "Bench", 100.0)]).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "not json").unwrap();
+
+        let contents = read(&path).unwrap();
+        assert!(!contents.stale);
+        assert_eq!(contents.entries.len(), 1);
+        assert_eq!(contents.corrupt, vec!["not json".to_string()]);
+
+        let entries = load_at(&path);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(
+            std::fs::read_to_string(corrupt_path(&path)).unwrap(),
+            "not json\n"
+        );
+        let reread = read(&path).unwrap();
+        assert!(reread.corrupt.is_empty());
+        assert_eq!(reread.entries.len(), 1);
+    }
+
+    #[test]
+    fn csv_and_api_copies_share_start_key() {
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut csv = entry("a", "Bench", 100.0);
+        csv.raw.workout_id = None;
+        csv.raw.start_time = "01 Jan 2024, 10:00".into();
+        csv.start = Some(start);
+        let mut api = entry("w1", "Bench", 105.0);
+        api.start = Some(start);
+
+        assert_eq!(workout_key(&csv), "2024-01-01 10:00");
+        assert_eq!(start_key(&csv), start_key(&api));
+
+        let mut existing = vec![csv.clone()];
+        merge_into(&mut existing, vec![api.clone()]);
+        assert_eq!(existing.len(), 1);
+        assert_eq!(existing[0].weight, Some(105.0));
+
+        let mut existing = vec![api];
+        merge_into(&mut existing, vec![csv]);
+        assert_eq!(existing.len(), 1);
+        assert_eq!(existing[0].weight, Some(100.0));
+    }
+
+    #[test]
+    fn csv_line_replaces_stored_api_copy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(FILE);
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let mut api = entry("w1", "Bench", 105.0);
+        api.start = Some(start);
+        let mut csv = entry("a", "Bench", 100.0);
+        csv.raw.workout_id = None;
+        csv.start = Some(start);
+        let mut other = entry("w2", "Squat", 150.0);
+        other.start = Some(start + chrono::Duration::days(1));
+
+        append_to(&path, &[api.clone(), other]).unwrap();
+        append_to(&path, &[csv]).unwrap();
+        let entries = load_at(&path);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].exercise, "Squat");
+        assert_eq!(entries[1].weight, Some(100.0));
+        assert_eq!(std::fs::read_to_string(&path).unwrap().lines().count(), 2);
+
+        append_to(&path, &[api]).unwrap();
+        let entries = read(&path).unwrap().entries;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].weight, Some(105.0));
+    }
+
+    #[test]
+    fn merge_replaces_matching_workouts() {
+        let mut existing = vec![entry("a", "Bench", 100.0), entry("b", "Squat", 150.0)];
+        merge_into(&mut existing, vec![entry("a", "Bench", 110.0)]);
+        assert_eq!(existing.len(), 2);
+        assert_eq!(existing[0].exercise, "Squat");
+        assert_eq!(existing[1].weight, Some(110.0));
+    }
+
+    #[test]
+    fn merge_sets_dedupes_and_reports_conflicts() {
+        let set = |ex: &str, idx: u32, weight: f32| WorkoutEntry {
+            start: None,
+            raw: RawWorkoutRow {
+                set_index: Some(idx),
+                ..entry("a", ex, weight).raw
+            },
+            ..entry("a", ex, weight)
+        };
+        let (merged, report) = merge_sets(vec![
+            (
+                "old.csv".into(),
+                vec![set("Bench", 0, 100.0), set("Bench", 1, 100.0)],
+            ),
+            (
+                "new.csv".into(),
+                vec![
+                    set("Bench", 0, 100.0),
+                    set("Bench", 1, 105.0),
+                    set("Squat", 0, 150.0),
+                ],
+            ),
+        ]);
+
+        assert_eq!(merged.len(), 3);
+        assert_eq!(merged[1].weight, Some(105.0));
+        assert_eq!(report.sources, 2);
+        assert_eq!(report.total_sets, 3);
+        assert_eq!(report.duplicates, 2);
+        assert_eq!(report.conflicts.len(), 1);
+        let c = &report.conflicts[0];
+        assert_eq!(c.set_index, 1);
+        assert_eq!(c.kept.source, "new.csv");
+        assert_eq!(c.kept.weight, Some(105.0));
+        assert_eq!(c.discarded.source, "old.csv");
+        assert_eq!(c.discarded.weight, Some(100.0));
+    }
+
+    #[test]
+    fn merge_sets_matches_api_and_csv_copies() {
+        let start = chrono::NaiveDate::from_ymd_opt(2024, 1, 1)
+            .unwrap()
+            .and_hms_opt(10, 0, 0)
+            .unwrap();
+        let set = |id: Option<&str>, start_time: &str, weight: f32| {
+            let mut e = entry("a", "Bench", weight);
+            e.start = Some(start);
+            e.raw.workout_id = id.map(str::to_string);
+            e.raw.start_time = start_time.into();
+            e.raw.set_index = Some(0);
+            e
+        };
+        let (merged, report) = merge_sets(vec![
+            (
+                "api".into(),
+                vec![set(Some("w1"), "2024-01-01T10:00:00Z", 100.0)],
+            ),
+            (
+                "export.csv".into(),
+                vec![set(None, "01 Jan 2024, 10:00", 105.0)],
+            ),
+        ]);
+
+        assert_eq!(merged.len(), 1);
+        assert_eq!(merged[0].weight, Some(105.0));
+        assert_eq!(report.duplicates, 1);
+        assert_eq!(report.conflicts.len(), 1);
+        assert_eq!(report.conflicts[0].kept.source, "export.csv");
+    }
+}
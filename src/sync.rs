@@ -1,7 +1,68 @@
+use crate::import::parse_api_time;
 use crate::{RawWorkoutRow, WorkoutEntry};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::time::Duration;
 
 const HEVY_URL: &str = "https://api.hevyapp.com/v1/workouts";
+const HEVY_EVENTS_URL: &str = "https://api.hevyapp.com/v1/workouts/events";
+pub const HEVY_TEMPLATES_URL: &str = "https://api.hevyapp.com/v1/exercise_templates";
+
+/// How `sync_from_hevy` requests changes from the API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SyncMode {
+    /// Fetch workouts started after the last sync and append unseen ones.
+    #[default]
+    NewWorkouts,
+    /// Replay the workout events feed so edits and deletions are applied.
+    Events,
+}
+
+/// Upper bound for a single backoff delay between retries. A server
+/// `Retry-After` above this ends the sync with [`SyncError::RateLimited`].
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(60);
+
+/// How often a backoff wait checks whether the sync was cancelled.
+const CANCEL_POLL: Duration = Duration::from_millis(200);
+
+/// How often and how long to wait before retrying rate-limited or failed
+/// requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RetryPolicy {
+    /// Total number of attempts per request, including the first one.
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for every further attempt.
+    pub base_delay_ms: u64,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            base_delay_ms: 500,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay to wait after the given failed attempt (starting at 1).
+    ///
+    /// A `Retry-After` value from the server is used as-is, otherwise the
+    /// base delay is doubled per attempt and capped at one minute.
+    pub fn delay(&self, attempt: u32, retry_after: Option<u64>) -> Duration {
+        match retry_after {
+            Some(secs) => Duration::from_secs(secs),
+            None => {
+                let factor = 1u64
+                    .checked_shl(attempt.saturating_sub(1))
+                    .unwrap_or(u64::MAX);
+                Duration::from_millis(self.base_delay_ms.saturating_mul(factor))
+                    .min(MAX_RETRY_DELAY)
+            }
+        }
+    }
+}
 
 /// Determine the API key to use for Hevy requests.
 ///
@@ -17,6 +78,17 @@ pub fn resolve_api_key(settings_key: Option<&str>) -> Option<String> {
 pub enum SyncError {
     Unauthorized(String),
     Forbidden(String),
+    /// HTTP 429; `retry_after` holds the server's `Retry-After` in seconds.
+    RateLimited {
+        retry_after: Option<u64>,
+    },
+    /// HTTP 5xx from the API.
+    Server {
+        status: u16,
+        body: String,
+    },
+    /// The caller stopped the sync from the progress callback.
+    Cancelled,
     Other(Box<dyn std::error::Error + Send + Sync>),
 }
 
@@ -25,6 +97,12 @@ impl std::fmt::Display for SyncError {
         match self {
             SyncError::Unauthorized(body) => write!(f, "Unauthorized: {body}"),
             SyncError::Forbidden(body) => write!(f, "Forbidden: {body}"),
+            SyncError::RateLimited {
+                retry_after: Some(secs),
+            } => write!(f, "Rate limited, retry after {secs}s"),
+            SyncError::RateLimited { retry_after: None } => write!(f, "Rate limited"),
+            SyncError::Server { status, body } => write!(f, "Server error {status}: {body}"),
+            SyncError::Cancelled => write!(f, "Sync cancelled"),
             SyncError::Other(e) => write!(f, "{e}"),
         }
     }
@@ -33,20 +111,146 @@ impl std::fmt::Display for SyncError {
 impl std::error::Error for SyncError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            SyncError::Unauthorized(_) | SyncError::Forbidden(_) => None,
+            SyncError::Unauthorized(_)
+            | SyncError::Forbidden(_)
+            | SyncError::RateLimited { .. }
+            | SyncError::Server { .. }
+            | SyncError::Cancelled => None,
             SyncError::Other(e) => Some(&**e),
         }
     }
 }
 
-fn fetch_latest_workouts_with_url(
+/// Number of workouts requested per page. The Hevy API caps `pageSize` at 10.
+const PAGE_SIZE: u32 = 10;
+
+/// Metres per mile, used to store API distances in the CSV's `distance_miles`.
+const METERS_PER_MILE: f32 = 1609.344;
+
+fn str_field(v: &Value, key: &str) -> Option<String> {
+    v.get(key)
+        .and_then(|v| v.as_str())
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string())
+}
+
+fn f32_field(v: &Value, key: &str) -> Option<f32> {
+    v.get(key).and_then(|v| v.as_f64()).map(|f| f as f32)
+}
+
+fn u32_field(v: &Value, key: &str) -> Option<u32> {
+    v.get(key).and_then(|v| v.as_u64()).map(|n| n as u32)
+}
+
+/// Read an identifier that the API may send as either a number or a string.
+fn id_field(v: &Value, key: &str) -> Option<String> {
+    match v.get(key)? {
+        Value::String(s) if !s.is_empty() => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Convert a single workout object from the API into flat `WorkoutEntry` rows.
+///
+/// Every set is mapped onto the same `RawWorkoutRow` columns a Hevy CSV
+/// export fills, so synced and imported data behave identically.
+fn workout_entries(w: &Value) -> Vec<WorkoutEntry> {
+    let mut entries = Vec::new();
+    let start_time = w.get("start_time").and_then(|v| v.as_str()).unwrap_or("");
+    let Some(start) = parse_api_time(start_time) else {
+        log::warn!("Skipping workout with unparseable start_time {start_time:?}");
+        return Vec::new();
+    };
+    let base = RawWorkoutRow {
+        title: str_field(w, "title"),
+        start_time: start_time.to_string(),
+        end_time: str_field(w, "end_time"),
+        description: str_field(w, "description"),
+        workout_id: str_field(w, "id"),
+        ..RawWorkoutRow::default()
+    };
+    if let Some(exercises) = w.get("exercises").and_then(|v| v.as_array()) {
+        for ex in exercises {
+            let name = str_field(ex, "title")
+                .or_else(|| str_field(ex, "name"))
+                .unwrap_or_else(|| "Unknown".to_string());
+            if let Some(sets) = ex.get("sets").and_then(|v| v.as_array()) {
+                for set in sets {
+                    let raw = RawWorkoutRow {
+                        exercise_title: name.clone(),
+                        superset_id: id_field(ex, "superset_id"),
+                        exercise_notes: str_field(ex, "notes"),
+                        set_index: u32_field(set, "index"),
+                        set_type: str_field(set, "type").or_else(|| str_field(set, "set_type")),
+                        weight_lbs: f32_field(set, "weight_lb")
+                            .or_else(|| f32_field(set, "weight_lbs")),
+                        weight_kg: f32_field(set, "weight_kg").or_else(|| f32_field(set, "weight")),
+                        reps: u32_field(set, "reps"),
+                        distance_miles: f32_field(set, "distance_meters")
+                            .map(|m| m / METERS_PER_MILE),
+                        duration_seconds: f32_field(set, "duration_seconds"),
+                        rpe: f32_field(set, "rpe"),
+                        ..base.clone()
+                    };
+                    if let Some(entry) = WorkoutEntry::from_raw(raw, start) {
+                        entries.push(entry);
+                    }
+                }
+            }
+        }
+    }
+    entries
+}
+
+/// A single page of results as returned by the API.
+///
+/// `page_count` is `None` when the server responded with a bare array, in
+/// which case the response is treated as the complete result.
+struct Page {
+    items: Vec<Value>,
+    page_count: Option<u32>,
+}
+
+/// Interpret a response body as either `{"<list_key>": [...], "page_count": n}`
+/// or a bare JSON array.
+fn parse_page(body: &str, list_key: &str) -> Result<Page, SyncError> {
+    let json: Value = serde_json::from_str(body).map_err(|e| SyncError::Other(Box::new(e)))?;
+    match json {
+        Value::Array(items) => Ok(Page {
+            items,
+            page_count: None,
+        }),
+        Value::Object(mut obj) => {
+            let items = match obj.remove(list_key) {
+                Some(Value::Array(w)) => w,
+                _ => Vec::new(),
+            };
+            let page_count = obj
+                .get("page_count")
+                .and_then(|v| v.as_u64())
+                .map(|n| n as u32);
+            Ok(Page { items, page_count })
+        }
+        _ => Ok(Page {
+            items: Vec::new(),
+            page_count: None,
+        }),
+    }
+}
+
+fn fetch_page(
     url: &str,
     api_key: &str,
-    after: Option<&str>,
-) -> Result<Vec<WorkoutEntry>, SyncError> {
-    let mut req = ureq::get(url);
-    if let Some(ts) = after {
-        req = req.query("after", ts);
+    query: &[(&str, &str)],
+    list_key: &str,
+    page: u32,
+) -> Result<Page, SyncError> {
+    let mut req = ureq::get(url)
+        .query("page", &page.to_string())
+        .query("pageSize", &PAGE_SIZE.to_string());
+    for (k, v) in query {
+        req = req.query(k, v);
     }
     let response = req
         .set("X-API-Key", api_key)
@@ -62,66 +266,283 @@ fn fetch_latest_workouts_with_url(
             let body = r.into_string().unwrap_or_default();
             return Err(SyncError::Forbidden(body));
         }
+        Err(ureq::Error::Status(429, r)) => {
+            let retry_after = r
+                .header("Retry-After")
+                .and_then(|v| v.trim().parse::<u64>().ok());
+            return Err(SyncError::RateLimited { retry_after });
+        }
+        Err(ureq::Error::Status(status @ 500..=599, r)) => {
+            let body = r.into_string().unwrap_or_default();
+            return Err(SyncError::Server { status, body });
+        }
         Err(e) => return Err(SyncError::Other(Box::new(e))),
     };
-    let json: Value = serde_json::from_str(&resp).map_err(|e| SyncError::Other(Box::new(e)))?;
-    let mut entries = Vec::new();
-    if let Some(workouts) = json.as_array() {
-        for w in workouts {
-            let start_time = w.get("start_time").and_then(|v| v.as_str()).unwrap_or("");
-            let date = start_time.split('T').next().unwrap_or("").to_string();
-            if let Some(exercises) = w.get("exercises").and_then(|v| v.as_array()) {
-                for ex in exercises {
-                    let name = ex.get("name").and_then(|v| v.as_str()).unwrap_or("Unknown");
-                    if let Some(sets) = ex.get("sets").and_then(|v| v.as_array()) {
-                        for set in sets {
-                            let weight = set
-                                .get("weight")
-                                .or_else(|| set.get("weight_kg"))
-                                .or_else(|| set.get("weight_lb"))
-                                .and_then(|v| v.as_f64());
-                            let reps = set.get("reps").and_then(|v| v.as_u64());
-                            if let (Some(weight), Some(reps)) = (weight, reps) {
-                                let mut raw = RawWorkoutRow::default();
-                                raw.start_time = start_time.to_string();
-                                raw.exercise_title = name.to_string();
-                                raw.weight_kg = Some(weight as f32);
-                                raw.reps = Some(reps as u32);
-                                let entry = WorkoutEntry {
-                                    date: date.clone(),
-                                    exercise: name.to_string(),
-                                    weight: Some(weight as f32 * 2.20462),
-                                    reps: Some(reps as u32),
-                                    raw,
-                                };
-                                entries.push(entry);
-                            }
-                        }
-                    }
+    parse_page(&resp, list_key)
+}
+
+/// Fetch a page, retrying rate-limited and server errors according to `retry`.
+///
+/// While waiting between attempts `cancelled` is polled every
+/// [`CANCEL_POLL`]; once it returns `true` the wait ends with
+/// [`SyncError::Cancelled`]. A `Retry-After` longer than [`MAX_RETRY_DELAY`]
+/// is not waited out and the rate limit error is returned instead.
+fn fetch_page_with_retry(
+    url: &str,
+    api_key: &str,
+    query: &[(&str, &str)],
+    list_key: &str,
+    page: u32,
+    retry: &RetryPolicy,
+    mut cancelled: impl FnMut() -> bool,
+) -> Result<Page, SyncError> {
+    let mut attempt = 1;
+    loop {
+        let retry_after = match fetch_page(url, api_key, query, list_key, page) {
+            Err(SyncError::RateLimited { retry_after })
+                if attempt < retry.max_attempts
+                    && retry_after.is_none_or(|s| s <= MAX_RETRY_DELAY.as_secs()) =>
+            {
+                retry_after
+            }
+            Err(SyncError::Server { .. }) if attempt < retry.max_attempts => None,
+            result => return result,
+        };
+        let delay = retry.delay(attempt, retry_after);
+        log::warn!("Hevy request failed, retrying in {delay:?} (attempt {attempt})");
+        let deadline = std::time::Instant::now() + delay;
+        loop {
+            if cancelled() {
+                return Err(SyncError::Cancelled);
+            }
+            let left = deadline.saturating_duration_since(std::time::Instant::now());
+            if left.is_zero() {
+                break;
+            }
+            std::thread::sleep(left.min(CANCEL_POLL));
+        }
+        attempt += 1;
+    }
+}
+
+/// Walk every page of a paginated endpoint starting at page 1 and collect the
+/// items stored under `list_key`.
+///
+/// `progress` is called after each page with the page just fetched and the
+/// total page count reported by the server. Returning `false` from it stops
+/// the walk with [`SyncError::Cancelled`]. It is also polled with the pages
+/// fetched so far while a retry is backing off, so cancelling takes effect
+/// during the wait.
+fn fetch_all_pages(
+    url: &str,
+    api_key: &str,
+    query: &[(&str, &str)],
+    list_key: &str,
+    retry: &RetryPolicy,
+    mut progress: impl FnMut(u32, u32) -> bool,
+) -> Result<Vec<Value>, SyncError> {
+    let mut items = Vec::new();
+    let mut page = 1;
+    let mut known_pages = 1;
+    loop {
+        let result = fetch_page_with_retry(url, api_key, query, list_key, page, retry, || {
+            !progress(page - 1, known_pages)
+        })?;
+        let empty = result.items.is_empty();
+        items.extend(result.items);
+        let page_count = result.page_count.unwrap_or(page);
+        known_pages = page_count.max(page);
+        if !progress(page, page_count.max(page)) {
+            return Err(SyncError::Cancelled);
+        }
+        if result.page_count.is_none() || empty || page >= page_count {
+            break;
+        }
+        page += 1;
+    }
+    Ok(items)
+}
+
+fn fetch_latest_workouts_with_url(
+    url: &str,
+    api_key: &str,
+    after: Option<&str>,
+    retry: &RetryPolicy,
+    progress: impl FnMut(u32, u32) -> bool,
+) -> Result<Vec<WorkoutEntry>, SyncError> {
+    let mut query = Vec::new();
+    if let Some(ts) = after {
+        query.push(("after", ts));
+    }
+    let workouts = fetch_all_pages(url, api_key, &query, "workouts", retry, progress)?;
+    Ok(workouts.iter().flat_map(workout_entries).collect())
+}
+
+/// A change reported by the workout events feed.
+#[derive(Debug)]
+pub enum WorkoutEvent {
+    /// A workout was created or edited; `entries` holds its current sets.
+    Updated {
+        id: String,
+        /// Parsed start time, used to match entries imported without an id.
+        start: Option<NaiveDateTime>,
+        entries: Vec<WorkoutEntry>,
+    },
+    /// A workout was removed in Hevy.
+    Deleted { id: String },
+}
+
+fn parse_event(ev: &Value) -> Option<WorkoutEvent> {
+    match ev.get("type").and_then(|v| v.as_str())? {
+        "updated" => {
+            let w = ev.get("workout")?;
+            let id = w.get("id").and_then(|v| v.as_str())?.to_string();
+            let start = w
+                .get("start_time")
+                .and_then(|v| v.as_str())
+                .and_then(parse_api_time);
+            Some(WorkoutEvent::Updated {
+                id,
+                start,
+                entries: workout_entries(w),
+            })
+        }
+        "deleted" => {
+            let id = ev.get("id").and_then(|v| v.as_str())?.to_string();
+            Some(WorkoutEvent::Deleted { id })
+        }
+        _ => None,
+    }
+}
+
+fn fetch_workout_events_with_url(
+    url: &str,
+    api_key: &str,
+    since: &str,
+    retry: &RetryPolicy,
+    progress: impl FnMut(u32, u32) -> bool,
+) -> Result<Vec<WorkoutEvent>, SyncError> {
+    let events = fetch_all_pages(url, api_key, &[("since", since)], "events", retry, progress)?;
+    Ok(events.iter().filter_map(parse_event).collect())
+}
+
+/// Fetch all workout events recorded since the given RFC 3339 timestamp.
+///
+/// Events are returned in the order the API reports them, oldest first, and
+/// should be passed to [`apply_workout_events`].
+pub fn fetch_workout_events(
+    api_key: &str,
+    since: &str,
+    retry: &RetryPolicy,
+    progress: impl FnMut(u32, u32) -> bool,
+) -> Result<Vec<WorkoutEvent>, SyncError> {
+    fetch_workout_events_with_url(HEVY_EVENTS_URL, api_key, since, retry, progress)
+}
+
+/// Apply events to the loaded entries, keyed by Hevy workout id.
+///
+/// Entries belonging to an updated or deleted workout are removed before the
+/// updated sets are appended. Entries without an id (for example CSV imports)
+/// are matched on their parsed start time instead, since the API and the CSV
+/// format timestamps differently. Deletions only carry an id, so they cannot
+/// reach such entries. Returns the number of updated workouts and of deleted
+/// workouts that actually removed rows.
+pub fn apply_workout_events(
+    entries: &mut Vec<WorkoutEntry>,
+    events: Vec<WorkoutEvent>,
+) -> (usize, usize) {
+    let mut updated = 0;
+    let mut deleted = 0;
+    for event in events {
+        match event {
+            WorkoutEvent::Updated {
+                id,
+                start,
+                entries: new_entries,
+            } => {
+                entries.retain(|e| match &e.raw.workout_id {
+                    Some(wid) => *wid != id,
+                    None => start.is_none() || e.start != start,
+                });
+                entries.extend(new_entries);
+                updated += 1;
+            }
+            WorkoutEvent::Deleted { id } => {
+                let before = entries.len();
+                entries.retain(|e| e.raw.workout_id.as_deref() != Some(id.as_str()));
+                if entries.len() < before {
+                    deleted += 1;
                 }
             }
         }
     }
-    Ok(entries)
+    (updated, deleted)
+}
+
+/// An exercise template from the Hevy catalog.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ExerciseTemplate {
+    pub id: String,
+    pub title: String,
+    /// Hevy set type such as `weight_reps`, `reps_only` or `distance_duration`.
+    pub kind: Option<String>,
+    pub primary_muscle_group: Option<String>,
+    pub secondary_muscle_groups: Vec<String>,
+    pub equipment: Option<String>,
+}
+
+fn parse_template(t: &Value) -> Option<ExerciseTemplate> {
+    Some(ExerciseTemplate {
+        id: id_field(t, "id")?,
+        title: str_field(t, "title")?,
+        kind: str_field(t, "type"),
+        primary_muscle_group: str_field(t, "primary_muscle_group"),
+        secondary_muscle_groups: t
+            .get("secondary_muscle_groups")
+            .and_then(|v| v.as_array())
+            .map(|a| {
+                a.iter()
+                    .filter_map(|m| m.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        equipment: str_field(t, "equipment"),
+    })
+}
+
+/// Fetch every exercise template visible to the account, including custom
+/// exercises, from `url` (normally [`HEVY_TEMPLATES_URL`]).
+pub fn fetch_exercise_templates_with_url(
+    url: &str,
+    api_key: &str,
+    retry: &RetryPolicy,
+    progress: impl FnMut(u32, u32) -> bool,
+) -> Result<Vec<ExerciseTemplate>, SyncError> {
+    let templates = fetch_all_pages(url, api_key, &[], "exercise_templates", retry, progress)?;
+    Ok(templates.iter().filter_map(parse_template).collect())
 }
 
 /// Fetch the latest workouts from the Hevy API using the provided API key.
 ///
-/// The function performs a simple HTTP GET request to the public Hevy
-/// endpoint and attempts to map the returned JSON into the existing
-/// `WorkoutEntry` structure. Only a subset of fields is extracted so the
-/// function remains resilient to API changes. Any missing data is skipped.
+/// All pages of the `/v1/workouts` endpoint are requested in turn and the
+/// returned JSON is mapped into the existing `WorkoutEntry` structure. Only a
+/// subset of fields is extracted so the function remains resilient to API
+/// changes. Any missing data is skipped. `progress` receives the current page
+/// and total page count after each request and returns `false` to cancel.
 pub fn fetch_latest_workouts(
     api_key: &str,
     after: Option<&str>,
+    retry: &RetryPolicy,
+    progress: impl FnMut(u32, u32) -> bool,
 ) -> Result<Vec<WorkoutEntry>, SyncError> {
     log::info!("Fetching latest workouts using API key: {api_key}");
-    fetch_latest_workouts_with_url(HEVY_URL, api_key, after)
+    fetch_latest_workouts_with_url(HEVY_URL, api_key, after, retry, progress)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::import::DateFormat;
     use httpmock::prelude::*;
 
     #[test]
@@ -132,8 +553,14 @@ mod tests {
             then.status(403).body("forbidden body");
         });
 
-        let err =
-            fetch_latest_workouts_with_url(&server.url("/v1/workouts"), "key", None).unwrap_err();
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &RetryPolicy::default(),
+            |_, _| true,
+        )
+        .unwrap_err();
         match err {
             SyncError::Forbidden(body) => assert_eq!(body, "forbidden body"),
             e => panic!("unexpected error: {e:?}"),
@@ -150,8 +577,14 @@ mod tests {
             then.status(401).body("unauthorized body");
         });
 
-        let err =
-            fetch_latest_workouts_with_url(&server.url("/v1/workouts"), "key", None).unwrap_err();
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &RetryPolicy::default(),
+            |_, _| true,
+        )
+        .unwrap_err();
         match err {
             SyncError::Unauthorized(body) => assert_eq!(body, "unauthorized body"),
             e => panic!("unexpected error: {e:?}"),
@@ -178,8 +611,14 @@ mod tests {
             then.status(200).body("[]");
         });
 
-        fetch_latest_workouts_with_url(&server.url("/v1/workouts"), key.as_deref().unwrap(), None)
-            .unwrap();
+        fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            key.as_deref().unwrap(),
+            None,
+            &RetryPolicy::default(),
+            |_, _| true,
+        )
+        .unwrap();
 
         m.assert();
 
@@ -187,4 +626,487 @@ mod tests {
             std::env::remove_var("HEVY_API_KEY");
         }
     }
+
+    fn workout_json(start: &str, weight: f64) -> String {
+        format!(
+            "{{\"start_time\":\"{start}\",\"exercises\":[{{\"name\":\"Bench Press\",\"sets\":[{{\"weight_kg\":{weight},\"reps\":5}}]}}]}}"
+        )
+    }
+
+    #[test]
+    fn walks_all_pages() {
+        let server = MockServer::start();
+        let p1 = server.mock(|when, then| {
+            when.method(GET)
+                .path("/v1/workouts")
+                .query_param("page", "1")
+                .query_param("pageSize", "10");
+            then.status(200).body(format!(
+                "{{\"page\":1,\"page_count\":2,\"workouts\":[{}]}}",
+                workout_json("2024-01-01T10:00:00Z", 100.0)
+            ));
+        });
+        let p2 = server.mock(|when, then| {
+            when.method(GET)
+                .path("/v1/workouts")
+                .query_param("page", "2")
+                .query_param("pageSize", "10");
+            then.status(200).body(format!(
+                "{{\"page\":2,\"page_count\":2,\"workouts\":[{}]}}",
+                workout_json("2024-01-03T10:00:00Z", 105.0)
+            ));
+        });
+
+        let mut pages = Vec::new();
+        let entries = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &RetryPolicy::default(),
+            |p, n| {
+                pages.push((p, n));
+                true
+            },
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].date, "2024-01-01");
+        assert_eq!(entries[1].date, "2024-01-03");
+        assert_eq!(entries[1].raw.weight_kg, Some(105.0));
+        assert_eq!(pages, vec![(1, 2), (2, 2)]);
+        p1.assert();
+        p2.assert();
+    }
+
+    #[test]
+    fn bare_array_is_single_page() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(200)
+                .body(format!("[{}]", workout_json("2024-02-01T08:00:00Z", 60.0)));
+        });
+
+        let mut pages = Vec::new();
+        let entries = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &RetryPolicy::default(),
+            |p, n| {
+                pages.push((p, n));
+                true
+            },
+        )
+        .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].exercise, "Bench Press");
+        assert_eq!(pages, vec![(1, 1)]);
+        m.assert_hits(1);
+    }
+
+    #[test]
+    fn fetches_exercise_templates() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET)
+                .path("/v1/exercise_templates")
+                .header("X-API-Key", "key");
+            then.status(200).body(
+                r#"{"page":1,"page_count":1,"exercise_templates":[
+                    {"id":"3BC06AD3","title":"Lat Pulldown (Cable)","type":"weight_reps",
+                     "primary_muscle_group":"lats","secondary_muscle_groups":["biceps","upper_back"],
+                     "equipment":"machine","is_custom":false},
+                    {"title":"Missing id"}
+                ]}"#,
+            );
+        });
+
+        let templates = fetch_exercise_templates_with_url(
+            &server.url("/v1/exercise_templates"),
+            "key",
+            &fast_retry(),
+            |_, _| true,
+        )
+        .unwrap();
+
+        assert_eq!(
+            templates,
+            vec![ExerciseTemplate {
+                id: "3BC06AD3".into(),
+                title: "Lat Pulldown (Cable)".into(),
+                kind: Some("weight_reps".into()),
+                primary_muscle_group: Some("lats".into()),
+                secondary_muscle_groups: vec!["biceps".into(), "upper_back".into()],
+                equipment: Some("machine".into()),
+            }]
+        );
+        m.assert();
+    }
+
+    #[test]
+    fn events_update_and_delete_by_workout_id() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET)
+                .path("/v1/workouts/events")
+                .query_param("since", "2024-01-01T00:00:00Z")
+                .query_param("page", "1");
+            then.status(200).body(format!(
+                "{{\"page\":1,\"page_count\":1,\"events\":[\
+                 {{\"type\":\"updated\",\"workout\":{}}},\
+                 {{\"type\":\"deleted\",\"id\":\"w2\",\"deleted_at\":\"2024-01-05T00:00:00Z\"}}]}}",
+                workout_json("2024-01-01T10:00:00Z", 110.0).replacen('{', "{\"id\":\"w1\",", 1)
+            ));
+        });
+
+        let mut entries = Vec::new();
+        for (id, start, weight) in [
+            ("w1", "2024-01-01T10:00:00Z", 100.0),
+            ("w2", "2024-01-02T10:00:00Z", 80.0),
+            ("w3", "2024-01-03T10:00:00Z", 90.0),
+        ] {
+            let w: Value = serde_json::from_str(&workout_json(start, weight).replacen(
+                '{',
+                &format!("{{\"id\":\"{id}\","),
+                1,
+            ))
+            .unwrap();
+            entries.extend(workout_entries(&w));
+        }
+
+        let events = fetch_workout_events_with_url(
+            &server.url("/v1/workouts/events"),
+            "key",
+            "2024-01-01T00:00:00Z",
+            &RetryPolicy::default(),
+            |_, _| true,
+        )
+        .unwrap();
+        let (updated, deleted) = apply_workout_events(&mut entries, events);
+
+        assert_eq!((updated, deleted), (1, 1));
+        assert_eq!(entries.len(), 2);
+        let w1 = entries
+            .iter()
+            .find(|e| e.raw.workout_id.as_deref() == Some("w1"))
+            .unwrap();
+        assert_eq!(w1.raw.weight_kg, Some(110.0));
+        assert!(
+            entries
+                .iter()
+                .all(|e| e.raw.workout_id.as_deref() != Some("w2"))
+        );
+        m.assert();
+    }
+
+    /// A set as it would come from a CSV import: no workout id and the
+    /// export's own timestamp layout for the given API start time.
+    fn csv_entry(api_start: &str, weight: f32) -> WorkoutEntry {
+        let start = parse_api_time(api_start).unwrap();
+        let data = format!(
+            "title,start_time,end_time,description,exercise_title,superset_id,exercise_notes,set_index,set_type,weight_kg,reps,distance_miles,duration_seconds,rpe\n\
+             Push,\"{}\",,,Bench Press,,,0,normal,{weight},5,,,\n",
+            start.format("%d %b %Y, %H:%M")
+        );
+        let mut entries =
+            crate::parse_workout_csv(data.as_bytes(), DateFormat::Auto, None).unwrap();
+        assert_eq!(entries.len(), 1);
+        entries.remove(0)
+    }
+
+    #[test]
+    fn update_event_replaces_csv_entry_by_start() {
+        let mut entries = vec![
+            csv_entry("2024-01-01T10:00:00Z", 100.0),
+            csv_entry("2024-01-02T10:00:00Z", 80.0),
+        ];
+        let w: Value = serde_json::from_str(&workout_json("2024-01-01T10:00:00Z", 110.0).replacen(
+            '{',
+            "{\"id\":\"w1\",",
+            1,
+        ))
+        .unwrap();
+        let events =
+            vec![parse_event(&serde_json::json!({"type": "updated", "workout": w})).unwrap()];
+
+        let (updated, deleted) = apply_workout_events(&mut entries, events);
+
+        assert_eq!((updated, deleted), (1, 0));
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].raw.weight_kg, Some(80.0));
+        assert_eq!(entries[1].raw.workout_id.as_deref(), Some("w1"));
+        assert_eq!(entries[1].raw.weight_kg, Some(110.0));
+    }
+
+    #[test]
+    fn delete_event_without_match_is_not_counted() {
+        let mut entries = vec![csv_entry("2024-01-01T10:00:00Z", 100.0)];
+        let events = vec![WorkoutEvent::Deleted { id: "w1".into() }];
+
+        let (updated, deleted) = apply_workout_events(&mut entries, events);
+
+        assert_eq!((updated, deleted), (0, 0));
+        assert_eq!(entries.len(), 1);
+    }
+
+    #[test]
+    fn progress_callback_can_cancel() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(200).body(format!(
+                "{{\"page\":1,\"page_count\":3,\"workouts\":[{}]}}",
+                workout_json("2024-01-01T10:00:00Z", 100.0)
+            ));
+        });
+
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &RetryPolicy::default(),
+            |_, _| false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SyncError::Cancelled));
+        m.assert_hits(1);
+    }
+
+    fn fast_retry() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 1,
+        }
+    }
+
+    #[test]
+    fn retries_rate_limit_then_gives_up() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(429).header("Retry-After", "0");
+        });
+
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &fast_retry(),
+            |_, _| true,
+        )
+        .unwrap_err();
+        match err {
+            SyncError::RateLimited { retry_after } => assert_eq!(retry_after, Some(0)),
+            e => panic!("unexpected error: {e:?}"),
+        }
+        m.assert_hits(3);
+    }
+
+    #[test]
+    fn retries_server_errors() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(503).body("unavailable");
+        });
+
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &fast_retry(),
+            |_, _| true,
+        )
+        .unwrap_err();
+        match err {
+            SyncError::Server { status, body } => {
+                assert_eq!(status, 503);
+                assert_eq!(body, "unavailable");
+            }
+            e => panic!("unexpected error: {e:?}"),
+        }
+        m.assert_hits(3);
+    }
+
+    #[test]
+    fn recovers_after_server_error() {
+        let server = MockServer::start();
+        let mut failing = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(500);
+        });
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 300,
+        };
+        let url = server.url("/v1/workouts");
+        let handle = std::thread::spawn(move || {
+            fetch_latest_workouts_with_url(&url, "key", None, &retry, |_, _| true)
+        });
+        while failing.hits() == 0 {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+        failing.delete();
+        let ok = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(200)
+                .body(format!("[{}]", workout_json("2024-01-01T10:00:00Z", 100.0)));
+        });
+
+        let entries = handle.join().unwrap().unwrap();
+        assert_eq!(entries.len(), 1);
+        ok.assert_hits(1);
+    }
+
+    #[test]
+    fn backoff_doubles_and_caps() {
+        let policy = RetryPolicy {
+            max_attempts: 10,
+            base_delay_ms: 500,
+        };
+        assert_eq!(policy.delay(1, None), Duration::from_millis(500));
+        assert_eq!(policy.delay(2, None), Duration::from_millis(1000));
+        assert_eq!(policy.delay(3, None), Duration::from_millis(2000));
+        assert_eq!(policy.delay(40, None), MAX_RETRY_DELAY);
+        assert_eq!(policy.delay(1, Some(7)), Duration::from_secs(7));
+        assert_eq!(policy.delay(1, Some(3600)), Duration::from_secs(3600));
+    }
+
+    #[test]
+    fn long_retry_after_gives_up() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(429).header("Retry-After", "3600");
+        });
+
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &fast_retry(),
+            |_, _| true,
+        )
+        .unwrap_err();
+        match err {
+            SyncError::RateLimited { retry_after } => assert_eq!(retry_after, Some(3600)),
+            e => panic!("unexpected error: {e:?}"),
+        }
+        m.assert_hits(1);
+    }
+
+    #[test]
+    fn cancel_interrupts_backoff() {
+        let server = MockServer::start();
+        let m = server.mock(|when, then| {
+            when.method(GET).path("/v1/workouts");
+            then.status(503);
+        });
+        let retry = RetryPolicy {
+            max_attempts: 3,
+            base_delay_ms: 60_000,
+        };
+
+        let started = std::time::Instant::now();
+        let err = fetch_latest_workouts_with_url(
+            &server.url("/v1/workouts"),
+            "key",
+            None,
+            &retry,
+            |_, _| false,
+        )
+        .unwrap_err();
+        assert!(matches!(err, SyncError::Cancelled));
+        assert!(started.elapsed() < Duration::from_secs(5));
+        m.assert_hits(1);
+    }
+
+    #[test]
+    fn api_start_uses_local_time() {
+        let w: Value =
+            serde_json::from_str(&workout_json("2024-01-02T00:30:00+00:00", 100.0)).unwrap();
+        let local = chrono::DateTime::parse_from_rfc3339("2024-01-02T00:30:00+00:00")
+            .unwrap()
+            .with_timezone(&chrono::Local)
+            .naive_local();
+
+        let entries = workout_entries(&w);
+        assert_eq!(entries[0].start, Some(local));
+        assert_eq!(entries[0].date, local.date().format("%Y-%m-%d").to_string());
+    }
+
+    #[test]
+    fn maps_all_set_fields() {
+        let w: Value = serde_json::from_str(
+            r##"{
+                "id": "abc",
+                "title": "Push Day",
+                "description": "felt good",
+                "start_time": "2024-03-01T12:00:00+00:00",
+                "end_time": "2024-03-01T19:05:00+00:00",
+                "exercises": [{
+                    "title": "Bench Press (Barbell)",
+                    "notes": "#paused",
+                    "superset_id": 2,
+                    "sets": [
+                        {"index": 0, "type": "warmup", "weight_kg": 40, "reps": 10, "rpe": null},
+                        {"index": 1, "type": "normal", "weight_kg": 80, "reps": 5, "rpe": 8.5,
+                         "distance_meters": 1609.344, "duration_seconds": 45}
+                    ]
+                }]
+            }"##,
+        )
+        .unwrap();
+
+        let entries = workout_entries(&w);
+        assert_eq!(entries.len(), 2);
+        let e = &entries[1];
+        assert_eq!(e.date, "2024-03-01");
+        assert_eq!(e.exercise, "Bench Press (Barbell)");
+        assert!((e.weight.unwrap() - 176.37).abs() < 0.01);
+        let raw = &e.raw;
+        assert_eq!(raw.workout_id.as_deref(), Some("abc"));
+        assert_eq!(raw.title.as_deref(), Some("Push Day"));
+        assert_eq!(raw.description.as_deref(), Some("felt good"));
+        assert_eq!(raw.end_time.as_deref(), Some("2024-03-01T19:05:00+00:00"));
+        assert_eq!(raw.exercise_notes.as_deref(), Some("#paused"));
+        assert_eq!(raw.superset_id.as_deref(), Some("2"));
+        assert_eq!(raw.set_index, Some(1));
+        assert_eq!(raw.set_type.as_deref(), Some("normal"));
+        assert_eq!(raw.rpe, Some(8.5));
+        assert!((raw.distance_miles.unwrap() - 1.0).abs() < 1e-6);
+        assert_eq!(raw.duration_seconds, Some(45.0));
+        assert_eq!(entries[0].raw.set_type.as_deref(), Some("warmup"));
+        assert_eq!(entries[0].raw.rpe, None);
+    }
+
+    #[test]
+    fn keeps_reps_only_timed_and_distance_sets() {
+        let w: Value = serde_json::from_str(
+            r##"{
+                "id": "cardio",
+                "start_time": "2024-03-02T07:00:00+00:00",
+                "exercises": [
+                    {"title": "Pull Up", "sets": [{"index": 0, "type": "normal", "reps": 8}]},
+                    {"title": "Plank", "sets": [{"index": 0, "type": "normal", "duration_seconds": 60}]},
+                    {"title": "Running", "sets": [{"index": 0, "type": "normal",
+                        "distance_meters": 5000, "duration_seconds": 1500}]},
+                    {"title": "Empty", "sets": [{"index": 0, "type": "normal", "weight_kg": 20}]}
+                ]
+            }"##,
+        )
+        .unwrap();
+
+        let entries = workout_entries(&w);
+        let names: Vec<&str> = entries.iter().map(|e| e.exercise.as_str()).collect();
+        assert_eq!(names, vec!["Pull Up", "Plank", "Running"]);
+        assert_eq!(entries[0].reps, Some(8));
+        assert_eq!(entries[0].weight, None);
+        assert_eq!(entries[1].raw.duration_seconds, Some(60.0));
+        assert!((entries[2].raw.distance_miles.unwrap() - 3.107).abs() < 0.001);
+        assert_eq!(entries[2].raw.duration_seconds, Some(1500.0));
+    }
 }
@@ -0,0 +1,170 @@
+//! Turn Hevy exercise templates into proposed muscle mappings and exercise
+//! information for exercises that have none yet.
+
+use std::collections::HashMap;
+
+use crate::body_parts::{Equipment, ExerciseInfo, ExerciseType};
+use crate::exercise_mapping::MuscleMapping;
+use crate::exercise_utils::normalize_exercise;
+use crate::sync::ExerciseTemplate;
+
+/// A mapping suggested from a Hevy template, waiting for the user to review.
+#[derive(Debug, Clone)]
+pub struct MappingProposal {
+    pub exercise: String,
+    /// Title of the template the proposal was built from.
+    pub template: String,
+    pub mapping: MuscleMapping,
+    pub info: ExerciseInfo,
+    /// Whether the proposal is applied when the review is confirmed.
+    pub accepted: bool,
+}
+
+/// Translate a Hevy muscle group id such as `upper_back` into the names used
+/// by the bundled mapping.
+pub fn muscle_name(hevy: &str) -> String {
+    match hevy {
+        "abdominals" => "Abs".into(),
+        "quadriceps" => "Quads".into(),
+        other => other
+            .split('_')
+            .map(|w| {
+                let mut chars = w.chars();
+                match chars.next() {
+                    Some(c) => c.to_uppercase().chain(chars).collect::<String>(),
+                    None => String::new(),
+                }
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+    }
+}
+
+/// Translate a Hevy equipment id into [`Equipment`].
+pub fn equipment(hevy: &str) -> Equipment {
+    match hevy {
+        "barbell" => Equipment::Barbell,
+        "dumbbell" => Equipment::Dumbbell,
+        "machine" => Equipment::Machine,
+        "cable" => Equipment::Cable,
+        "none" => Equipment::Bodyweight,
+        _ => Equipment::Other,
+    }
+}
+
+/// Guess the exercise type from the template's set type and muscle groups.
+pub fn exercise_type(template: &ExerciseTemplate) -> ExerciseType {
+    match template.kind.as_deref() {
+        Some("duration") | Some("weight_duration") => ExerciseType::Isometric,
+        Some("distance_duration") | Some("short_distance_weight") => ExerciseType::Cardio,
+        _ if template.primary_muscle_group.as_deref() == Some("cardio") => ExerciseType::Cardio,
+        _ if template.secondary_muscle_groups.is_empty() => ExerciseType::Isolation,
+        _ => ExerciseType::Compound,
+    }
+}
+
+fn proposal(exercise: &str, template: &ExerciseTemplate) -> MappingProposal {
+    MappingProposal {
+        exercise: exercise.to_string(),
+        template: template.title.clone(),
+        mapping: MuscleMapping {
+            primary: template
+                .primary_muscle_group
+                .as_deref()
+                .map(muscle_name)
+                .unwrap_or_default(),
+            secondary: template
+                .secondary_muscle_groups
+                .iter()
+                .map(|m| muscle_name(m))
+                .collect(),
+            category: String::new(),
+        },
+        info: ExerciseInfo {
+            kind: exercise_type(template),
+            difficulty: None,
+            equipment: template.equipment.as_deref().map(equipment),
+        },
+        accepted: true,
+    }
+}
+
+/// Propose mappings for `unmapped` exercises that match a template title.
+///
+/// Titles are compared exactly first and then ignoring case and surrounding
+/// whitespace. Exercises without a matching template are left out.
+pub fn propose(unmapped: &[String], templates: &[ExerciseTemplate]) -> Vec<MappingProposal> {
+    let exact: HashMap<&str, &ExerciseTemplate> =
+        templates.iter().map(|t| (t.title.as_str(), t)).collect();
+    let normalized: HashMap<String, &ExerciseTemplate> = templates
+        .iter()
+        .map(|t| (normalize_exercise(&t.title), t))
+        .collect();
+    unmapped
+        .iter()
+        .filter_map(|ex| {
+            let template = exact
+                .get(ex.as_str())
+                .or_else(|| normalized.get(&normalize_exercise(ex)))?;
+            Some(proposal(ex, template))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(title: &str, kind: &str, primary: &str, secondary: &[&str]) -> ExerciseTemplate {
+        ExerciseTemplate {
+            id: title.into(),
+            title: title.into(),
+            kind: Some(kind.into()),
+            primary_muscle_group: Some(primary.into()),
+            secondary_muscle_groups: secondary.iter().map(|s| s.to_string()).collect(),
+            equipment: Some("machine".into()),
+        }
+    }
+
+    #[test]
+    fn translates_muscle_names() {
+        assert_eq!(muscle_name("upper_back"), "Upper Back");
+        assert_eq!(muscle_name("quadriceps"), "Quads");
+        assert_eq!(muscle_name("abdominals"), "Abs");
+        assert_eq!(muscle_name("chest"), "Chest");
+    }
+
+    #[test]
+    fn proposes_only_matching_exercises() {
+        let templates = vec![
+            template(
+                "Lat Pulldown (Cable)",
+                "weight_reps",
+                "lats",
+                &["biceps", "upper_back"],
+            ),
+            template("Leg Extension (Machine)", "weight_reps", "quadriceps", &[]),
+            template("Plank", "duration", "abdominals", &[]),
+        ];
+        let unmapped = vec![
+            "lat pulldown (cable)".to_string(),
+            "Leg Extension (Machine)".to_string(),
+            "Plank".to_string(),
+            "My Custom Lift".to_string(),
+        ];
+
+        let proposals = propose(&unmapped, &templates);
+
+        assert_eq!(proposals.len(), 3);
+        let lat = &proposals[0];
+        assert_eq!(lat.exercise, "lat pulldown (cable)");
+        assert_eq!(lat.template, "Lat Pulldown (Cable)");
+        assert_eq!(lat.mapping.primary, "Lats");
+        assert_eq!(lat.mapping.secondary, vec!["Biceps", "Upper Back"]);
+        assert_eq!(lat.info.kind, ExerciseType::Compound);
+        assert_eq!(lat.info.equipment, Some(Equipment::Machine));
+        assert!(lat.accepted);
+        assert_eq!(proposals[1].info.kind, ExerciseType::Isolation);
+        assert_eq!(proposals[2].info.kind, ExerciseType::Isometric);
+    }
+}
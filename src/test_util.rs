@@ -0,0 +1,27 @@
+//! Fixtures shared by the unit tests of several modules.
+
+use crate::import::DateFormat;
+use crate::{RawWorkoutRow, WorkoutEntry};
+
+/// A set of `exercise` done in a workout starting at `start`.
+///
+/// `start` is parsed like a CSV timestamp and also fills `date`; a bare
+/// `2024-01-01` date leaves `start` empty. Other raw fields can be set on the
+/// returned entry.
+pub fn set_entry(exercise: &str, start: &str, weight: f32, reps: u32) -> WorkoutEntry {
+    let parsed = DateFormat::Auto.parse(start);
+    WorkoutEntry {
+        date: parsed
+            .map(|s| s.date().format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| start.to_string()),
+        exercise: exercise.into(),
+        weight: Some(weight),
+        reps: Some(reps),
+        start: parsed,
+        raw: RawWorkoutRow {
+            start_time: start.into(),
+            exercise_title: exercise.into(),
+            ..RawWorkoutRow::default()
+        },
+    }
+}
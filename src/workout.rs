@@ -0,0 +1,197 @@
+//! Workout sessions assembled from the flat set rows.
+//!
+//! Hevy exports and the API both deliver one row per set. [`build_workouts`]
+//! groups them back into sessions with their exercises and sets in the order
+//! they were performed.
+
+use chrono::NaiveDateTime;
+use std::collections::HashMap;
+
+use crate::WorkoutEntry;
+use crate::import::{DateFormat, parse_api_time};
+use crate::store::workout_key;
+
+/// One exercise within a session together with its sets.
+#[derive(Debug, Clone)]
+pub struct WorkoutExercise {
+    pub name: String,
+    pub notes: Option<String>,
+    /// Exercises sharing a superset id were performed back to back.
+    pub superset_id: Option<String>,
+    /// Sets ordered by their set index.
+    pub sets: Vec<WorkoutEntry>,
+}
+
+/// A single training session.
+#[derive(Debug, Clone)]
+pub struct Workout {
+    /// Hevy workout id, or the start time to the minute for CSV imports (see
+    /// [`workout_key`]).
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub date: String,
+    pub start: Option<NaiveDateTime>,
+    pub end: Option<NaiveDateTime>,
+    /// Exercises in the order they were first logged.
+    pub exercises: Vec<WorkoutExercise>,
+}
+
+impl Workout {
+    /// Session length, if both timestamps are known and in order.
+    pub fn duration(&self) -> Option<chrono::Duration> {
+        let (start, end) = (self.start?, self.end?);
+        (end > start).then(|| end - start)
+    }
+
+    /// Session length in minutes.
+    pub fn minutes(&self) -> Option<f64> {
+        self.duration().map(|d| d.num_seconds() as f64 / 60.0)
+    }
+
+    /// Volume per minute of session time in lbs.
+    pub fn density(&self) -> Option<f64> {
+        self.minutes().map(|m| self.volume() as f64 / m)
+    }
+
+    pub fn sets_per_hour(&self) -> Option<f64> {
+        self.minutes().map(|m| self.set_count() as f64 * 60.0 / m)
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.exercises.iter().map(|e| e.sets.len()).sum()
+    }
+
+    /// Total weight times reps in lbs.
+    pub fn volume(&self) -> f32 {
+        self.exercises
+            .iter()
+            .flat_map(|e| &e.sets)
+            .filter_map(|s| s.volume())
+            .sum()
+    }
+}
+
+/// Parse `end_time` with the same layout that produced the entry's start.
+///
+/// Synced starts were converted to local time by [`parse_api_time`], so an
+/// end time is converted the same way when that reproduces the start.
+fn parse_end(e: &WorkoutEntry) -> Option<NaiveDateTime> {
+    let end = e.raw.end_time.as_deref()?;
+    let start = e.start?;
+    if parse_api_time(&e.raw.start_time) == Some(start) {
+        return parse_api_time(end);
+    }
+    DateFormat::KNOWN
+        .iter()
+        .find(|f| f.parse(&e.raw.start_time) == Some(start))
+        .and_then(|f| f.parse(end))
+}
+
+/// Group set rows into sessions, newest first.
+///
+/// Sets are grouped by [`workout_key`]. Within a session exercises keep the
+/// order they first appear in and sets are sorted by their index.
+pub fn build_workouts(entries: &[WorkoutEntry]) -> Vec<Workout> {
+    let mut workouts: Vec<Workout> = Vec::new();
+    let mut index: HashMap<String, usize> = HashMap::new();
+    for e in entries {
+        let key = workout_key(e);
+        let i = *index.entry(key.clone()).or_insert_with(|| {
+            workouts.push(Workout {
+                id: key,
+                title: e.raw.title.clone().unwrap_or_default(),
+                description: e.raw.description.clone().filter(|d| !d.is_empty()),
+                date: e.date.clone(),
+                start: e.start,
+                end: parse_end(e),
+                exercises: Vec::new(),
+            });
+            workouts.len() - 1
+        });
+        let workout = &mut workouts[i];
+        match workout
+            .exercises
+            .iter_mut()
+            .find(|x| x.name == e.exercise && x.superset_id == e.raw.superset_id)
+        {
+            Some(x) => x.sets.push(e.clone()),
+            None => workout.exercises.push(WorkoutExercise {
+                name: e.exercise.clone(),
+                notes: e.raw.exercise_notes.clone().filter(|n| !n.is_empty()),
+                superset_id: e.raw.superset_id.clone(),
+                sets: vec![e.clone()],
+            }),
+        }
+    }
+    for w in &mut workouts {
+        for x in &mut w.exercises {
+            x.sets.sort_by_key(|s| s.raw.set_index);
+        }
+    }
+    workouts.sort_by(|a, b| b.start.cmp(&a.start).then_with(|| b.date.cmp(&a.date)));
+    workouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::set_entry;
+
+    fn set(
+        title: &str,
+        start: &str,
+        exercise: &str,
+        idx: u32,
+        superset: Option<&str>,
+    ) -> WorkoutEntry {
+        let mut e = set_entry(exercise, start, 100.0, 5);
+        e.raw.title = Some(title.into());
+        e.raw.end_time = Some("26 Jul 2025, 08:11".into());
+        e.raw.superset_id = superset.map(str::to_string);
+        e.raw.set_index = Some(idx);
+        e
+    }
+
+    #[test]
+    fn groups_sets_into_sessions() {
+        let entries = vec![
+            set("Lower", "26 Jul 2025, 07:06", "Squat", 1, None),
+            set("Upper", "27 Jul 2025, 07:06", "Bench", 0, None),
+            set("Lower", "26 Jul 2025, 07:06", "Squat", 0, None),
+            set("Lower", "26 Jul 2025, 07:06", "Curl", 0, Some("1")),
+            set("Lower", "26 Jul 2025, 07:06", "Pushdown", 0, Some("1")),
+        ];
+
+        let workouts = build_workouts(&entries);
+
+        assert_eq!(workouts.len(), 2);
+        assert_eq!(workouts[0].title, "Upper");
+        let lower = &workouts[1];
+        assert_eq!(lower.set_count(), 4);
+        assert_eq!(lower.duration(), Some(chrono::Duration::minutes(65)));
+        assert!((lower.volume() - 2000.0).abs() < 1e-6);
+        let names: Vec<&str> = lower.exercises.iter().map(|x| x.name.as_str()).collect();
+        assert_eq!(names, vec!["Squat", "Curl", "Pushdown"]);
+        let squat_sets: Vec<Option<u32>> = lower.exercises[0]
+            .sets
+            .iter()
+            .map(|s| s.raw.set_index)
+            .collect();
+        assert_eq!(squat_sets, vec![Some(0), Some(1)]);
+        assert_eq!(lower.exercises[2].superset_id.as_deref(), Some("1"));
+    }
+
+    #[test]
+    fn synced_sessions_keep_their_end_time() {
+        // An offset other than the local one is converted before bucketing,
+        // which no CSV layout reproduces.
+        let mut e = set("Sync", "2025-07-26T07:06:00+09:30", "Squat", 0, None);
+        e.start = parse_api_time(&e.raw.start_time);
+        e.raw.end_time = Some("2025-07-26T08:11:00+09:30".into());
+
+        let workouts = build_workouts(&[e]);
+
+        assert_eq!(workouts[0].duration(), Some(chrono::Duration::minutes(65)));
+    }
+}